@@ -1,6 +1,8 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::VecDeque;
 
 use crate::codegen::ir::*;
+use crate::log_verbose;
+use crate::utils::logging::LogLevel;
 use crate::vm::bytecode::{self, ArgMode};
 use crate::vm::heap::HeapValue;
 use crate::vm::value::Value;
@@ -14,14 +16,14 @@ struct PatchEntry {
 /// NOTE: compiles all procedures' CFGs into Chunks of bytecode.
 #[derive(Default)]
 pub struct BytecodeEmitter {
-    /// NOTE: this is used as a stack of node locations to visit next during bytecode emission.
-    pending_node_ids: VecDeque<i32>,
-
-    /// NOTE: this is used as a stack of backpatching locations to fix jumps during bytecode emission.
+    /// NOTE: this is used as a stack of backpatching locations to fix jumps during bytecode emission. Nested if / while constructs resolve their own entries first, so the innermost pending jump is always on top.
     pending_patches: VecDeque<PatchEntry>,
 
     /// NOTE: stores temporary bytecode per CFG generated.
     temp_instructions: Vec<bytecode::Instruction>,
+
+    /// NOTE: controls the per-procedure progress messages, which only verbose runs show.
+    log_level: LogLevel,
 }
 
 fn convert_ir_arg_tag(arg: Region) -> ArgMode {
@@ -30,16 +32,19 @@ fn convert_ir_arg_tag(arg: Region) -> ArgMode {
         Region::TempStack => ArgMode::StackOffset,
         Region::ObjectHeap => ArgMode::HeapId,
         Region::Field => ArgMode::InstanceFieldId,
+        Region::Methods => ArgMode::ProcedureId,
         Region::Functions => ArgMode::ProcedureId,
         Region::Natives => ArgMode::NativeId,
         Region::BlockId => ArgMode::CodeOffset,
-        _ => ArgMode::Dud,
     }
 }
 
 impl BytecodeEmitter {
+    pub fn set_log_level(&mut self, level: LogLevel) {
+        self.log_level = level;
+    }
+
     pub fn reset_state(&mut self) {
-        self.pending_node_ids.clear();
         self.pending_patches.clear();
         self.temp_instructions.clear();
     }
@@ -52,61 +57,69 @@ impl BytecodeEmitter {
         self.pending_patches.push_back(patch);
     }
 
-    fn update_patch(&mut self) {
-        let next_jump_location = self.get_last_instruction_pos();
+    /// NOTE: An unconditional forward jump ends a branch (the if-block) whose own `JUMP_ELSE` must be resolved first, so it waits under the top entry.
+    fn start_branch_end_patch(&mut self, patch: PatchEntry) {
+        let branch_patch_opt = self.pending_patches.pop_back();
 
-        self.pending_patches.front_mut().unwrap().patching_value = next_jump_location;
-    }
-
-    fn update_backpatch(&mut self) {
-        let back_jump_location = self.get_last_instruction_pos();
+        self.pending_patches.push_back(patch);
 
-        self.pending_patches.front_mut().unwrap().instruction_pos = back_jump_location;
+        if let Some(branch_patch) = branch_patch_opt {
+            self.pending_patches.push_back(branch_patch);
+        }
     }
 
-    fn apply_patch(&mut self) {
-        if !self.pending_patches.is_empty() {
-            let is_backwards_patch = self.pending_patches.front().unwrap().is_backward;
+    fn patch_jump_at(&mut self, patch: PatchEntry) {
+        if patch.instruction_pos == -1 || patch.patching_value == -1 {
+            return;
+        }
 
-            if is_backwards_patch {
-                self.update_backpatch();
-            } else {
-                self.update_patch();
-            }
+        let target_ref: &mut bytecode::Instruction = self
+            .temp_instructions
+            .get_mut(patch.instruction_pos as usize)
+            .unwrap();
 
-            let next_patch = self.pending_patches.pop_front().unwrap();
+        if target_ref.is_valid_jump() {
+            return;
+        }
 
-            if next_patch.instruction_pos == -1 || next_patch.patching_value == -1 {
-                self.pending_patches.push_front(next_patch);
-                return;
-            }
+        match target_ref {
+            bytecode::Instruction::Jump(jump_target_loc) => {
+                jump_target_loc.1 = patch.patching_value;
+            },
+            bytecode::Instruction::JumpIf(_, jump_target_loc) => {
+                jump_target_loc.1 = patch.patching_value;
+            },
+            bytecode::Instruction::JumpElse(_, jump_target_loc) => {
+                jump_target_loc.1 = patch.patching_value;
+            },
+            _ => {},
+        }
+    }
 
-            // println!("BytecodeEmitter::apply_patch(): next_patch.instruction_pos = {}, current_instructions.len = {}", next_patch.instruction_pos, self.temp_instructions.len());
-            let target_ref: &mut bytecode::Instruction = self
-                .temp_instructions
-                .get_mut(next_patch.instruction_pos as usize)
-                .unwrap();
-
-            if target_ref.is_valid_jump() {
-                // println!(
-                //     "Omitted duplicate patch... is_backward={}, patching_value={}, instruction_pos{}",
-                //     next_patch.is_backward, next_patch.patching_value, next_patch.instruction_pos
-                // );
-                return;
-            }
+    /// NOTE: Resolves the innermost pending forward jump to the last emitted instruction, which is always a NOP target.
+    fn apply_patch(&mut self) {
+        if let Some(mut next_patch) = self.pending_patches.pop_back() {
+            next_patch.patching_value = self.get_last_instruction_pos();
+            self.patch_jump_at(next_patch);
+        }
+    }
 
-            match target_ref {
-                bytecode::Instruction::Jump(jump_target_loc) => {
-                    jump_target_loc.1 = next_patch.patching_value;
-                },
-                bytecode::Instruction::JumpIf(_, jump_target_loc) => {
-                    jump_target_loc.1 = next_patch.patching_value;
-                },
-                bytecode::Instruction::JumpElse(_, jump_target_loc) => {
-                    jump_target_loc.1 = next_patch.patching_value;
-                },
-                _ => {},
-            }
+    /// NOTE: Resolves a loop's back jump, which waits under the loop's exit `JUMP_ELSE`, to the loop's starting position.
+    fn apply_backpatch(&mut self) {
+        let loop_exit_patch_opt = self.pending_patches.pop_back();
+        let back_jump_patch_opt = self.pending_patches.pop_back();
+        let loop_begin_patch_opt = self.pending_patches.pop_back();
+
+        if let (Some(mut back_jump_patch), Some(loop_begin_patch)) = (back_jump_patch_opt, loop_begin_patch_opt)
+            && loop_begin_patch.is_backward {
+            back_jump_patch.patching_value = loop_begin_patch.patching_value;
+            self.patch_jump_at(back_jump_patch);
+        } else {
+            eprintln!("GenError: unmatched loop back-jump.");
+        }
+
+        if let Some(loop_exit_patch) = loop_exit_patch_opt {
+            self.pending_patches.push_back(loop_exit_patch);
         }
     }
 
@@ -118,6 +131,15 @@ impl BytecodeEmitter {
             Opcode::Pop => {
                 self.temp_instructions.push(bytecode::Instruction::Pop);
             },
+            Opcode::PushTrue => {
+                self.temp_instructions.push(bytecode::Instruction::PushTrue);
+            },
+            Opcode::PushFalse => {
+                self.temp_instructions.push(bytecode::Instruction::PushFalse);
+            },
+            Opcode::Not => {
+                self.temp_instructions.push(bytecode::Instruction::Not);
+            },
             Opcode::Add => {
                 self.temp_instructions.push(bytecode::Instruction::Add);
             },
@@ -146,6 +168,14 @@ impl BytecodeEmitter {
                 self.temp_instructions
                     .push(bytecode::Instruction::CompareGt);
             },
+            Opcode::CompareLe => {
+                self.temp_instructions
+                    .push(bytecode::Instruction::CompareLe);
+            },
+            Opcode::CompareGe => {
+                self.temp_instructions
+                    .push(bytecode::Instruction::CompareGe);
+            },
             Opcode::GenBeginLoop => {
                 self.start_backpatch(PatchEntry {
                     instruction_pos: -1,
@@ -157,7 +187,7 @@ impl BytecodeEmitter {
                 self.apply_patch();
             },
             Opcode::GenPatchBack => {
-                self.apply_patch();
+                self.apply_backpatch();
             },
             Opcode::Leave => {
                 self.temp_instructions
@@ -207,7 +237,7 @@ impl BytecodeEmitter {
             Opcode::Jump => {
                 self.temp_instructions
                     .push(bytecode::Instruction::Jump(converted_arg_0));
-                self.start_backpatch(PatchEntry {
+                self.start_branch_end_patch(PatchEntry {
                     instruction_pos: self.get_last_instruction_pos(),
                     patching_value: -1,
                     is_backward: false,
@@ -241,6 +271,12 @@ impl BytecodeEmitter {
                     converted_arg_1,
                 ));
             },
+            Opcode::GetField => {
+                self.temp_instructions.push(bytecode::Instruction::GetField(
+                    converted_arg_0,
+                    converted_arg_1,
+                ));
+            },
             Opcode::JumpIf => {
                 self.temp_instructions.push(bytecode::Instruction::JumpIf(
                     converted_arg_0,
@@ -323,38 +359,22 @@ impl BytecodeEmitter {
         temp_consts: &mut Vec<Value>,
         temp_cfg: &CFG,
     ) -> Option<bytecode::Chunk> {
-        let mut done_ids = HashSet::<i32>::new();
-
-        self.pending_node_ids
-            .push_back(if temp_cfg.get_root().is_some() { 0 } else { -1 });
-
-        while !self.pending_node_ids.is_empty() {
-            let next_node_id = self.pending_node_ids.pop_back().unwrap();
-
-            if next_node_id == -1 || done_ids.contains(&next_node_id) {
-                continue;
-            }
-
-            let (block_gen_ok, block_truthy_id, block_falsy_id) =
+        // NOTE: The IR emitter only ever appends steps to the newest block, so emitting blocks by ID keeps the source order that the jump patches rely on.
+        for next_node_id in 0..temp_cfg.get_node_count() {
+            let (block_gen_ok, _, _) =
                 self.emit_ir_block_code(temp_cfg.get_node_ref(next_node_id).unwrap());
-            done_ids.insert(next_node_id);
 
             if !block_gen_ok {
                 eprintln!("GenError: failed to emit block.");
                 return None;
             }
-
-            if block_truthy_id != -1 && block_falsy_id == -1 {
-                // A single-truthy-child block always implies a post-if block... Prioritize the child's generation to occur after the if/else blocks are done.
-                self.pending_node_ids.push_front(block_truthy_id);
-            } else {
-                // Normal case: prioritize if/else branches to generate after the pre-branch block. The previous code handles the follow-up case.
-                self.pending_node_ids.push_back(block_falsy_id);
-                self.pending_node_ids.push_back(block_truthy_id);
-            }
         }
 
-        self.apply_patch();
+        if !self.pending_patches.is_empty() {
+            eprintln!("GenError: {} jump(s) were left unpatched.", self.pending_patches.len());
+            self.pending_patches.clear();
+            return None;
+        }
 
         let mut temp_chunk_constants = Vec::<Value>::new();
         let mut temp_chunk_instructions = Vec::<bytecode::Instruction>::new();
@@ -386,8 +406,11 @@ impl BytecodeEmitter {
 
             temp_chunk.as_ref()?;
 
-            println!("loaded bytecode of proc-CFG #{cfg_id}");
-            temp_procedures.push(bytecode::Procedure::new(temp_chunk.unwrap(), cfg_id));
+            log_verbose!(self.log_level, "loaded bytecode of proc-CFG #{cfg_id}");
+
+            let temp_frame_slots = cfg_list.get(cfg_id as usize).unwrap().get_frame_slots();
+
+            temp_procedures.push(bytecode::Procedure::new(temp_chunk.unwrap(), cfg_id, temp_frame_slots));
         }
 
         let moved_preloadables = std::mem::take(temp_heap_preloadables);
@@ -395,3 +418,40 @@ impl BytecodeEmitter {
         Some(bytecode::Program::new(temp_procedures, moved_preloadables, main_fun_id))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::bytecode::Instruction;
+
+    #[test]
+    fn member_regions_map_to_member_args() {
+        assert!(convert_ir_arg_tag(Region::Field) == ArgMode::InstanceFieldId);
+        assert!(convert_ir_arg_tag(Region::Methods) == ArgMode::ProcedureId);
+    }
+
+    #[test]
+    fn member_accesses_use_member_args() {
+        let source = "class Counter {\n    public let count: int;\n\n    public ctor(start: int) {\n        count = start;\n        return;\n    }\n\n    public met bump(): int {\n        count = count + 1;\n        return count;\n    }\n\n    public met twice(): int {\n        bump();\n        return bump();\n    }\n}\n\nfun main(): int {\n    let c: Counter = Counter(1);\n\n    if c.twice() != 3 {\n        return 1;\n    }\n\n    return c.count - 3;\n}\n";
+        let program = crate::compile("./test.loxie", source).expect("test source should compile");
+
+        let mut field_arg_count = 0;
+        let mut method_call_count = 0;
+
+        for (_, _, step) in program.instructions() {
+            match step {
+                Instruction::GetField(_, field_arg) | Instruction::Replace(field_arg, _) if field_arg.0 == ArgMode::InstanceFieldId => {
+                    field_arg_count += 1;
+                },
+                Instruction::InstanceCall(_, callee_arg, _) => {
+                    assert!(callee_arg.0 == ArgMode::ProcedureId);
+                    method_call_count += 1;
+                },
+                _ => {},
+            }
+        }
+
+        assert_eq!(field_arg_count, 3);
+        assert_eq!(method_call_count, 3);
+    }
+}
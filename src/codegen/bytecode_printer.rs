@@ -24,6 +24,12 @@ fn disassemble_proc_chunk(chunk: &Chunk) {
             Instruction::Pop => {
                 println!("POP");
             },
+            Instruction::PushTrue => {
+                println!("PUSH_TRUE");
+            },
+            Instruction::PushFalse => {
+                println!("PUSH_FALSE");
+            },
             Instruction::MakeHeapValue(arg_0) => {
                 print!("MAKE_HEAP_VAL ");
                 disassemble_op_arg(arg_0);
@@ -40,11 +46,20 @@ fn disassemble_proc_chunk(chunk: &Chunk) {
                 disassemble_op_arg(arg_1);
                 println!();
             },
+            Instruction::GetField(arg_0, arg_1) => {
+                print!("GET_FIELD ");
+                disassemble_op_arg(arg_0);
+                disassemble_op_arg(arg_1);
+                println!();
+            },
             Instruction::Neg(arg_0) => {
                 print!("NEG ");
                 disassemble_op_arg(arg_0);
                 println!();
             },
+            Instruction::Not => {
+                println!("NOT");
+            },
             Instruction::Inc(arg_0) => {
                 print!("INC ");
                 disassemble_op_arg(arg_0);
@@ -79,6 +94,12 @@ fn disassemble_proc_chunk(chunk: &Chunk) {
             Instruction::CompareGt => {
                 println!("CMP_GT");
             },
+            Instruction::CompareLe => {
+                println!("CMP_LE");
+            },
+            Instruction::CompareGe => {
+                println!("CMP_GE");
+            },
             Instruction::JumpIf(arg_0, arg_1) => {
                 print!("JMP_IF ");
                 disassemble_op_arg(arg_0);
@@ -134,9 +155,9 @@ pub fn disassemble_program(program: &Program) {
         let proc_id = proc_entry.get_id();
 
         if proc_id == main_proc_id {
-            println!("proc (main):\n");
+            println!("proc (main) with {} frame slots:\n", proc_entry.get_frame_slots());
         } else {
-            println!("proc #{proc_id}:\n");
+            println!("proc #{proc_id} with {} frame slots:\n", proc_entry.get_frame_slots());
         }
 
         disassemble_proc_chunk(proc_entry.get_chunk());
@@ -0,0 +1,181 @@
+use std::collections::VecDeque;
+
+use crate::codegen::ir::*;
+
+/**
+ * ### ABOUT
+ * Validates the links of a procedure's CFG before bytecode emission, so miswired nodes are reported at compile time instead of surfacing as an `IllegalInstruction` at runtime.
+ * ### RULES
+ * 1. Every truthy / falsy link must name a node of the same CFG.
+ * 2. A node ending in a jump must have a successor link for that jump.
+ * 3. Every node with real instructions must be reachable from the root through links or by falling through to the next node.
+ */
+fn is_jump_exit(opcode: Opcode) -> bool {
+    matches!(opcode, Opcode::JumpIf | Opcode::JumpElse | Opcode::Jump)
+}
+
+fn is_terminal_exit(opcode: Opcode) -> bool {
+    matches!(opcode, Opcode::Return | Opcode::Leave)
+}
+
+/// NOTE: Pseudo-ops like `#GEN_PATCH` and `NOP`s emit no runnable work, so nodes made of only these may be left unreachable e.g an if-block's empty merge point after both branches return.
+fn has_real_steps(node: &Node) -> bool {
+    node.get_steps().iter().any(|step| {
+        !matches!(step.get_opcode(), Opcode::Nop | Opcode::GenBeginLoop | Opcode::GenPatch | Opcode::GenPatchBack)
+    })
+}
+
+fn get_exit_opcode(node: &Node) -> Option<Opcode> {
+    node.get_steps().iter().rev().map(|step| step.get_opcode()).find(|opcode| {
+        !matches!(opcode, Opcode::Nop | Opcode::GenBeginLoop | Opcode::GenPatch | Opcode::GenPatchBack)
+    })
+}
+
+fn check_node_links(cfg: &CFG, cfg_id: usize, node_id: i32) -> bool {
+    let node_count = cfg.get_node_count();
+    let node_ref = cfg.get_node_ref(node_id).unwrap();
+
+    for link_id in [node_ref.get_truthy_id(), node_ref.get_falsy_id()].into_iter().flatten() {
+        if link_id < 0 || link_id >= node_count {
+            eprintln!("GenError: CFG #{cfg_id} has node #{node_id} linked to node #{link_id}, but only nodes #0 to #{} exist.", node_count - 1);
+            return false;
+        }
+    }
+
+    let exit_opcode_opt = get_exit_opcode(node_ref);
+    let has_no_links = node_ref.get_truthy_id().is_none() && node_ref.get_falsy_id().is_none();
+
+    if let Some(exit_opcode) = exit_opcode_opt
+        && is_jump_exit(exit_opcode)
+        && has_no_links {
+        eprintln!("GenError: CFG #{cfg_id} has node #{node_id} ending in '{}' without any successor link, so the jump would dangle.", exit_opcode.get_name());
+        return false;
+    }
+
+    true
+}
+
+fn find_reached_nodes(cfg: &CFG) -> Vec<bool> {
+    let node_count = cfg.get_node_count();
+
+    if node_count == 0 {
+        return Vec::new();
+    }
+
+    let mut reached = vec![false; node_count as usize];
+    let mut next_ids = VecDeque::<i32>::from([0]);
+    reached[0] = true;
+
+    while let Some(next_id) = next_ids.pop_front() {
+        let node_ref = cfg.get_node_ref(next_id).unwrap();
+        let falls_through = !get_exit_opcode(node_ref).is_some_and(|opcode| is_terminal_exit(opcode) || opcode == Opcode::Jump);
+        let fallthrough_id_opt = if falls_through && next_id + 1 < node_count { Some(next_id + 1) } else { None };
+
+        for successor_id in [node_ref.get_truthy_id(), node_ref.get_falsy_id(), fallthrough_id_opt].into_iter().flatten() {
+            if !reached[successor_id as usize] {
+                reached[successor_id as usize] = true;
+                next_ids.push_back(successor_id);
+            }
+        }
+    }
+
+    reached
+}
+
+fn find_unreachable_node(cfg: &CFG) -> Option<i32> {
+    let reached = find_reached_nodes(cfg);
+
+    (0..cfg.get_node_count()).find(|node_id| !reached[*node_id as usize] && has_real_steps(cfg.get_node_ref(*node_id).unwrap()))
+}
+
+/// NOTE: Checks if running a procedure could go past its last node, which happens when that node is reachable but doesn't end in a `RETURN` or `LEAVE`.
+pub fn can_run_past_end(cfg: &CFG) -> bool {
+    let last_node_id = cfg.get_node_count() - 1;
+
+    if last_node_id < 0 {
+        return true;
+    }
+
+    let ends_in_exit = get_exit_opcode(cfg.get_node_ref(last_node_id).unwrap()).is_some_and(is_terminal_exit);
+
+    !ends_in_exit && find_reached_nodes(cfg)[last_node_id as usize]
+}
+
+pub fn check_cfg(cfg: &CFG, cfg_id: usize) -> bool {
+    for node_id in 0..cfg.get_node_count() {
+        if !check_node_links(cfg, cfg_id, node_id) {
+            return false;
+        }
+    }
+
+    if let Some(lost_node_id) = find_unreachable_node(cfg) {
+        eprintln!("GenError: CFG #{cfg_id} has node #{lost_node_id} with instructions that no path from the entry node reaches.");
+        return false;
+    }
+
+    true
+}
+
+pub fn check_all_cfgs(cfg_list: &CFGStorage) -> bool {
+    cfg_list.iter().enumerate().all(|(cfg_id, cfg)| check_cfg(cfg, cfg_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_cfg(nodes: Vec<Node>) -> CFG {
+        let mut cfg = CFG::new();
+
+        for node in nodes {
+            cfg.add_node(node);
+        }
+
+        cfg
+    }
+
+    fn return_step() -> Instruction {
+        Instruction::Unary(Opcode::Return, (Region::Immediate, 0))
+    }
+
+    #[test]
+    fn linked_cfgs_pass() {
+        let cfg = make_cfg(vec![
+            Node::new(vec![Instruction::Unary(Opcode::Jump, (Region::BlockId, 1))], 1, -1),
+            Node::new(vec![return_step()], -1, -1),
+        ]);
+
+        assert!(check_cfg(&cfg, 0));
+        assert!(!can_run_past_end(&cfg));
+    }
+
+    #[test]
+    fn out_of_range_links_fail() {
+        let cfg = make_cfg(vec![
+            Node::new(vec![Instruction::Unary(Opcode::JumpElse, (Region::TempStack, 0))], 1, 5),
+            Node::new(vec![return_step()], -1, -1),
+        ]);
+
+        assert!(!check_cfg(&cfg, 0));
+    }
+
+    #[test]
+    fn dangling_jumps_fail() {
+        let cfg = make_cfg(vec![
+            Node::new(vec![Instruction::Unary(Opcode::Jump, (Region::BlockId, -1))], -1, -1),
+            Node::new(vec![return_step()], -1, -1),
+        ]);
+
+        assert!(!check_cfg(&cfg, 0));
+    }
+
+    #[test]
+    fn unreachable_steps_fail() {
+        let cfg = make_cfg(vec![
+            Node::new(vec![return_step()], -1, -1),
+            Node::new(vec![return_step()], -1, -1),
+        ]);
+
+        assert!(!check_cfg(&cfg, 0));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::frontend::ast::*;
+use crate::frontend::token::TokenType;
+use crate::semantics::types::OperatorTag;
+use crate::vm::value::Value;
+
+/**
+ * ### ABOUT
+ * Folds constant expressions of int, long, and bool literals into a single `Value` at compile time, e.g `2 * 3 + 1` into `Int(7)` or `2 < 1` into `Bool(false)`. Float literals may only be compared e.g `0.0 == -0.0`.
+ * ### RULES
+ * 1. Every fold uses the same `Value` operations as the engine, so a folded expression gives what the runtime would.
+ * ### CAVEATS
+ * Any name, call, or other literal operand makes the expression non-constant. Float arithmetic is left to the runtime, so a folded expression never gives a float.
+ */
+struct ConstEvaluator<'a> {
+    source: &'a str,
+
+    /// INFO: source line of the first literal folded, which locates the whole expression for diagnostics.
+    first_line_no: usize,
+}
+
+impl ExprVisitor<'_, Option<Value>> for ConstEvaluator<'_> {
+    fn visit_primitive(&mut self, e: &Primitive) -> Option<Value> {
+        let literal_token = e.get_token();
+        let literal_lexeme = literal_token.to_lexeme_str(self.source)?;
+
+        let literal_value = match literal_token.tag {
+            TokenType::LiteralBool => Value::Bool(literal_lexeme == "true"),
+            TokenType::LiteralInt => Value::Int(literal_lexeme.parse::<i32>().ok()?),
+            TokenType::LiteralLong => Value::Long(literal_lexeme.trim_end_matches('L').parse::<i64>().ok()?),
+            TokenType::LiteralFloat => Value::Float(literal_lexeme.parse::<f32>().ok()?),
+            _ => {
+                return None;
+            },
+        };
+
+        if self.first_line_no == 0 {
+            self.first_line_no = literal_token.line_no;
+        }
+
+        Some(literal_value)
+    }
+
+    #[allow(unused_variables)]
+    fn visit_call(&mut self, e: &Call) -> Option<Value> {
+        None
+    }
+
+    #[allow(unused_variables)]
+    fn visit_index(&mut self, e: &Index) -> Option<Value> {
+        None
+    }
+
+    fn visit_unary(&mut self, e: &Unary) -> Option<Value> {
+        let mut inner_value = e.get_inner().accept_visitor_const(self)?;
+
+        match e.get_operator() {
+            OperatorTag::Negate => {
+                inner_value.negate();
+
+                Some(inner_value)
+            },
+            OperatorTag::Not => {
+                if let Value::Bool(flag) = inner_value {
+                    return Some(Value::Bool(!flag));
+                }
+
+                None
+            },
+            _ => None,
+        }
+    }
+
+    fn visit_binary(&mut self, e: &Binary) -> Option<Value> {
+        let lhs_value = e.get_lhs().accept_visitor_const(self)?;
+        let rhs_value = e.get_rhs().accept_visitor_const(self)?;
+
+        // NOTE: mixed operand types are left alone for the analyzer to report.
+        if !lhs_value.check_type_code(&rhs_value) {
+            return None;
+        }
+
+        if let Value::Float(_) = lhs_value
+            && !e.get_operator().is_comparison() {
+            return None;
+        }
+
+        let folded_value = match e.get_operator() {
+            OperatorTag::Times => lhs_value.mul(&rhs_value),
+            OperatorTag::Slash => lhs_value.div(&rhs_value).unwrap_or(Value::Empty()),
+            OperatorTag::Plus => lhs_value.add(&rhs_value),
+            OperatorTag::Minus => lhs_value.sub(&rhs_value),
+            OperatorTag::Equality => Value::Bool(lhs_value.is_equal(&rhs_value)),
+            OperatorTag::Inequality => Value::Bool(lhs_value.is_unequal(&rhs_value)),
+            OperatorTag::LessThan => Value::Bool(lhs_value.is_lesser(&rhs_value)),
+            OperatorTag::GreaterThan => Value::Bool(lhs_value.is_greater(&rhs_value)),
+            OperatorTag::LessEqual => Value::Bool(lhs_value.is_lesser_equal(&rhs_value)),
+            OperatorTag::GreaterEqual => Value::Bool(lhs_value.is_greater_equal(&rhs_value)),
+            _ => Value::Empty(),
+        };
+
+        // NOTE: `Value` arithmetic gives `Empty` for illegal cases like `1 / 0`, which are left for the runtime to report.
+        if let Value::Empty() = folded_value {
+            return None;
+        }
+
+        Some(folded_value)
+    }
+}
+
+pub fn eval_const_expr(e: &dyn Expr, source: &str) -> Option<Value> {
+    let mut evaluator = ConstEvaluator { source, first_line_no: 0 };
+
+    match e.accept_visitor_const(&mut evaluator)? {
+        Value::Float(_) => None,
+        folded_value => Some(folded_value),
+    }
+}
+
+/// NOTE: Gives the folded truth value of a literal-only condition along with its source line, or `None` if the condition depends on runtime values.
+pub fn eval_const_condition(e: &dyn Expr, source: &str) -> Option<(bool, usize)> {
+    let mut evaluator = ConstEvaluator { source, first_line_no: 0 };
+
+    if let Value::Bool(flag) = e.accept_visitor_const(&mut evaluator)? {
+        return Some((flag, evaluator.first_line_no));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::token::Token;
+    use crate::token_from;
+
+    const SOURCE: &str = "2 * 3 + 1 * n";
+
+    fn literal_at(tag: TokenType, start: usize) -> Box<dyn Expr> {
+        Box::new(Primitive::new(token_from!(tag, start, 1, 1, start + 1)))
+    }
+
+    #[test]
+    fn literal_arithmetic_folds_to_an_int() {
+        let product = Binary::new(literal_at(TokenType::LiteralInt, 0), literal_at(TokenType::LiteralInt, 4), OperatorTag::Times);
+        let sum = Binary::new(Box::new(product), literal_at(TokenType::LiteralInt, 8), OperatorTag::Plus);
+
+        assert!(matches!(eval_const_expr(&sum, SOURCE), Some(Value::Int(7))));
+    }
+
+    #[test]
+    fn named_operands_are_not_constant() {
+        let product = Binary::new(literal_at(TokenType::LiteralInt, 8), literal_at(TokenType::Identifier, 12), OperatorTag::Times);
+        let sum = Binary::new(literal_at(TokenType::LiteralInt, 0), Box::new(product), OperatorTag::Plus);
+
+        assert!(eval_const_expr(&sum, SOURCE).is_none());
+        assert!(eval_const_condition(&sum, SOURCE).is_none());
+    }
+}
@@ -20,10 +20,14 @@ pub enum Opcode {
     LoadConst,
     Push,
     Pop,
+    PushTrue,
+    PushFalse,
     MakeHeapValue,
     MakeHeapObject,
     Replace,
+    GetField,
     Neg,
+    Not,
     Inc,
     Dec,
     Add,
@@ -37,6 +41,8 @@ pub enum Opcode {
     CompareNe,
     CompareLt,
     CompareGt,
+    CompareLe,
+    CompareGe,
     JumpIf,
     JumpElse,
     Jump,
@@ -54,10 +60,14 @@ impl Opcode {
             Self::LoadConst => 1,
             Self::Push => 1,
             Self::Pop => 0,
+            Self::PushTrue => 0,
+            Self::PushFalse => 0,
             Self::MakeHeapValue => 1,
             Self::MakeHeapObject => 1,
             Self::Replace => 2,
+            Self::GetField => 2,
             Self::Neg => 1,
+            Self::Not => 0,
             Self::Inc => 1,
             Self::Dec => 1,
             Self::Add => 0,
@@ -71,6 +81,8 @@ impl Opcode {
             Self::CompareNe => 0,
             Self::CompareLt => 0,
             Self::CompareGt => 0,
+            Self::CompareLe => 0,
+            Self::CompareGe => 0,
             Self::JumpIf => 2,
             Self::JumpElse => 2,
             Self::Jump => 1,
@@ -89,10 +101,14 @@ impl Opcode {
             Self::LoadConst => 1,
             Self::Push => 1,
             Self::Pop => -1,
+            Self::PushTrue => 1,
+            Self::PushFalse => 1,
             Self::MakeHeapValue => 1,
             Self::MakeHeapObject => 1,
             Self::Replace => 0,
+            Self::GetField => 1,
             Self::Neg => 0,
+            Self::Not => 0,
             Self::Inc => 0,
             Self::Dec => 0,
             Self::Add => -1,
@@ -106,6 +122,8 @@ impl Opcode {
             Self::CompareNe => -1,
             Self::CompareLt => -1,
             Self::CompareGt => -1,
+            Self::CompareLe => -1,
+            Self::CompareGe => -1,
             Self::JumpIf => -1,
             Self::JumpElse => -1,
             Self::Jump => 0,
@@ -123,10 +141,14 @@ impl Opcode {
             Self::LoadConst => "LOAD_CONST",
             Self::Push => "PUSH",
             Self::Pop => "POP",
+            Self::PushTrue => "PUSH_TRUE",
+            Self::PushFalse => "PUSH_FALSE",
             Self::MakeHeapValue => "MAKE_HEAP_VAL",
             Self::MakeHeapObject => "MAKE_HEAP_OBJ",
             Self::Replace => "REPLACE",
+            Self::GetField => "GET_FIELD",
             Self::Neg => "NEG",
+            Self::Not => "NOT",
             Self::Inc => "INC",
             Self::Dec => "DEC",
             Self::Add => "ADD",
@@ -140,6 +162,8 @@ impl Opcode {
             Self::CompareNe => "CMP_NE",
             Self::CompareLt => "CMP_LT",
             Self::CompareGt => "CMP_GT",
+            Self::CompareLe => "CMP_LE",
+            Self::CompareGe => "CMP_GE",
             Self::JumpIf => "JMP_IF",
             Self::JumpElse => "JMP_ELSE",
             Self::Jump => "JMP",
@@ -158,6 +182,9 @@ pub fn ast_op_to_ir_op(arg: OperatorTag) -> Opcode {
         OperatorTag::Noop => Opcode::Nop,
         // OperatorTag::Access => Opcode::Nop,
         OperatorTag::Negate => Opcode::Neg,
+        OperatorTag::Not => Opcode::Not,
+        OperatorTag::Increment | OperatorTag::PostIncrement => Opcode::Inc,
+        OperatorTag::Decrement | OperatorTag::PostDecrement => Opcode::Dec,
         OperatorTag::Times => Opcode::Mul,
         OperatorTag::Slash => Opcode::Div,
         OperatorTag::Plus => Opcode::Add,
@@ -166,6 +193,8 @@ pub fn ast_op_to_ir_op(arg: OperatorTag) -> Opcode {
         OperatorTag::Inequality => Opcode::CompareNe,
         OperatorTag::LessThan => Opcode::CompareLt,
         OperatorTag::GreaterThan => Opcode::CompareGt,
+        OperatorTag::LessEqual => Opcode::CompareLe,
+        OperatorTag::GreaterEqual => Opcode::CompareGe,
         OperatorTag::Assign => Opcode::Replace,
         _ => Opcode::Nop,
     }
@@ -286,6 +315,12 @@ impl Node {
 pub struct CFG {
     nodes: Vec<Node>,
     count: i32,
+
+    /// NOTE: tracks the most stack slots the procedure's frame would use at once from its base, counting its arguments.
+    frame_slots: i32,
+
+    /// NOTE: counts the argument slots at the frame's base, which the procedure's own steps must never pop.
+    param_count: i32,
 }
 
 impl CFG {
@@ -294,6 +329,8 @@ impl CFG {
         Self {
             nodes: Vec::new(),
             count: 0,
+            frame_slots: 0,
+            param_count: 0,
         }
     }
 
@@ -301,6 +338,23 @@ impl CFG {
         self.count
     }
 
+    pub fn get_frame_slots(&self) -> i32 {
+        self.frame_slots
+    }
+
+    pub fn get_param_count(&self) -> i32 {
+        self.param_count
+    }
+
+    pub fn set_param_count(&mut self, count: i32) {
+        self.param_count = count;
+    }
+
+    /// NOTE: Grows the frame size to cover a stack offset from the frame's base, where offset `0` is the first slot.
+    pub fn note_stack_offset(&mut self, offset: i32) {
+        self.frame_slots = self.frame_slots.max(offset + 1);
+    }
+
     pub fn get_root(&self) -> Option<&Node> {
         self.nodes.first()
     }
@@ -362,7 +416,10 @@ impl CFG {
             return;
         }
 
-        let target_ref = self.nodes.get_mut(from_id as usize).unwrap();
+        // NOTE: An out of range source has no node to link, so it is skipped instead of panicking.
+        let Some(target_ref) = self.nodes.get_mut(from_id as usize) else {
+            return;
+        };
 
         if target_ref.get_truthy_id().is_none() {
             target_ref.set_left_neighbor_id(to_id);
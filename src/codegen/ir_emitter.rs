@@ -1,16 +1,19 @@
 use std::collections::HashMap;
 use std::collections::VecDeque;
 
+use crate::codegen::cfg_checker::can_run_past_end;
+use crate::codegen::const_eval::{eval_const_condition, eval_const_expr};
 use crate::codegen::ir::*;
 use crate::codegen::layouts::ClassLayout;
 use crate::codegen::layouts::LayoutTable;
 use crate::frontend::ast::*;
 use crate::frontend::token::*;
-use crate::semantics::types::OperatorTag;
+use crate::semantics::modules::ModuleTable;
+use crate::semantics::types::{AccessFlag, OperatorTag};
 use crate::compiler::driver::SourceIndexedAST;
 use crate::token_from;
 use crate::utils::bundle::NativeBrief;
-use crate::vm::heap::HeapValue;
+use crate::vm::heap::{HeapValue, MAX_HEAP_SLOTS};
 use crate::vm::value::Value;
 
 fn translate_escaped_char(lexeme: &str) -> char {
@@ -28,10 +31,43 @@ fn translate_escaped_char(lexeme: &str) -> char {
         't' => '\t',
         'r' => '\r',
         'n' => '\n',
+        '\\' => '\\',
+        '"' => '"',
+        'x' => {
+            let hex_digits: String = c_sequence.take(2).collect();
+
+            u8::from_str_radix(&hex_digits, 16).unwrap_or(0) as char
+        },
         _ => '\0',
     }
 }
 
+/// NOTE: the lexer already rejects malformed `\xNN` escapes, so any bad leftovers here just decode to `'\0'`.
+fn translate_escaped_varchar(lexeme: &str) -> String {
+    let mut result = String::with_capacity(lexeme.len());
+    let mut c_sequence = lexeme.chars();
+
+    while let Some(c) = c_sequence.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        let c_1 = c_sequence.next().unwrap_or('\0');
+
+        if c_1 != 'x' {
+            result.push(translate_escaped_char(&format!("\\{c_1}")));
+            continue;
+        }
+
+        let hex_digits: String = c_sequence.by_ref().take(2).collect();
+
+        result.push(translate_escaped_char(&format!("\\x{hex_digits}")));
+    }
+
+    result
+}
+
 type IRLinkPair = (i32, i32);
 pub type IRResult = (CFGStorage, Vec<Vec<Value>>, i32, Vec<HeapValue>);
 type FuncInfo = (Locator, i32);
@@ -41,6 +77,25 @@ pub struct IREmitter<'b> {
     class_var_names: HashMap<String, (String, Locator)>,
     fun_locals: HashMap<String, Locator>,
     fun_locations: HashMap<String, FuncInfo>,
+
+    /// NOTE: maps each plain function's name to its declared result type name, so a chained access like `make_box().get()` can find the result's class layout.
+    fun_result_types: HashMap<String, String>,
+
+    /// NOTE: maps each plain function's name to its procedure ID & arity, so embedders can run it directly. Constructors & methods are left out as they need an instance.
+    function_exports: HashMap<String, (i32, i32)>,
+
+    /// NOTE: holds each module's imports & top-level names like the analyzer's, so plain & qualified names reach the same functions & constants.
+    modules: ModuleTable,
+
+    /// NOTE: maps each TU's source ID to its module name, which is `""` for the main program.
+    module_names: HashMap<i32, String>,
+
+    /// NOTE: names the module of the TU being emitted.
+    current_module: String,
+
+    /// NOTE: maps module-level constant names to their folded values, which get loaded as per-chunk constants on each use.
+    module_const_values: HashMap<String, Value>,
+
     result: CFGStorage,
 
     /// NOTE: contains a Vec of corresponding constant Values per bytecode Chunk.
@@ -48,6 +103,12 @@ pub struct IREmitter<'b> {
 
     proto_heap_vals: Vec<HeapValue>,
 
+    /// NOTE: maps the text of read-only varchar literals to their shared heap ID across the whole program.
+    shared_varchar_ids: HashMap<String, i32>,
+
+    /// NOTE: Indicates that the next visited literal is a direct argument of a native which never mutates it, so it may be shared.
+    ctx_readonly_arg: bool,
+
     /// NOTE: contains `(from: i32, to: i32)` tuples to process after CFG node generation... Each "proto" link is applied to a CFG node by `from` before exhaustion which clears this Vec- The function's CFG is done by then!
     proto_links: Vec<IRLinkPair>,
 
@@ -56,10 +117,13 @@ pub struct IREmitter<'b> {
     /// NOTE: Stores the class name (if applicable) of a visited declaration.
     ctx_class_name: String,
 
-    native_registry: &'b HashMap<&'static str, NativeBrief>,
+    native_registry: &'b HashMap<String, NativeBrief>,
 
     ctx_instance_locator: Locator,
 
+    /// NOTE: holds the temporary of a chained method call's object e.g the result of `obj.next()` in `obj.next().get()`, which that call replaces with its own result.
+    ctx_chained_object: Option<Locator>,
+
     /// NOTE: tracks the current mapped source's ID for checking when to refresh the source to the next one by ID.
     current_source_id: i32,
 
@@ -81,20 +145,29 @@ pub struct IREmitter<'b> {
 }
 
 impl<'b> IREmitter<'b> {
-    pub fn new(old_src: &str, native_mapping: &'b HashMap<&'static str, NativeBrief>) -> Self {
+    pub fn new(old_src: &str, native_mapping: &'b HashMap<String, NativeBrief>) -> Self {
         Self {
             class_layouts: LayoutTable::default(),
             class_var_names: HashMap::new(),
             fun_locals: HashMap::new(),
             fun_locations: HashMap::new(),
+            fun_result_types: HashMap::new(),
+            function_exports: HashMap::new(),
+            modules: ModuleTable::default(),
+            module_names: HashMap::new(),
+            current_module: String::new(),
+            module_const_values: HashMap::new(),
             result: CFGStorage::new(),
             proto_constants: Vec::<Vec<Value>>::new(),
             proto_heap_vals: Vec::<HeapValue>::new(),
+            shared_varchar_ids: HashMap::new(),
+            ctx_readonly_arg: false,
             proto_links: Vec::<IRLinkPair>::new(),
             source_copy: String::from(old_src),
             ctx_class_name: String::default(),
             native_registry: native_mapping,
             ctx_instance_locator: (Region::TempStack, -1),
+            ctx_chained_object: None,
             current_source_id: -1,
             relative_stack_offset: -1,
             relative_local_count: 0,
@@ -123,10 +196,9 @@ impl<'b> IREmitter<'b> {
     fn lookup_method_as_fun(&self, class_name: &str, method_name: &str) -> Option<i32> {
         let class_name_s = class_name.to_string();
 
-        if let Some(layout_ref) = self.class_layouts.get(&class_name_s) {
-            if let Some(method_mapping) = layout_ref.get_real_method_id(method_name.to_string()) {
-                return Some(method_mapping.1);
-            }
+        if let Some(layout_ref) = self.class_layouts.get(&class_name_s)
+            && let Some(method_mapping) = layout_ref.get_real_method_id(method_name.to_string()) {
+            return Some(method_mapping.1);
         }
 
         None
@@ -149,6 +221,17 @@ impl<'b> IREmitter<'b> {
         Some(next_fun_id as i32)
     }
 
+    fn report_unresolved_call(&mut self, e: &Call, reason: &str) {
+        let callee_token_opt = e.get_callee().get_token_opt();
+        let callee_line_no = callee_token_opt.map_or(0, |callee_token| callee_token.line_no);
+        let callee_name = callee_token_opt
+            .and_then(|callee_token| callee_token.to_lexeme_str(&self.source_copy))
+            .unwrap_or("?");
+
+        eprintln!("Oops: At line {callee_line_no}, the callee '{callee_name}' {reason}.");
+        self.has_error = true;
+    }
+
     fn enter_fun_scope(&mut self) {
         self.result.push(CFG::new());
         self.proto_constants.push(Vec::new());
@@ -156,8 +239,31 @@ impl<'b> IREmitter<'b> {
         self.reset_relative_offset(-1);
     }
 
+    /// NOTE: A body without a final `return` would run past its procedure's code, so it gets an implicit `LEAVE` for constructors or else a `RETURN` of an empty value.
+    fn help_emit_implicit_return(&mut self) {
+        if !can_run_past_end(self.result.last().unwrap()) {
+            return;
+        }
+
+        if self.result.last().unwrap().get_node_count() == 0 {
+            self.result.last_mut().unwrap().add_node(Node::new(Vec::new(), -1, -1));
+        }
+
+        if self.in_ctor {
+            self.emit_step(Instruction::Nonary(Opcode::Leave));
+        } else {
+            let empty_locator = self.record_proto_constant(Value::Empty());
+            self.emit_step(Instruction::Unary(Opcode::Return, empty_locator));
+        }
+    }
+
     fn leave_fun_scope(&mut self) {
+        if let Some(current_cfg) = self.result.last_mut() {
+            current_cfg.set_param_count(self.relative_arg_count);
+        }
+
         self.fun_locals.clear();
+        self.class_var_names.clear();
         self.relative_arg_count = 0;
         self.relative_local_count = 0;
     }
@@ -166,6 +272,15 @@ impl<'b> IREmitter<'b> {
         self.fun_locals.insert(name, item);
     }
 
+    /// NOTE: Instance params are tracked like instance locals, so method calls on them can find the class layout. See `visit_variable_decl()`.
+    fn record_param_locator(&mut self, name: String, type_name: String, item: Locator) {
+        if self.class_layouts.contains_key(&type_name) {
+            self.class_var_names.insert(name, (type_name, item));
+        } else {
+            self.record_varname_locator(name, item);
+        }
+    }
+
     fn record_proto_constant(&mut self, item: Value) -> Locator {
         let mut proto_id = 0;
 
@@ -188,29 +303,68 @@ impl<'b> IREmitter<'b> {
     }
 
     fn lookup_locator_of(&self, opt_class_name: &str, name: &str) -> Option<Locator> {
-        if !opt_class_name.is_empty() {
-            if let Some(class_layout_ref) = self.class_layouts.get(opt_class_name) {
-                if let Some(field_id) = class_layout_ref.get_member_id(name.to_string()) {
-                    return Some((Region::Field, field_id));
-                } else if let Some((met_internal_id, met_external_id)) = class_layout_ref.get_real_method_id(name.to_string()) {
-                    return Some(
-                        if self.ctx_instance_locator.1 != -1 { (Region::Functions, met_external_id) } else { (Region::Methods, met_internal_id) }
-                    );
-                }
+        if !opt_class_name.is_empty()
+            && let Some(class_layout_ref) = self.class_layouts.get(opt_class_name) {
+            if let Some(field_id) = class_layout_ref.get_member_id(name.to_string()) {
+                return Some((Region::Field, field_id));
+            } else if let Some((met_internal_id, met_external_id)) = class_layout_ref.get_real_method_id(name.to_string()) {
+                // NOTE: constructors (named by their class) & static methods never take an instance, so they're plain function calls.
+                let is_instanceless = name == opt_class_name || class_layout_ref.is_static_method(name);
+
+                return Some(
+                    if self.ctx_instance_locator.1 != -1 || is_instanceless { (Region::Functions, met_external_id) } else { (Region::Methods, met_internal_id) }
+                );
             }
         }
  
         if self.fun_locals.contains_key(name) {
             return Some(self.fun_locals.get(name).unwrap().clone());
+        } else if let Some((_, class_var_locator)) = self.class_var_names.get(name) {
+            return Some(class_var_locator.clone());
         } else if self.native_registry.contains_key(name) {
             return Some((Region::Natives, self.native_registry.get(name).unwrap().id));
-        } else if self.fun_locations.contains_key(name) {
-            return Some(self.fun_locations.get(name).unwrap().clone().0);
+        } else if let Some((fun_locator, _)) = self.fun_locations.get(&self.modules.resolve_global_key(&self.current_module, name)) {
+            return Some(fun_locator.clone());
         }
 
         None
     }
 
+    /// NOTE: Finds the global name of a qualified access like `math.abs`, whose object must name a module imported here instead of a variable or class.
+    fn find_module_member_key(&self, access: &Binary) -> Option<String> {
+        let module_name = access.get_lhs().get_token_opt()?.to_lexeme_str(&self.source_copy)?;
+        let member_name = access.get_rhs().get_token_opt()?.to_lexeme_str(&self.source_copy)?;
+
+        let is_module_access = access.get_lhs().get_operator() == OperatorTag::Noop
+            && self.modules.is_imported_by(&self.current_module, module_name)
+            && !self.fun_locals.contains_key(module_name)
+            && !self.class_var_names.contains_key(module_name);
+
+        is_module_access.then(|| ModuleTable::qualify(module_name, member_name))
+    }
+
+    /// NOTE: Lowers a qualified name like `math.abs` just like a plain name, except that a foreign stub is found by its native's plain name.
+    fn help_emit_module_member(&mut self, access: &Binary, member_key: String) -> Option<Locator> {
+        if let Some((fun_locator, _)) = self.fun_locations.get(&member_key) {
+            return Some(fun_locator.clone());
+        }
+
+        if let Some(const_value) = self.module_const_values.get(&member_key).cloned() {
+            let const_locator = self.record_proto_constant(const_value);
+
+            if !self.skip_emit {
+                self.emit_step(Instruction::Unary(Opcode::LoadConst, const_locator.clone()));
+                self.update_relative_offset(1);
+            }
+
+            return Some(const_locator);
+        }
+
+        let member_name = access.get_rhs().get_token_opt()?.to_lexeme_str(&self.source_copy)?;
+
+        Some((Region::Natives, self.native_registry.get(member_name)?.id))
+    }
+
     fn record_proto_link(&mut self, from_id: i32, to_id: i32) {
         self.proto_links.push((from_id, to_id));
     }
@@ -226,12 +380,37 @@ impl<'b> IREmitter<'b> {
         self.proto_links.clear();
     }
 
+    /// NOTE: An if-check that folds to a constant needs no `JUMP_ELSE`, so only the taken branch is emitted and the dead one is dropped.
+    fn help_emit_folded_if(&mut self, s: &If, check_flag: bool) -> bool {
+        let pre_if_block_id: i32 = self.result.last().unwrap().get_node_count() - 1;
+        let taken_body = if check_flag { s.get_truthy_body() } else { s.get_falsy_body() };
+
+        if taken_body.accept_visitor(self) {
+            self.record_proto_link(pre_if_block_id, pre_if_block_id + 1);
+            self.apply_proto_links();
+        } else if self.has_error {
+            eprintln!("Oops: failed to generate folded if-block");
+            return false;
+        }
+
+        true
+    }
+
     fn get_relative_offset(&self) -> i32 {
         self.relative_stack_offset
     }
 
     fn reset_relative_offset(&mut self, arg: i32) {
         self.relative_stack_offset = arg;
+        self.help_note_frame_slots();
+    }
+
+    fn help_note_frame_slots(&mut self) {
+        let current_offset = self.relative_stack_offset;
+
+        if let Some(current_cfg) = self.result.last_mut() {
+            current_cfg.note_stack_offset(current_offset);
+        }
     }
 
     fn get_relative_local_count(&self) -> i32 {
@@ -252,6 +431,42 @@ impl<'b> IREmitter<'b> {
 
     fn update_relative_offset(&mut self, count: i32) {
         self.relative_stack_offset += count;
+        self.help_note_frame_slots();
+    }
+
+    /// NOTE: Pops every value pushed since the marks were taken, e.g block-local variables before leaving the block or looping back. Since `POP` releases heap references, values allocated each loop iteration are not leaked.
+    fn help_emit_block_local_pops(&mut self, local_count_mark: i32, offset_mark: i32) {
+        let doomed_value_n = self.get_relative_offset() - offset_mark;
+
+        for _ in 0..doomed_value_n {
+            self.emit_step(Instruction::Nonary(Opcode::Pop));
+        }
+
+        self.relative_local_count = local_count_mark;
+        self.reset_relative_offset(offset_mark);
+    }
+
+    /// NOTE: Jumps pop their tested value, so the condition must end up as a fresh temporary on top. Most conditions push one already, but some like `x = true` only yield a live variable's locator, so a copy is pushed for those.
+    fn help_emit_condition_temp(&mut self, check: &dyn Expr) -> Option<Locator> {
+        let pre_check_offset = self.get_relative_offset();
+        let check_locator = check.accept_visitor(self)?;
+
+        if self.get_relative_offset() == pre_check_offset {
+            self.emit_step(Instruction::Unary(Opcode::Push, check_locator));
+            self.update_relative_offset(1);
+        }
+
+        Some((Region::TempStack, self.get_relative_offset()))
+    }
+
+    /// NOTE: Bools have their own push steps instead of constant slots, so they're pushed even when skipping emission e.g for an assignment's RHS, which then pops the temporary.
+    fn help_emit_bool(&mut self, flag: bool) -> Locator {
+        let push_opcode = if flag { Opcode::PushTrue } else { Opcode::PushFalse };
+
+        self.emit_step(Instruction::Nonary(push_opcode));
+        self.update_relative_offset(1);
+
+        (Region::TempStack, self.get_relative_offset())
     }
 
     fn get_next_heap_id(&mut self) -> i32 {
@@ -263,6 +478,91 @@ impl<'b> IREmitter<'b> {
         self.result.last_mut().unwrap().add_instruction_recent(step);
     }
 
+    /// NOTE: Prefix steps yield the updated value while postfix steps yield the old value, so the copy is pushed after or before the INC / DEC respectively.
+    fn help_emit_step_unary(&mut self, e: &Unary) -> Option<Locator> {
+        let expr_op = e.get_operator();
+
+        let old_skip_emit = self.skip_emit;
+        self.skip_emit = true;
+        let var_locator_opt = e.get_inner().accept_visitor(self);
+        self.skip_emit = old_skip_emit;
+
+        var_locator_opt.as_ref()?;
+
+        let var_locator = var_locator_opt.unwrap();
+
+        if var_locator.0 != Region::TempStack {
+            eprintln!("Oops: Unsupported operand for codegen of '{}', expected a local variable.", expr_op.as_symbol());
+            self.has_error = true;
+            return None;
+        }
+
+        let step_opcode = ast_op_to_ir_op(expr_op.clone());
+
+        match expr_op {
+            OperatorTag::Increment | OperatorTag::Decrement => {
+                self.emit_step(Instruction::Unary(step_opcode, var_locator.clone()));
+                self.emit_step(Instruction::Unary(Opcode::Push, var_locator));
+            },
+            _ => {
+                self.emit_step(Instruction::Unary(Opcode::Push, var_locator.clone()));
+                self.emit_step(Instruction::Unary(step_opcode, var_locator));
+            },
+        }
+
+        self.update_relative_offset(1);
+
+        Some((Region::TempStack, self.get_relative_offset()))
+    }
+
+    /// NOTE: Lowers sugar like `s[i]` into a call of the named native, pushing each operand as an arg. The native must be registered, even if no stub of it is imported.
+    fn help_emit_intrinsic_call(&mut self, native_name: &str, args: &[&dyn Expr], line_no: usize) -> Option<Locator> {
+        let Some(native_brief) = self.native_registry.get(native_name) else {
+            eprintln!("GenError: At line {line_no}, the indexing needs the native '{native_name}', which is not registered.");
+            self.has_error = true;
+            return None;
+        };
+
+        let (native_id, is_readonly_native) = (native_brief.id, !native_brief.mutates_args);
+        let old_skip_emit = self.skip_emit;
+        let result_locator = (Region::TempStack, self.get_relative_offset() + 1);
+
+        self.skip_emit = false;
+
+        for arg_ref in args {
+            self.ctx_readonly_arg = is_readonly_native && arg_ref.get_operator() == OperatorTag::Noop;
+            let arg_locator_opt = arg_ref.accept_visitor(self);
+            self.ctx_readonly_arg = false;
+
+            if arg_locator_opt.is_none() {
+                self.skip_emit = old_skip_emit;
+                return None;
+            }
+        }
+
+        self.emit_step(Instruction::Unary(Opcode::NativeCall, (Region::Natives, native_id)));
+        self.update_relative_offset(1 - args.len() as i32);
+        self.skip_emit = old_skip_emit;
+
+        Some(result_locator)
+    }
+
+    /// NOTE: An assignment like `s[i] = c` becomes a call of the varchar store native, which fails on an out-of-range position & otherwise leaves a `true` result as the assignment's value. In a chain like `s[i] = c = x`, the inner assignment runs first so its target can be pushed as the char arg.
+    fn help_emit_index_assign(&mut self, e: &Binary) -> Option<Locator> {
+        let index_lhs = e.get_lhs().as_index()?;
+        let inner_assign_opt = e.get_rhs().as_binary().filter(|inner_assign| inner_assign.get_operator() == OperatorTag::Assign);
+
+        let char_arg = if let Some(inner_assign) = inner_assign_opt {
+            inner_assign.accept_visitor(self)?;
+
+            inner_assign.get_lhs()
+        } else {
+            e.get_rhs()
+        };
+
+        self.help_emit_intrinsic_call("intrin_varchar_store", &[index_lhs.get_target(), index_lhs.get_index(), char_arg], index_lhs.get_bracket_token().line_no)
+    }
+
     fn help_emit_assign(&mut self, e: &Binary) -> Option<Locator> {
         let lhs_arity = ast_op_to_ir_op(e.get_lhs().get_operator()).arity();
         let rhs_arity = ast_op_to_ir_op(e.get_rhs().get_operator()).arity();
@@ -283,11 +583,14 @@ impl<'b> IREmitter<'b> {
         lhs_locator = lhs_locator_opt.unwrap();
         self.skip_emit = false;
 
-        self.emit_step(Instruction::Binary(
-            Opcode::Replace,
-            lhs_locator.clone(),
-            rhs_locator.clone(),
-        ));
+        // NOTE: a self-assignment like `a = a` would replace a slot with itself, so it's elided as a no-op.
+        if lhs_locator != rhs_locator {
+            self.emit_step(Instruction::Binary(
+                Opcode::Replace,
+                lhs_locator.clone(),
+                rhs_locator.clone(),
+            ));
+        }
 
         // NOTE: If the inner-expr is an assignment, the source temporary can be popped if it'll appear at the stack's top BUT ABOVE all local var-values!
         let current_alive_local_count = self.get_relative_local_count();
@@ -301,7 +604,135 @@ impl<'b> IREmitter<'b> {
         Some(lhs_locator)
     }
 
+    /// NOTE: Finds the class of an access's object e.g `Purse` for `wallet.get_purse()`, using the declared types of variables, fields, and results. A class name itself names its class, as for static accesses & constructor calls.
+    fn find_object_class_name(&self, e: &dyn Expr) -> Option<String> {
+        let class_name = match e.get_operator() {
+            OperatorTag::Noop => {
+                let name = e.get_token_opt()?.to_lexeme_str(&self.source_copy)?;
+
+                if let Some(field_class_name) = self.find_own_field_class_name(name) {
+                    field_class_name
+                } else {
+                    self.class_var_names.get(name).map_or(name.to_string(), |(var_class_name, _)| var_class_name.clone())
+                }
+            },
+            OperatorTag::Call => {
+                let callee = e.as_call()?.get_callee();
+
+                if let Some(member_key) = callee.as_binary().and_then(|callee_access| self.find_module_member_key(callee_access)) {
+                    self.fun_result_types.get(&member_key)?.clone()
+                } else if let Some(callee_access) = callee.as_binary() {
+                    self.find_member_type_name(callee_access)?
+                } else {
+                    let callee_name = callee.get_token_opt()?.to_lexeme_str(&self.source_copy)?;
+
+                    // NOTE: a native giving `any` gives its first argument's type, like the analyzer assumes.
+                    match self.fun_result_types.get(&self.modules.resolve_global_key(&self.current_module, callee_name)).map(String::as_str) {
+                        Some("any") => self.find_object_class_name(e.as_call()?.get_args().first()?.as_ref())?,
+                        Some(result_type_name) => result_type_name.to_string(),
+                        None => callee_name.to_string(),
+                    }
+                }
+            },
+            OperatorTag::Access => self.find_member_type_name(e.as_binary()?)?,
+            _ => {
+                return None;
+            },
+        };
+
+        self.class_layouts.contains_key(&class_name).then_some(class_name)
+    }
+
+    /// NOTE: Finds the class of a class-typed field of the current class named by a plain name e.g `Leaf` for `next`. Fields come first like in `lookup_locator_of`.
+    fn find_own_field_class_name(&self, name: &str) -> Option<String> {
+        let class_layout_ref = self.class_layouts.get(&self.ctx_class_name)?;
+        class_layout_ref.get_member_id(name.to_string())?;
+
+        let field_type_name = class_layout_ref.get_member_type_name(name)?;
+
+        self.class_layouts.contains_key(field_type_name).then(|| field_type_name.to_string())
+    }
+
+    fn find_member_type_name(&self, access: &Binary) -> Option<String> {
+        let object_class_name = self.find_object_class_name(access.get_lhs())?;
+        let member_name = access.get_rhs().get_token_opt()?.to_lexeme_str(&self.source_copy)?;
+
+        Some(self.class_layouts.get(&object_class_name)?.get_member_type_name(member_name)?.to_string())
+    }
+
+    /// NOTE: Moves a chain step's result down into its object's temporary, so a chain like `a.b().c()` leaves only one value on the stack.
+    fn help_emit_chain_result(&mut self, object_locator: Locator) -> Option<Locator> {
+        let step_result_locator = (Region::TempStack, self.get_relative_offset());
+
+        self.emit_step(Instruction::Binary(Opcode::Replace, object_locator.clone(), step_result_locator));
+        self.emit_step(Instruction::Nonary(Opcode::Pop));
+        self.update_relative_offset(-1);
+
+        Some(object_locator)
+    }
+
+    /// NOTE: Lowers an access whose object is a call or another access e.g `wallet.get_purse().add(1)`, by pushing that object as a temporary first. A field is read from it right away, but a method's call replaces it in `visit_call()`.
+    fn help_emit_chained_access(&mut self, e: &Binary) -> Option<Locator> {
+        let member_name_token = e.get_rhs().get_token_opt().unwrap_or(token_from!(TokenType::Unknown, 0, 0, 0, 0));
+        let access_expr_line_no = member_name_token.line_no;
+        let member_name = member_name_token.to_lexeme_str(&self.source_copy).unwrap_or("").to_string();
+
+        let Some(object_class_name) = self.find_object_class_name(e.get_lhs()) else {
+            eprintln!("Oops: At line {access_expr_line_no}, the object of a chained access has no known class- Cannot determine its layout information.");
+            self.has_error = true;
+            return None;
+        };
+
+        self.skip_emit = false;
+        let mut object_locator = e.get_lhs().accept_visitor(self)?;
+
+        // NOTE: an own field's object was just pushed, so its temporary is used instead of the field itself.
+        if object_locator.0 == Region::Field {
+            object_locator = (Region::TempStack, self.get_relative_offset());
+        }
+
+        let object_layout = self.class_layouts.get(&object_class_name).unwrap();
+
+        if object_layout.is_static_method(&member_name) {
+            eprintln!("Oops: At line {access_expr_line_no}, the static method '{member_name}' must be called by its class name, not through an instance.");
+            self.has_error = true;
+            return None;
+        }
+
+        if let Some(real_method_fun_id) = self.lookup_method_as_fun(&object_class_name, &member_name) {
+            self.ctx_instance_locator = object_locator.clone();
+            self.ctx_chained_object = Some(object_locator);
+
+            return Some((Region::Methods, real_method_fun_id));
+        }
+
+        let Some(read_field_id) = object_layout.get_member_id(member_name.clone()) else {
+            eprintln!("Oops: At line {access_expr_line_no}, class '{object_class_name}' has no member '{member_name}' for a chained access.");
+            self.has_error = true;
+            return None;
+        };
+
+        self.emit_step(Instruction::Binary(Opcode::GetField, object_locator.clone(), (Region::Field, read_field_id)));
+        self.update_relative_offset(1);
+
+        self.help_emit_chain_result(object_locator)
+    }
+
     fn help_emit_access(&mut self, e: &Binary) -> Option<Locator> {
+        // NOTE: a field's object lives in the current instance instead of a stack slot, so it's pushed as a temporary like a chain's object e.g `next.get()`.
+        let lhs_is_own_field = e.get_lhs().get_token_opt()
+            .and_then(|lhs_token| lhs_token.to_lexeme_str(&self.source_copy))
+            .is_some_and(|lhs_name| self.find_own_field_class_name(lhs_name).is_some());
+
+        if e.get_lhs().get_operator() != OperatorTag::Noop || lhs_is_own_field {
+            return self.help_emit_chained_access(e);
+        }
+
+        if let Some(member_key) = self.find_module_member_key(e) {
+            return self.help_emit_module_member(e, member_key);
+        }
+
+        let old_skip_emit = self.skip_emit;
         self.skip_emit = true;
 
         let instance_name_token = e.get_lhs().get_token_opt().unwrap_or(token_from!(TokenType::Unknown, 0, 0, 0, 0));
@@ -324,6 +755,10 @@ impl<'b> IREmitter<'b> {
         let instance_name_lexeme = instance_name_token.to_lexeme_str(&self.source_copy).unwrap_or("");
         let instance_class_info_opt = self.class_var_names.get(instance_name_lexeme);
 
+        if instance_class_info_opt.is_none() && self.class_layouts.contains_key(instance_name_lexeme) {
+            return self.help_emit_static_access(instance_name_lexeme.to_string(), &method_name_token);
+        }
+
         if instance_class_info_opt.is_none() {
             eprintln!("Oops: At line {access_expr_line_no}, no valid LHS class name exists- Cannot determine the object's layout information.");
             self.has_error = true;
@@ -344,20 +779,62 @@ impl<'b> IREmitter<'b> {
         let method_name = instance_method_name_opt.unwrap().to_string();
 
         if let Some(real_method_fun_id) = self.lookup_method_as_fun(&class_info.0, &method_name) {
+            if self.class_layouts.get(&class_info.0).unwrap().is_static_method(&method_name) {
+                return Some((Region::Functions, real_method_fun_id));
+            }
+
             self.ctx_instance_locator = class_info.1;
 
             return Some((Region::Methods, real_method_fun_id));
         } else if let Some(read_field_id) = self.lookup_locator_of(&class_info.0, &method_name) {
-            return Some(read_field_id);
+            // NOTE: Assignment targets keep the field locator, but reading `obj.field` must push the field from that object instead of the current instance.
+            if old_skip_emit {
+                return Some(read_field_id);
+            }
+
+            self.emit_step(Instruction::Binary(Opcode::GetField, class_info.1, read_field_id));
+            self.update_relative_offset(1);
+
+            return Some((Region::TempStack, self.get_relative_offset()));
+        }
+
+        None
+    }
+
+    /// NOTE: `ClassName.met` only names a static method, which is lowered to a plain `CALL` since there's no instance to pass.
+    fn help_emit_static_access(&mut self, class_name: String, method_name_token: &Token) -> Option<Locator> {
+        self.skip_emit = false;
+
+        let method_name = method_name_token.to_lexeme_str(&self.source_copy).unwrap_or("").to_string();
+        let is_static_method = self.class_layouts.get(&class_name).unwrap().is_static_method(&method_name);
+
+        if let Some(real_method_fun_id) = self.lookup_method_as_fun(&class_name, &method_name)
+            && is_static_method {
+            return Some((Region::Functions, real_method_fun_id));
         }
 
+        eprintln!("Oops: At line {}, '{}' is not a static method of class '{}'.", method_name_token.line_no, &method_name, &class_name);
+        self.has_error = true;
+
         None
     }
 
+    /// NOTE: Finds the `equals` method of the LHS's class for an `==` or `!=` e.g `a == b` where `a` is a `Point` with `met equals(other: Point): bool`.
+    fn find_equals_method_id(&self, e: &Binary) -> Option<i32> {
+        if !matches!(e.get_operator(), OperatorTag::Equality | OperatorTag::Inequality) {
+            return None;
+        }
+
+        let lhs_class_name = self.find_object_class_name(e.get_lhs())?;
+
+        self.lookup_method_as_fun(&lhs_class_name, "equals")
+    }
+
     fn help_emit_bin_normal(&mut self, e: &Binary) -> Option<Locator> {
         let expr_opcode = ast_op_to_ir_op(e.get_operator());
 
         let result_locator = (Region::TempStack, self.get_relative_offset() + 1);
+        let equals_method_id_opt = self.find_equals_method_id(e);
 
         self.skip_emit = false;
         let lhs_locator_opt_2 = e.get_lhs().accept_visitor(self);
@@ -366,49 +843,91 @@ impl<'b> IREmitter<'b> {
         let rhs_locator_opt_2 = e.get_rhs().accept_visitor(self);
         rhs_locator_opt_2.as_ref()?;
 
+        // NOTE: instances with an `equals` method are compared by calling it on the LHS like a chained call e.g `a.equals(b)`, so `!=` flips its result after.
+        if let Some(equals_method_id) = equals_method_id_opt {
+            self.emit_step(Instruction::Ternary(Opcode::InstanceCall, result_locator.clone(), (Region::Functions, equals_method_id), (Region::Immediate, 1)));
+            self.help_emit_chain_result(result_locator.clone());
+
+            if e.get_operator() == OperatorTag::Inequality {
+                self.emit_step(Instruction::Nonary(Opcode::Not));
+            }
+
+            return Some(result_locator);
+        }
+
         self.emit_step(Instruction::Nonary(expr_opcode));
         self.update_relative_offset(expr_opcode.get_stack_delta());
 
         Some(result_locator)
     }
 
-    pub fn emit_all_ir(&mut self, ast_tops: &VecDeque<SourceIndexedAST>, source_map: &HashMap<i32, String>) -> Option<IRResult> {
+    pub fn take_function_exports(&mut self) -> HashMap<String, (i32, i32)> {
+        std::mem::take(&mut self.function_exports)
+    }
+
+    pub fn set_module_names(&mut self, module_names: HashMap<i32, String>) {
+        self.module_names = module_names;
+    }
+
+    fn switch_source(&mut self, source_id: i32, source_map: &HashMap<i32, String>) {
+        if source_id != self.current_source_id {
+            self.source_copy = source_map.get(&source_id).unwrap().clone();
+            self.current_source_id = source_id;
+            self.current_module = self.module_names.get(&source_id).cloned().unwrap_or_default();
+        }
+    }
+
+    /// NOTE: Tracks every declaration's CFG ID, layout, and signature before any body is emitted, so calls may target procedures declared later.
+    pub fn track_all_decls(&mut self, ast_tops: &VecDeque<SourceIndexedAST>, source_map: &HashMap<i32, String>) -> bool {
         self.set_prepass_flag(true);
 
         for (source_id, temp) in ast_tops {
-            if *source_id != self.current_source_id {
-                self.source_copy = source_map.get(source_id).unwrap().clone();
-                self.current_source_id = *source_id;
-            }
+            self.switch_source(*source_id, source_map);
 
             if !temp.accept_visitor(self) {
                 eprintln!("Oops: failed to track declaration");
-                return None;
+                return false;
             }
         }
 
         self.set_prepass_flag(false);
 
-        for (source_id_2, temp) in ast_tops {
-            if *source_id_2 != self.current_source_id {
-                self.source_copy = source_map.get(source_id_2).unwrap().clone();
-                self.current_source_id = *source_id_2;
-            }
+        true
+    }
 
-            if !temp.accept_visitor(self) {
-                eprintln!("Oops: failed to generate function from declaration");
-                return None;
-            }
+    /// NOTE: Emits one tracked declaration's procedures, which lets the driver drop each AST right after its code is made.
+    pub fn emit_decl_ir(&mut self, source_id: i32, decl_ast: &dyn Stmt, source_map: &HashMap<i32, String>) -> bool {
+        self.switch_source(source_id, source_map);
+
+        if !decl_ast.accept_visitor(self) {
+            eprintln!("Oops: failed to generate function from declaration");
+            return false;
         }
 
-        let saved_main_id = self.main_id;
+        true
+    }
 
-        Some((
+    pub fn take_ir_result(&mut self) -> IRResult {
+        (
             std::mem::take(&mut self.result),
             std::mem::take(&mut self.proto_constants),
-            saved_main_id,
+            self.main_id,
             std::mem::take(&mut self.proto_heap_vals),
-        ))
+        )
+    }
+
+    pub fn emit_all_ir(&mut self, ast_tops: &VecDeque<SourceIndexedAST>, source_map: &HashMap<i32, String>) -> Option<IRResult> {
+        if !self.track_all_decls(ast_tops, source_map) {
+            return None;
+        }
+
+        for (source_id, temp) in ast_tops {
+            if !self.emit_decl_ir(*source_id, temp.as_ref(), source_map) {
+                return None;
+            }
+        }
+
+        Some(self.take_ir_result())
     }
 }
 
@@ -419,20 +938,7 @@ impl<'evl3> ExprVisitor<'evl3, Option<Locator>> for IREmitter<'evl3> {
         let literal_token_tag = literal_token_ref.tag;
 
         match literal_token_tag {
-            TokenType::LiteralBool => {
-                let temp_flag = literal_lexeme == "true";
-                let temp_flag_locator = self.record_proto_constant(Value::Bool(temp_flag));
-
-                if !self.skip_emit {
-                    self.emit_step(Instruction::Unary(
-                        Opcode::LoadConst,
-                        temp_flag_locator.clone(),
-                    ));
-                    self.update_relative_offset(1);
-                }
-
-                Some(temp_flag_locator)
-            }
+            TokenType::LiteralBool => Some(self.help_emit_bool(literal_lexeme == "true")),
             TokenType::LiteralChar => {
                 let parsed_char = translate_escaped_char(literal_lexeme);
                 let temp_char_locator = self.record_proto_constant(Value::Char(parsed_char as u8));
@@ -475,14 +981,56 @@ impl<'evl3> ExprVisitor<'evl3, Option<Locator>> for IREmitter<'evl3> {
 
                 Some(temp_float_locator)
             },
+            TokenType::LiteralLong => {
+                let temp_long_opt = literal_lexeme.trim_end_matches('L').parse::<i64>();
+
+                // NOTE: the analyzer already rejects long literals out of range, so this only guards against a skipped check.
+                if temp_long_opt.is_err() {
+                    eprintln!("Oops: long literal '{literal_lexeme}' is out of range.");
+                    self.has_error = true;
+                    return None;
+                }
+
+                let temp_long_locator = self.record_proto_constant(Value::Long(temp_long_opt.unwrap()));
+
+                if !self.skip_emit {
+                    self.emit_step(Instruction::Unary(
+                        Opcode::LoadConst,
+                        temp_long_locator.clone(),
+                    ));
+                    self.update_relative_offset(1);
+                }
+
+                Some(temp_long_locator)
+            },
             TokenType::LiteralVarchar => {
-                let temp_varchar = literal_lexeme.to_string();
+                let temp_varchar = translate_escaped_varchar(literal_lexeme);
+                let is_shareable = std::mem::take(&mut self.ctx_readonly_arg);
+                let shared_heap_id_opt = if is_shareable { self.shared_varchar_ids.get(&temp_varchar).copied() } else { None };
+
+                let temp_varchar_heap_id = shared_heap_id_opt.unwrap_or_else(|| self.get_next_heap_id());
 
-                let temp_varchar_heap_id = self.get_next_heap_id();
+                // NOTE: only the first literal past the limit is reported, as every later one would repeat the same error.
+                if temp_varchar_heap_id >= MAX_HEAP_SLOTS {
+                    if temp_varchar_heap_id == MAX_HEAP_SLOTS {
+                        eprintln!("GenError: At line {}, the varchar literal needs heap-id-{temp_varchar_heap_id} but only {MAX_HEAP_SLOTS} heap slots can exist- Use fewer distinct varchar literals.", literal_token_ref.line_no);
+                    }
+
+                    self.has_error = true;
+                    return None;
+                }
                 let temp_varchar_locator = self.record_proto_constant(Value::HeapRef(temp_varchar_heap_id));
 
-                if !self.skip_emit {
+                // NOTE: a reserved heap ID always needs its preloaded varchar, even when the literal is directly used as an operand under `skip_emit`.
+                if shared_heap_id_opt.is_none() {
+                    if is_shareable {
+                        self.shared_varchar_ids.insert(temp_varchar.clone(), temp_varchar_heap_id);
+                    }
+
                     self.proto_heap_vals.push(HeapValue::Varchar(temp_varchar));
+                }
+
+                if !self.skip_emit {
                     self.emit_step(Instruction::Unary(Opcode::Push, (Region::ObjectHeap, temp_varchar_heap_id)));
                     self.update_relative_offset(1);
                 }
@@ -492,10 +1040,37 @@ impl<'evl3> ExprVisitor<'evl3, Option<Locator>> for IREmitter<'evl3> {
             TokenType::Identifier => {
                 let named_locator_opt = self.lookup_locator_of(&self.ctx_class_name, literal_lexeme);
 
+                // NOTE: locals & fields shadow a module constant of the same name, so it's only used for otherwise unresolved names.
+                if named_locator_opt.is_none()
+                    && let Some(const_value) = self.module_const_values.get(&self.modules.resolve_global_key(&self.current_module, literal_lexeme)).cloned() {
+                    if let Value::Bool(const_flag) = const_value {
+                        return Some(self.help_emit_bool(const_flag));
+                    }
+
+                    let const_locator = self.record_proto_constant(const_value);
+
+                    if !self.skip_emit {
+                        self.emit_step(Instruction::Unary(Opcode::LoadConst, const_locator.clone()));
+                        self.update_relative_offset(1);
+                    }
+
+                    return Some(const_locator);
+                }
+
                 named_locator_opt.as_ref()?;
 
                 let named_locator = named_locator_opt.unwrap().clone();
 
+                // NOTE: the analyzer only allows a function name outside of a callee for a `callback` argument, which passes the function's procedure ID as an int.
+                if !self.skip_emit && named_locator.0 == Region::Functions {
+                    let callback_id_locator = self.record_proto_constant(Value::Int(named_locator.1));
+
+                    self.emit_step(Instruction::Unary(Opcode::LoadConst, callback_id_locator.clone()));
+                    self.update_relative_offset(1);
+
+                    return Some(callback_id_locator);
+                }
+
                 // NOTE: avoid emitting function values for now, see `visit_call()`!
                 if !self.skip_emit {
                     match named_locator.0 {
@@ -524,18 +1099,35 @@ impl<'evl3> ExprVisitor<'evl3, Option<Locator>> for IREmitter<'evl3> {
         let callee_locator_opt = e.get_callee().accept_visitor(self);
         self.skip_emit = old_skip_emit;
 
-        callee_locator_opt.as_ref()?;
+        // NOTE: The prepass records every function before any body is generated, so forward & mutually recursive calls resolve here... Anything else is a bug to report.
+        let Some(callee_locator) = callee_locator_opt.filter(|locator| locator.1 != -1) else {
+            self.report_unresolved_call(e, "resolves to no recorded function");
+            return None;
+        };
 
-        let callee_locator = callee_locator_opt.unwrap();
+        // NOTE: nested method calls among the args change the instance context, so this call's instance is kept beforehand.
+        let instance_locator = self.ctx_instance_locator.clone();
+        let chained_object_opt = self.ctx_chained_object.take();
 
         let calling_args = e.get_args();
         let passed_arity = calling_args.len() as i32;
 
+        // NOTE: a call always runs, so its args must be pushed even when the whole call is only an operand e.g the RHS of `x = f(1)`.
+        self.skip_emit = false;
+
         let result_locator = (Region::TempStack, self.get_relative_offset() + 1);
 
+        let is_readonly_native = callee_locator.0 == Region::Natives && self.native_registry.values().any(
+            |brief| brief.id == callee_locator.1 && !brief.mutates_args
+        );
+
         // NOTE: all args are temporary values and the consuming function call will automatically pop them all...
         for arg_ref in calling_args {
-            arg_ref.accept_visitor(self)?;
+            self.ctx_readonly_arg = is_readonly_native && arg_ref.get_operator() == OperatorTag::Noop;
+            let arg_locator_opt = arg_ref.accept_visitor(self);
+            self.ctx_readonly_arg = false;
+
+            arg_locator_opt?;
         }
 
         match callee_locator.0 {
@@ -556,17 +1148,23 @@ impl<'evl3> ExprVisitor<'evl3, Option<Locator>> for IREmitter<'evl3> {
                 // NOTE: See `Runtime.md` notes under the "Sample Object Method Table" section for why -1 is used as a dud instance location.
                 self.emit_step(Instruction::Ternary(
                     Opcode::InstanceCall,
-                    if self.ctx_instance_locator.1 != -1 { self.ctx_instance_locator.clone() } else { (Region::ObjectHeap, -1) },
+                    if instance_locator.1 != -1 { instance_locator } else { (Region::ObjectHeap, -1) },
                     (Region::Functions, callee_locator.1),
                     (Region::Immediate, passed_arity),
                 ));
             },
             _ => {
+                self.report_unresolved_call(e, "is not a callable function, method, or native");
                 return None;
             }
         }
 
         self.update_relative_offset(1 - passed_arity);
+        self.skip_emit = old_skip_emit;
+
+        if let Some(chained_object_locator) = chained_object_opt {
+            return self.help_emit_chain_result(chained_object_locator);
+        }
 
         Some(result_locator)
     }
@@ -577,42 +1175,84 @@ impl<'evl3> ExprVisitor<'evl3, Option<Locator>> for IREmitter<'evl3> {
     fn visit_unary(&mut self, e: &Unary) -> Option<Locator> {
         // println!("visit_unary");
         let expr_op = e.get_operator();
-        let result_locator_opt = e.accept_visitor(self);
 
-        result_locator_opt.as_ref()?;
+        if matches!(expr_op, OperatorTag::Increment | OperatorTag::Decrement | OperatorTag::PostIncrement | OperatorTag::PostDecrement) {
+            return self.help_emit_step_unary(e);
+        }
 
-        let result_locator = result_locator_opt.unwrap();
-        let expr_opcode = match expr_op {
-            OperatorTag::Minus => Opcode::Neg,
-            _ => Opcode::Nop,
-        };
+        let inner_locator_opt = e.get_inner().accept_visitor(self);
 
-        if expr_opcode == Opcode::Nop {
-            return None;
-        }
+        inner_locator_opt.as_ref()?;
+
+        // NOTE: the inner value is always copied onto the stack top, so the unary op must target that copy and not the original variable.
+        let result_locator = (Region::TempStack, self.get_relative_offset());
 
-        self.emit_step(Instruction::Unary(expr_opcode, result_locator.clone()));
+        match expr_op {
+            OperatorTag::Negate => {
+                self.emit_step(Instruction::Unary(Opcode::Neg, result_locator.clone()));
+            },
+            OperatorTag::Not => {
+                self.emit_step(Instruction::Nonary(Opcode::Not));
+            },
+            _ => {
+                return None;
+            },
+        }
 
         Some(result_locator)
     }
 
     fn visit_binary(&mut self, e: &Binary) -> Option<Locator> {
         match e.op_tag {
+            OperatorTag::Assign if e.get_lhs().get_operator() == OperatorTag::Index => self.help_emit_index_assign(e),
             OperatorTag::Assign => self.help_emit_assign(e),
             OperatorTag::Access => self.help_emit_access(e),
             _ => self.help_emit_bin_normal(e),
         }
     }
+
+    fn visit_index(&mut self, e: &Index) -> Option<Locator> {
+        self.help_emit_intrinsic_call("intrin_varchar_get", &[e.get_target(), e.get_index()], e.get_bracket_token().line_no)
+    }
 }
 
 impl StmtVisitor<bool> for IREmitter<'_> {
-    #[allow(unused_variables)]
     fn visit_import(&mut self, s: &Import) -> bool {
+        if self.has_prepass {
+            let target_name = s.get_target().to_lexeme_str(&self.source_copy).unwrap_or("").to_string();
+
+            self.modules.record_import(&self.current_module, target_name.as_str());
+        }
+
         true
     }
 
     #[allow(unused_variables)]
     fn visit_foreign_stub(&mut self, s: &ForeignStub) -> bool {
+        if self.has_prepass {
+            let stub_name = String::from(s.get_name_token().to_lexeme_str(&self.source_copy).unwrap_or(""));
+
+            self.fun_result_types.insert(stub_name, s.get_result_type().typename());
+        }
+
+        true
+    }
+
+    fn visit_const_decl(&mut self, s: &ConstDecl) -> bool {
+        if !self.has_prepass {
+            return true;
+        }
+
+        let const_name = String::from(s.get_name_token().to_lexeme_str(&self.source_copy).unwrap_or(""));
+
+        let Some(const_value) = eval_const_expr(s.get_init_expr(), &self.source_copy) else {
+            eprintln!("Oops: constant '{const_name}' has no folded value- This can result from a skipped semantic check.");
+            return false;
+        };
+
+        self.modules.record_owner(&self.current_module, const_name.as_str());
+        self.module_const_values.insert(ModuleTable::qualify(&self.current_module, const_name.as_str()), const_value);
+
         true
     }
 
@@ -622,7 +1262,19 @@ impl StmtVisitor<bool> for IREmitter<'_> {
 
         if self.has_prepass {
             let arity_i32 = s.get_params().len() as i32;
-            let func_name_record_id = self.record_fun_by_name(function_name.clone(), arity_i32).unwrap_or(-1);
+            let function_key = ModuleTable::qualify(&self.current_module, function_name.as_str());
+            let func_name_record_id = self.record_fun_by_name(function_key.clone(), arity_i32).unwrap_or(-1);
+
+            if func_name_record_id != -1 {
+                self.modules.record_owner(&self.current_module, function_name.as_str());
+
+                if s.get_access() == AccessFlag::Hidden {
+                    self.modules.record_private(&self.current_module, function_name.as_str());
+                }
+
+                self.fun_result_types.insert(function_key.clone(), s.get_result_type().typename());
+                self.function_exports.insert(function_key, (func_name_record_id, arity_i32));
+            }
 
             func_name_record_id != -1
         } else {
@@ -641,7 +1293,7 @@ impl StmtVisitor<bool> for IREmitter<'_> {
                     .to_lexeme_str(&self.source_copy)
                     .unwrap();
 
-                self.record_varname_locator(String::from(param_name), (Region::TempStack, param_it as i32));
+                self.record_param_locator(String::from(param_name), param.get_typing().typename(), (Region::TempStack, param_it as i32));
                 self.update_relative_offset(1);
                 self.update_relative_local_count(1);
                 self.update_relative_arg_count(1);
@@ -652,6 +1304,8 @@ impl StmtVisitor<bool> for IREmitter<'_> {
                 self.has_error = true;
             }
 
+            self.help_emit_implicit_return();
+
             self.leave_fun_scope();
             !self.has_error
         }
@@ -674,7 +1328,15 @@ impl StmtVisitor<bool> for IREmitter<'_> {
         let current_class_name_copy = self.ctx_class_name.clone();
 
         // println!("prepass for field of class {}", self.ctx_class_name.as_str());
-        self.record_class_field(&current_class_name_copy, field_name)
+        if !self.record_class_field(&current_class_name_copy, field_name) {
+            return false;
+        }
+
+        if let Some(class_layout_ref) = self.class_layouts.get_mut(current_class_name_copy.as_str()) {
+            class_layout_ref.set_member_type_name(field_name.to_string(), s.get_type().typename());
+        }
+
+        true
     }
 
     fn visit_constructor_decl(&mut self, s: &ConstructorDecl) -> bool {
@@ -717,7 +1379,7 @@ impl StmtVisitor<bool> for IREmitter<'_> {
                     .to_lexeme_str(&self.source_copy)
                     .unwrap();
 
-                self.record_varname_locator(String::from(param_name), (Region::TempStack, param_it as i32));
+                self.record_param_locator(String::from(param_name), param.get_typing().typename(), (Region::TempStack, param_it as i32));
                 self.update_relative_offset(1);
                 self.update_relative_local_count(1);
                 self.update_relative_arg_count(1);
@@ -733,6 +1395,26 @@ impl StmtVisitor<bool> for IREmitter<'_> {
             
             self.emit_step(Instruction::Unary(Opcode::MakeHeapObject, (Region::Immediate, layout_field_count)));
 
+            // NOTE: MAKE_HEAP_OBJECT leaves the new object's reference above the arguments, so it's counted like a hidden local that temporaries of the body must stay above.
+            self.update_relative_offset(1);
+            self.update_relative_local_count(1);
+
+            // NOTE: `let` parameters are stored into their fields once the object exists, just like an explicit `value = value;` would be.
+            for (param_it, param) in s.get_params().iter().enumerate() {
+                if !param.binds_field() {
+                    continue;
+                }
+
+                let param_name = param.get_name_token().to_lexeme_str(&self.source_copy).unwrap_or("");
+                let Some(field_id) = self.class_layouts.get(&ctor_class_name).and_then(|layout_ref| layout_ref.get_member_id(param_name.to_string())) else {
+                    eprintln!("Oops: failed to find field '{param_name}' for a constructor parameter of class '{}'", ctor_class_name.as_str());
+                    self.has_error = true;
+                    return false;
+                };
+
+                self.emit_step(Instruction::Binary(Opcode::Replace, (Region::Field, field_id), (Region::TempStack, param_it as i32)));
+            }
+
             self.record_proto_link(pre_ctor_body_block_id, ctor_start_block_id);
             self.apply_proto_links();
 
@@ -741,6 +1423,8 @@ impl StmtVisitor<bool> for IREmitter<'_> {
                 self.has_error = true;
             }
 
+            self.help_emit_implicit_return();
+
             self.in_ctor = false;
             self.leave_fun_scope();
 
@@ -756,13 +1440,19 @@ impl StmtVisitor<bool> for IREmitter<'_> {
 
         if self.has_prepass {
             let met_arity = s.get_params().len() as i32;
-            let met_mangled_name = format!("{}_{}", &class_name, method_name);
+            // NOTE: `::` can't appear in source names, so a method's key never collides with a function's e.g `Span_add` or a module member's e.g `Span.add`.
+            let met_mangled_name = format!("{class_name}::{method_name}");
             let met_real_fun_id = self.record_fun_by_name(met_mangled_name, met_arity).unwrap_or(-1);
 
-            if met_real_fun_id != -1 {
-                if let Some(class_layout_ref) = self.class_layouts.get_mut(class_name.as_str()) {
-                    return class_layout_ref.add_method_id(method_name.to_string(), met_real_fun_id);
+            if met_real_fun_id != -1
+                && let Some(class_layout_ref) = self.class_layouts.get_mut(class_name.as_str()) {
+                if s.is_static() {
+                    class_layout_ref.mark_method_static(method_name.to_string());
                 }
+
+                class_layout_ref.set_member_type_name(method_name.to_string(), s.get_result_type().typename());
+
+                return class_layout_ref.add_method_id(method_name.to_string(), met_real_fun_id);
             }
 
             false
@@ -775,7 +1465,7 @@ impl StmtVisitor<bool> for IREmitter<'_> {
                     .to_lexeme_str(&self.source_copy)
                     .unwrap();
 
-                self.record_varname_locator(String::from(param_name), (Region::TempStack, param_it as i32));
+                self.record_param_locator(String::from(param_name), param.get_typing().typename(), (Region::TempStack, param_it as i32));
                 self.update_relative_offset(1);
                 self.update_relative_local_count(1);
                 self.update_relative_arg_count(1);
@@ -786,6 +1476,8 @@ impl StmtVisitor<bool> for IREmitter<'_> {
                 self.has_error = true;
             }
 
+            self.help_emit_implicit_return();
+
             self.leave_fun_scope();
 
             !self.has_error
@@ -862,7 +1554,11 @@ impl StmtVisitor<bool> for IREmitter<'_> {
 
     fn visit_if(&mut self, s: &If) -> bool {
         // println!("visit_if_decl");
-        let condition_value_locator_opt = s.get_check().accept_visitor(self);
+        if let Some((check_flag, _)) = eval_const_condition(s.get_check(), &self.source_copy) {
+            return self.help_emit_folded_if(s, check_flag);
+        }
+
+        let condition_value_locator_opt = self.help_emit_condition_temp(s.get_check());
 
         if condition_value_locator_opt.is_none() {
             eprintln!("Oops: failed to generate if-check");
@@ -880,12 +1576,20 @@ impl StmtVisitor<bool> for IREmitter<'_> {
         ));
         self.update_relative_offset(-1);
 
+        let pre_if_local_count = self.get_relative_local_count();
+        let pre_if_offset = self.get_relative_offset();
+
         if !s.get_truthy_body().accept_visitor(self) {
             eprintln!("Oops: failed to generate if-block");
             self.has_error = true;
             return false;
         }
 
+        self.help_emit_block_local_pops(pre_if_local_count, pre_if_offset);
+
+        // NOTE: The truthy body may hold nested blocks, so its last node is the one ending the branch.
+        let block_1_end_id = self.result.last().unwrap().get_node_count() - 1;
+
         // NOTE: Here, I must patch the jump_else from before the if-block to go to a NOP before a possible JUMP skipping the else-block if available. This is done for correctness.
         self.emit_step(Instruction::Unary(Opcode::Jump, (Region::BlockId, -1)));
         self.emit_step(Instruction::Nonary(Opcode::Nop));
@@ -896,16 +1600,16 @@ impl StmtVisitor<bool> for IREmitter<'_> {
         let falsy_body_ok = s.get_falsy_body().accept_visitor(self);
 
         if !falsy_body_ok && !self.has_error {
-            let if_fallthrough_id = block_1_id + 1;
+            let if_fallthrough_id = block_1_end_id + 1;
             self.result
                 .last_mut()
                 .unwrap()
                 .add_node(Node::new(Vec::new(), -1, -1));
             self.emit_step(Instruction::Nonary(Opcode::Nop));
-            // self.emit_step(Instruction::Nonary(Opcode::GenPatch));
+            self.emit_step(Instruction::Nonary(Opcode::GenPatch));
 
             self.record_proto_link(pre_if_block_id, if_fallthrough_id);
-            self.record_proto_link(block_1_id, if_fallthrough_id);
+            self.record_proto_link(block_1_end_id, if_fallthrough_id);
             self.apply_proto_links();
 
             return true;
@@ -915,22 +1619,24 @@ impl StmtVisitor<bool> for IREmitter<'_> {
             return false;
         }
 
+        self.help_emit_block_local_pops(pre_if_local_count, pre_if_offset);
+
         self.emit_step(Instruction::Nonary(Opcode::Nop));
         self.emit_step(Instruction::Nonary(Opcode::GenPatch));
 
-        let block_2_id = self.result.last().unwrap().get_node_count() - 1;
+        let block_2_id = block_1_end_id + 1;
+        let block_2_end_id = self.result.last().unwrap().get_node_count() - 1;
         self.record_proto_link(pre_if_block_id, block_2_id);
 
-        let post_if_block_id = block_2_id + 1;
+        let post_if_block_id = block_2_end_id + 1;
         self.result
             .last_mut()
             .unwrap()
             .add_node(Node::new(Vec::new(), -1, -1));
         self.emit_step(Instruction::Nonary(Opcode::Nop));
-        self.emit_step(Instruction::Nonary(Opcode::GenPatch));
 
-        self.record_proto_link(block_1_id, post_if_block_id);
-        self.record_proto_link(block_2_id, post_if_block_id);
+        self.record_proto_link(block_1_end_id, post_if_block_id);
+        self.record_proto_link(block_2_end_id, post_if_block_id);
         self.apply_proto_links();
 
         true
@@ -940,7 +1646,7 @@ impl StmtVisitor<bool> for IREmitter<'_> {
         // println!("visit_while");
         self.emit_step(Instruction::Nonary(Opcode::Nop));
         self.emit_step(Instruction::Nonary(Opcode::GenBeginLoop));
-        let condition_value_locator_opt = s.get_check().accept_visitor(self);
+        let condition_value_locator_opt = self.help_emit_condition_temp(s.get_check());
 
         if condition_value_locator_opt.is_none() {
             eprintln!("Oops: failed to generate while-check");
@@ -959,25 +1665,134 @@ impl StmtVisitor<bool> for IREmitter<'_> {
         self.update_relative_offset(-1);
         self.record_proto_link(pre_while_block_id, while_block_id);
 
+        let pre_body_local_count = self.get_relative_local_count();
+        let pre_body_offset = self.get_relative_offset();
+
         if !s.get_body().accept_visitor(self) {
             eprintln!("Oops: failed to generate while-body");
             self.has_error = true;
             return false;
         }
+
+        // NOTE: The stack must be the same at every check of the loop, so each iteration drops its own body locals before looping back.
+        self.help_emit_block_local_pops(pre_body_local_count, pre_body_offset);
         self.emit_step(Instruction::Unary(Opcode::Jump, (Region::BlockId, -1)));
         self.emit_step(Instruction::Nonary(Opcode::GenPatchBack));
-        self.record_proto_link(while_block_id, while_block_id);
+
+        // NOTE: The back-edge leaves from the body's last node, which differs from its first one when the body has nested blocks. It goes to the node holding the loop check.
+        let while_block_end_id = self.result.last().unwrap().get_node_count() - 1;
+        self.record_proto_link(while_block_end_id, pre_while_block_id);
 
         self.result
             .last_mut()
             .unwrap()
             .add_node(Node::new(Vec::new(), -1, -1));
         let post_while_block_id = self.result.last().unwrap().get_node_count() - 1;
-        self.record_proto_link(while_block_id, post_while_block_id);
+        self.record_proto_link(pre_while_block_id, post_while_block_id);
+        self.emit_step(Instruction::Nonary(Opcode::Nop));
+        self.emit_step(Instruction::Nonary(Opcode::GenPatch));
+
+        self.apply_proto_links();
+
+        true
+    }
+
+    /// NOTE: Lowers `foreach (c : s) { ... }` into an index loop over hidden locals for the varchar, its next index, and its length, which are only popped after the loop.
+    fn visit_foreach(&mut self, s: &Foreach) -> bool {
+        let foreach_line_no = s.get_item_token().line_no;
+
+        let (Some(len_brief), Some(get_brief)) = (self.native_registry.get("intrin_varchar_len"), self.native_registry.get("intrin_varchar_get")) else {
+            eprintln!("GenError: At line {foreach_line_no}, the foreach loop needs the natives 'intrin_varchar_len' & 'intrin_varchar_get', which are not registered.");
+            self.has_error = true;
+            return false;
+        };
+
+        let (len_native_id, get_native_id) = (len_brief.id, get_brief.id);
+        let pre_loop_local_count = self.get_relative_local_count();
+        let pre_loop_offset = self.get_relative_offset();
+
+        let Some(target_locator) = s.get_target().accept_visitor(self) else {
+            eprintln!("Oops: failed to generate foreach-target");
+            self.has_error = true;
+            return false;
+        };
+
+        // NOTE: a named varchar only yields its locator, so a copy is pushed to keep the hidden locals contiguous.
+        if self.get_relative_offset() == pre_loop_offset {
+            self.emit_step(Instruction::Unary(Opcode::Push, target_locator));
+            self.update_relative_offset(1);
+        }
+
+        let varchar_locator = (Region::TempStack, self.get_relative_offset());
+
+        let start_index_locator = self.record_proto_constant(Value::Int(0));
+        self.emit_step(Instruction::Unary(Opcode::LoadConst, start_index_locator));
+        self.update_relative_offset(1);
+        let index_locator = (Region::TempStack, self.get_relative_offset());
+
+        self.emit_step(Instruction::Unary(Opcode::Push, varchar_locator.clone()));
+        self.emit_step(Instruction::Unary(Opcode::NativeCall, (Region::Natives, len_native_id)));
+        self.update_relative_offset(1);
+        let length_locator = (Region::TempStack, self.get_relative_offset());
+
+        self.update_relative_local_count(3);
+
+        self.emit_step(Instruction::Nonary(Opcode::Nop));
+        self.emit_step(Instruction::Nonary(Opcode::GenBeginLoop));
+        self.emit_step(Instruction::Unary(Opcode::Push, index_locator.clone()));
+        self.emit_step(Instruction::Unary(Opcode::Push, length_locator));
+        self.emit_step(Instruction::Nonary(Opcode::CompareLt));
+        self.update_relative_offset(1);
+
+        let pre_foreach_block_id: i32 = self.result.last().unwrap().get_node_count() - 1;
+        let foreach_block_id = pre_foreach_block_id + 1;
+
+        self.emit_step(Instruction::Binary(
+            Opcode::JumpElse,
+            (Region::TempStack, self.get_relative_offset()),
+            (Region::BlockId, -1),
+        ));
+        self.update_relative_offset(-1);
+        self.record_proto_link(pre_foreach_block_id, foreach_block_id);
+
+        let pre_body_local_count = self.get_relative_local_count();
+        let pre_body_offset = self.get_relative_offset();
+
+        self.emit_step(Instruction::Unary(Opcode::Push, varchar_locator));
+        self.emit_step(Instruction::Unary(Opcode::Push, index_locator.clone()));
+        self.emit_step(Instruction::Unary(Opcode::NativeCall, (Region::Natives, get_native_id)));
+        self.update_relative_offset(1);
+        self.update_relative_local_count(1);
+
+        let item_name = String::from(s.get_item_token().to_lexeme_str(&self.source_copy).unwrap());
+        self.record_varname_locator(item_name, (Region::TempStack, self.get_relative_offset()));
+
+        if !s.get_body().accept_visitor(self) {
+            eprintln!("Oops: failed to generate foreach-body");
+            self.has_error = true;
+            return false;
+        }
+
+        // NOTE: Like a while loop, each iteration drops its char & body locals before stepping the index and looping back.
+        self.help_emit_block_local_pops(pre_body_local_count, pre_body_offset);
+        self.emit_step(Instruction::Unary(Opcode::Inc, index_locator));
+        self.emit_step(Instruction::Unary(Opcode::Jump, (Region::BlockId, -1)));
+        self.emit_step(Instruction::Nonary(Opcode::GenPatchBack));
+
+        let foreach_block_end_id = self.result.last().unwrap().get_node_count() - 1;
+        self.record_proto_link(foreach_block_end_id, pre_foreach_block_id);
+
+        self.result
+            .last_mut()
+            .unwrap()
+            .add_node(Node::new(Vec::new(), -1, -1));
+        let post_foreach_block_id = self.result.last().unwrap().get_node_count() - 1;
+        self.record_proto_link(pre_foreach_block_id, post_foreach_block_id);
         self.emit_step(Instruction::Nonary(Opcode::Nop));
         self.emit_step(Instruction::Nonary(Opcode::GenPatch));
 
         self.apply_proto_links();
+        self.help_emit_block_local_pops(pre_loop_local_count, pre_loop_offset);
 
         true
     }
@@ -989,8 +1804,9 @@ impl StmtVisitor<bool> for IREmitter<'_> {
             return true;
         }
 
-        let result_locator_opt = s.get_result().accept_visitor(self);
-        let result_delta = ast_op_to_ir_op(s.get_result().get_operator()).get_stack_delta();
+        // NOTE: a nested return may be skipped at runtime, so the following code must see the same stack offset as before the return.
+        let pre_return_offset = self.get_relative_offset();
+        let result_locator_opt = s.get_result().and_then(|result| result.accept_visitor(self));
 
         if result_locator_opt.is_none() {
             eprintln!("Oops: failed to find locator for return result");
@@ -1005,7 +1821,7 @@ impl StmtVisitor<bool> for IREmitter<'_> {
 
             match result_region {
                 Region::Immediate => result_locator,
-                Region::TempStack => if adjust_offset_of_ultrapure_fn { (result_region, local_arg_value_n) } else { (result_region, result_n + result_delta) },
+                Region::TempStack => if adjust_offset_of_ultrapure_fn { (result_region, local_arg_value_n) } else { (result_region, result_n) },
                 Region::ObjectHeap => result_locator,
                 Region::Field => result_locator,
                 _ => (Region::Immediate, -1),
@@ -1023,14 +1839,70 @@ impl StmtVisitor<bool> for IREmitter<'_> {
             checked_locator,
         ));
 
-        self.reset_relative_offset(-1);
+        self.reset_relative_offset(pre_return_offset);
 
         true
     }
 
     fn visit_expr_stmt(&mut self, s: &ExprStmt) -> bool {
         // println!("visit_expr_stmt");
+        let pre_stmt_offset = self.get_relative_offset();
+
         s.get_inner().accept_visitor(self);
-        true
+
+        // NOTE: any unused result e.g of a call statement must be popped, or else loops would grow the stack past the locals' known offsets.
+        for _ in pre_stmt_offset..self.get_relative_offset() {
+            self.emit_step(Instruction::Nonary(Opcode::Pop));
+            self.update_relative_offset(-1);
+        }
+
+        !self.has_error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::driver::CompileError;
+
+    /// NOTE: how many literals each generated function holds, which keeps the function count within two-letter names.
+    const FILL_LITERALS: i32 = 256;
+
+    /// NOTE: Makes functions assigning `literal_count` distinct varchar literals in total, each of which needs its own heap slot. Constants are looked up per function, so the literals are spread out to keep compiling them quick.
+    fn source_with_literals(literal_count: i32) -> String {
+        let mut source = String::new();
+
+        for literal_n in 0..literal_count {
+            if literal_n % FILL_LITERALS == 0 {
+                // NOTE: names can't hold digits, so each function is named by letters e.g `fill_ab`.
+                let fun_n = (literal_n / FILL_LITERALS) as u8;
+                let fun_name = format!("fill_{}{}", (b'a' + fun_n / 26) as char, (b'a' + fun_n % 26) as char);
+
+                source.push_str(&format!("fun {fun_name}(): int {{\n    let text: varchar = \"t{literal_n}\";\n"));
+            } else {
+                source.push_str(&format!("    text = \"t{literal_n}\";\n"));
+            }
+
+            if literal_n % FILL_LITERALS == FILL_LITERALS - 1 || literal_n == literal_count - 1 {
+                source.push_str("    return 0;\n}\n\n");
+            }
+        }
+
+        source.push_str("fun main(): int {\n    return 0;\n}\n");
+        source
+    }
+
+    #[test]
+    fn literals_filling_the_heap_slots_compile() {
+        let program = crate::compile("./test.loxie", &source_with_literals(MAX_HEAP_SLOTS)).expect("test source should compile");
+
+        assert_eq!(program.get_heap_preloadables().len(), MAX_HEAP_SLOTS as usize);
+    }
+
+    #[test]
+    fn literals_past_the_heap_slots_fail_ir_emission() {
+        let compile_errors = crate::compile("./test.loxie", &source_with_literals(MAX_HEAP_SLOTS + 1)).err().expect("test source should not compile");
+
+        assert_eq!(compile_errors, vec![CompileError::IREmit]);
     }
 }
@@ -17,6 +17,34 @@ fn format_locator(loc: &Locator) -> String {
     }
 }
 
+fn format_ir_step(step: &Instruction) -> String {
+    match step {
+        Instruction::Nonary(op) => {
+            op.get_name().to_string()
+        }
+        Instruction::Unary(op, arg_0) => {
+            format!("{} {}", op.get_name(), format_locator(arg_0))
+        }
+        Instruction::Binary(op, arg_0, arg_1) => {
+            format!(
+                "{} {} {}",
+                op.get_name(),
+                format_locator(arg_0),
+                format_locator(arg_1)
+            )
+        },
+        Instruction::Ternary(op, arg_0, arg_1, arg_2) => {
+            format!(
+                "{} {} {} {}",
+                op.get_name(),
+                format_locator(arg_0),
+                format_locator(arg_1),
+                format_locator(arg_2),
+            )
+        },
+    }
+}
+
 fn print_ir_node(node: &Node, id: i32) -> bool {
     let truthy_id = node.get_truthy_id().unwrap_or(-1);
     let falsy_id = node.get_falsy_id().unwrap_or(-1);
@@ -25,31 +53,7 @@ fn print_ir_node(node: &Node, id: i32) -> bool {
     println!("truthy-link: {truthy_id}, falsy-link: {falsy_id}\n");
 
     for step in node.get_steps() {
-        match step {
-            Instruction::Nonary(op) => {
-                println!("{}", op.get_name());
-            }
-            Instruction::Unary(op, arg_0) => {
-                println!("{} {}", op.get_name(), format_locator(arg_0));
-            }
-            Instruction::Binary(op, arg_0, arg_1) => {
-                println!(
-                    "{} {} {}",
-                    op.get_name(),
-                    format_locator(arg_0),
-                    format_locator(arg_1)
-                );
-            },
-            Instruction::Ternary(op, arg_0, arg_1, arg_2) => {
-                println!(
-                    "{} {} {} {}",
-                    op.get_name(),
-                    format_locator(arg_0),
-                    format_locator(arg_1),
-                    format_locator(arg_2),
-                );
-            },
-        }
+        println!("{}", format_ir_step(step));
     }
 
     println!();
@@ -57,6 +61,39 @@ fn print_ir_node(node: &Node, id: i32) -> bool {
     truthy_id != -1 || falsy_id != -1
 }
 
+/// NOTE: Gives a Graphviz DOT graph of the CFG for debugging branch lowering e.g by `dot -Tsvg`. Each node is labeled by its instructions, and only truthy / falsy links become edges, so falling through to the next node is left implicit.
+pub fn cfg_to_dot(cfg: &CFG) -> String {
+    let mut dot_text = format!("digraph cfg {{\n    label=\"frame slots: {}\";\n    node [shape=box, fontname=\"monospace\"];\n", cfg.get_frame_slots());
+
+    for node_id in 0..cfg.get_node_count() {
+        let node_ref = cfg.get_node_ref(node_id).unwrap();
+        let mut node_label = format!("Block {node_id}:\\l");
+
+        for step in node_ref.get_steps() {
+            node_label.push_str(format_ir_step(step).replace('"', "\\\"").as_str());
+            node_label.push_str("\\l");
+        }
+
+        dot_text.push_str(format!("    n{node_id} [label=\"{node_label}\"];\n").as_str());
+    }
+
+    for node_id in 0..cfg.get_node_count() {
+        let node_ref = cfg.get_node_ref(node_id).unwrap();
+
+        if let Some(truthy_id) = node_ref.get_truthy_id() {
+            dot_text.push_str(format!("    n{node_id} -> n{truthy_id} [label=\"truthy\"];\n").as_str());
+        }
+
+        if let Some(falsy_id) = node_ref.get_falsy_id() {
+            dot_text.push_str(format!("    n{node_id} -> n{falsy_id} [label=\"falsy\"];\n").as_str());
+        }
+    }
+
+    dot_text.push_str("}\n");
+
+    dot_text
+}
+
 pub fn print_cfg(function_cfg: &CFG) {
     let mut done_ids = HashSet::<i32>::new();
     let mut next_nodes = VecDeque::<&Node>::new();
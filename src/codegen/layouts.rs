@@ -1,10 +1,18 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Default)]
 pub struct ClassLayout {
     members: HashMap<String, i32>,
+    /// Contains field names in declaration order, so the name at index `N` has member ID `N`.
+    member_names: Vec<String>,
     /// Contains mappings of method names to these entries: `(<class-method-id>, <top-function-id>)`
     method_table: HashMap<String, (i32, i32)>,
+    /// Contains names of static methods, which are called like plain functions without an instance.
+    static_method_names: HashSet<String>,
+    /// Contains method names in declaration order, so the name at index `N` has class-method ID `N`.
+    method_names: Vec<String>,
+    /// Contains the declared type names of fields & method results, so chained accesses like `obj.next().get()` can find the layout of each step's class.
+    member_type_names: HashMap<String, String>,
 }
 
 impl ClassLayout {
@@ -12,9 +20,21 @@ impl ClassLayout {
         self.members.len() as i32
     }
 
+    /// NOTE: duplicate names are rejected without touching the existing ID, so member IDs always follow declaration order.
     pub fn add_member(&mut self, name: String) -> bool {
+        if self.members.contains_key(&name) {
+            return false;
+        }
+
         let next_member_id = self.members.len() as i32;
-        self.members.insert(name, next_member_id).is_none()
+        self.members.insert(name.clone(), next_member_id);
+        self.member_names.push(name);
+
+        true
+    }
+
+    pub fn get_member_names(&self) -> &[String] {
+        &self.member_names
     }
 
     pub fn get_member_id(&self, name: String) -> Option<i32> {
@@ -26,9 +46,35 @@ impl ClassLayout {
     }
 
     pub fn add_method_id(&mut self, name: String, real_fun_id: i32) -> bool {
+        if self.method_table.contains_key(&name) {
+            return false;
+        }
+
         let next_method_id = self.method_table.len() as i32;
+        self.method_table.insert(name.clone(), (next_method_id, real_fun_id));
+        self.method_names.push(name);
+
+        true
+    }
+
+    pub fn get_method_names(&self) -> &[String] {
+        &self.method_names
+    }
+
+    pub fn mark_method_static(&mut self, name: String) -> bool {
+        self.static_method_names.insert(name)
+    }
+
+    pub fn is_static_method(&self, name: &str) -> bool {
+        self.static_method_names.contains(name)
+    }
 
-        self.method_table.insert(name, (next_method_id, real_fun_id)).is_none()
+    pub fn set_member_type_name(&mut self, name: String, type_name: String) {
+        self.member_type_names.insert(name, type_name);
+    }
+
+    pub fn get_member_type_name(&self, name: &str) -> Option<&str> {
+        self.member_type_names.get(name).map(|type_name| type_name.as_str())
     }
 
     pub fn get_real_method_id(&self, name: String) -> Option<(i32, i32)> {
@@ -41,3 +87,39 @@ impl ClassLayout {
 }
 
 pub type LayoutTable = HashMap<String, ClassLayout>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn member_ids_follow_declaration_order() {
+        let mut layout = ClassLayout::default();
+        let field_names = ["zeta", "alpha", "mid", "beta"];
+
+        for field_name in field_names {
+            assert!(layout.add_member(String::from(field_name)));
+        }
+
+        assert!(!layout.add_member(String::from("alpha")));
+        assert_eq!(layout.get_field_count(), 4);
+        assert_eq!(layout.get_member_names(), field_names);
+
+        for (field_id, field_name) in field_names.iter().enumerate() {
+            assert_eq!(layout.get_member_id(field_name.to_string()), Some(field_id as i32));
+        }
+    }
+
+    #[test]
+    fn method_ids_follow_declaration_order() {
+        let mut layout = ClassLayout::default();
+
+        assert!(layout.add_method_id(String::from("step"), 7));
+        assert!(layout.add_method_id(String::from("get"), 3));
+        assert!(!layout.add_method_id(String::from("step"), 9));
+
+        assert_eq!(layout.get_method_names(), ["step", "get"]);
+        assert_eq!(layout.get_real_method_id(String::from("get")), Some((1, 3)));
+        assert_eq!(layout.get_real_method_id(String::from("step")), Some((0, 7)));
+    }
+}
@@ -1,6 +1,9 @@
+pub mod cfg_checker;
+pub mod const_eval;
 pub mod ir;
 pub mod ir_emitter;
 pub mod ir_printer;
 pub mod layouts;
+pub mod stack_checker;
 pub mod bytecode_emitter;
 pub mod bytecode_printer;
@@ -0,0 +1,224 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::codegen::ir::*;
+
+/**
+ * ### ABOUT
+ * Simulates the stack depth from a procedure's frame base across its CFG before bytecode emission, so codegen that leaves or loses stack values is reported at compile time instead of quietly shifting the slots later temporaries & locals use.
+ * ### RULES
+ * 1. The depth never drops below the procedure's argument slots.
+ * 2. Every path into a node must agree on the depth there, which catches e.g a loop body leaving a temporary each round.
+ * 3. Every stack operand, including a `RETURN`'s result, must address a slot below the simulated top.
+ * ### CAVEATS
+ * Natives are assumed to pop their arguments & push one result, as the IR emitter does.
+ */
+enum NodeExit {
+    /// NOTE: the node's steps end with the first depth, which its successors are entered with, except that a falsy successor is entered with the depth right after a conditional jump if the node has one.
+    Continues(i32, Option<i32>),
+    /// NOTE: the node returns or leaves, so it passes no depth on.
+    Stops,
+    /// NOTE: the node broke a rule, which was already reported.
+    Broken,
+}
+
+fn is_terminal_exit(opcode: Opcode) -> bool {
+    matches!(opcode, Opcode::Return | Opcode::Leave)
+}
+
+fn get_immediate(locator_opt: Option<&Locator>) -> i32 {
+    locator_opt.filter(|(region, _)| *region == Region::Immediate).map_or(0, |(_, value)| *value)
+}
+
+/// NOTE: Gives a step's net stack effect at the given depth, refining `Opcode::get_stack_delta` where the effect depends on the operands e.g a call's argument count.
+fn get_step_delta(step: &Instruction, depth: i32, native_arities: &HashMap<i32, i32>) -> i32 {
+    let opcode = step.get_opcode();
+
+    match opcode {
+        Opcode::Call => 1 - get_immediate(step.get_arg_1()),
+        Opcode::InstanceCall => 1 - get_immediate(step.get_arg_2()),
+        Opcode::NativeCall => {
+            let native_id = step.get_arg_0().map_or(-1, |(_, native_id)| *native_id);
+
+            1 - native_arities.get(&native_id).copied().unwrap_or(0)
+        },
+        // NOTE: a conditional jump only pops its tested value when that value is the top temporary, just like the engine does.
+        Opcode::JumpIf | Opcode::JumpElse => {
+            let tests_top = step.get_arg_0().is_some_and(|(region, offset)| *region == Region::TempStack && *offset == depth - 1);
+
+            if tests_top { -1 } else { 0 }
+        },
+        _ => opcode.get_stack_delta(),
+    }
+}
+
+fn check_step_operands(step: &Instruction, depth: i32, cfg_id: usize, node_id: i32) -> bool {
+    for (region, offset) in [step.get_arg_0(), step.get_arg_1(), step.get_arg_2()].into_iter().flatten() {
+        if *region == Region::TempStack && (*offset < 0 || *offset >= depth) {
+            eprintln!("GenError: CFG #{cfg_id} has node #{node_id} with '{}' using stack slot {offset}, but only slots 0 to {} are live there.", step.get_opcode().get_name(), depth - 1);
+            return false;
+        }
+    }
+
+    true
+}
+
+/// NOTE: Also records the depth at a `#GEN_BEGIN_LOOP` marker, as a loop's back jump lands there instead of at its node's start.
+fn run_node_steps(cfg: &CFG, cfg_id: usize, node_id: i32, entry_depth: i32, native_arities: &HashMap<i32, i32>, loop_head_depths: &mut [Option<i32>]) -> NodeExit {
+    let mut depth = entry_depth;
+    let mut branch_depth_opt = None::<i32>;
+
+    for step in cfg.get_node_ref(node_id).unwrap().get_steps() {
+        if !check_step_operands(step, depth, cfg_id, node_id) {
+            return NodeExit::Broken;
+        }
+
+        if step.get_opcode() == Opcode::GenBeginLoop {
+            loop_head_depths[node_id as usize] = Some(depth);
+        }
+
+        if is_terminal_exit(step.get_opcode()) {
+            return NodeExit::Stops;
+        }
+
+        depth += get_step_delta(step, depth, native_arities);
+
+        if matches!(step.get_opcode(), Opcode::JumpIf | Opcode::JumpElse) {
+            branch_depth_opt = Some(depth);
+        }
+
+        if depth < cfg.get_param_count() {
+            eprintln!("GenError: CFG #{cfg_id} has node #{node_id} popping past the frame base with '{}', leaving {depth} of its {} argument slots.", step.get_opcode().get_name(), cfg.get_param_count());
+            return NodeExit::Broken;
+        }
+    }
+
+    NodeExit::Continues(depth, branch_depth_opt)
+}
+
+fn is_back_jump(node: &Node) -> bool {
+    node.get_steps().iter().any(|step| step.get_opcode() == Opcode::GenPatchBack)
+}
+
+fn falls_through(node: &Node) -> bool {
+    // NOTE: pseudo-ops are skipped here like in `cfg_checker`, as a trailing `#GEN_PATCH` still lets a jump decide the exit.
+    let exit_opcode_opt = node.get_steps().iter().rev().map(|step| step.get_opcode()).find(|opcode| {
+        !matches!(opcode, Opcode::Nop | Opcode::GenBeginLoop | Opcode::GenPatch | Opcode::GenPatchBack)
+    });
+
+    !exit_opcode_opt.is_some_and(|opcode| is_terminal_exit(opcode) || opcode == Opcode::Jump)
+}
+
+pub fn check_stack_balance(cfg: &CFG, cfg_id: usize, native_arities: &HashMap<i32, i32>) -> bool {
+    let node_count = cfg.get_node_count();
+
+    if node_count == 0 {
+        return true;
+    }
+
+    let mut entry_depths = vec![None::<i32>; node_count as usize];
+    let mut loop_head_depths = vec![None::<i32>; node_count as usize];
+    let mut next_ids = VecDeque::<i32>::from([0]);
+    entry_depths[0] = Some(cfg.get_param_count());
+
+    while let Some(next_id) = next_ids.pop_front() {
+        let node_ref = cfg.get_node_ref(next_id).unwrap();
+        let entry_depth = entry_depths[next_id as usize].unwrap();
+
+        let (exit_depth, branch_depth_opt) = match run_node_steps(cfg, cfg_id, next_id, entry_depth, native_arities, &mut loop_head_depths) {
+            NodeExit::Continues(depth, branch_depth_opt) => (depth, branch_depth_opt),
+            NodeExit::Stops => {
+                continue;
+            },
+            NodeExit::Broken => {
+                return false;
+            },
+        };
+
+        if is_back_jump(node_ref) {
+            let loop_head_id = node_ref.get_truthy_id().unwrap_or(-1);
+            let loop_head_depth_opt = loop_head_depths.get(loop_head_id as usize).copied().flatten();
+
+            if loop_head_depth_opt != Some(exit_depth) {
+                eprintln!("GenError: CFG #{cfg_id} has node #{next_id} jumping back to the loop in node #{loop_head_id} with {exit_depth} stack slots, but the loop began with {}.", loop_head_depth_opt.map_or(String::from("unknown"), |depth| depth.to_string()));
+                return false;
+            }
+
+            continue;
+        }
+
+        let fallthrough_id_opt = if falls_through(node_ref) && next_id + 1 < node_count { Some(next_id + 1) } else { None };
+
+        let falsy_depth = branch_depth_opt.unwrap_or(exit_depth);
+        let successors = [(node_ref.get_truthy_id(), exit_depth), (node_ref.get_falsy_id(), falsy_depth), (fallthrough_id_opt, exit_depth)];
+
+        for (successor_id, successor_depth) in successors.into_iter().filter_map(|(id_opt, depth)| Some((id_opt?, depth))) {
+            match entry_depths[successor_id as usize] {
+                Some(known_depth) if known_depth != successor_depth => {
+                    eprintln!("GenError: CFG #{cfg_id} has node #{successor_id} entered with {known_depth} and {successor_depth} stack slots along different paths, so its stack is unbalanced.");
+                    return false;
+                },
+                Some(_) => {},
+                None => {
+                    entry_depths[successor_id as usize] = Some(successor_depth);
+                    next_ids.push_back(successor_id);
+                },
+            }
+        }
+    }
+
+    true
+}
+
+pub fn check_all_stacks(cfg_list: &CFGStorage, native_arities: &HashMap<i32, i32>) -> bool {
+    cfg_list.iter().enumerate().all(|(cfg_id, cfg)| check_stack_balance(cfg, cfg_id, native_arities))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// NOTE: Builds an if-like CFG whose taken branch runs `branch_steps` before joining the untaken one at a returning node.
+    fn make_branching_cfg(branch_steps: Vec<Instruction>) -> CFG {
+        let mut cfg = CFG::new();
+
+        cfg.add_node(Node::new(vec![
+            Instruction::Nonary(Opcode::PushTrue),
+            Instruction::Unary(Opcode::JumpElse, (Region::TempStack, 0)),
+        ], 1, 2));
+        cfg.add_node(Node::new(branch_steps, -1, -1));
+        cfg.add_node(Node::new(vec![Instruction::Unary(Opcode::Return, (Region::Immediate, 0))], -1, -1));
+
+        cfg
+    }
+
+    #[test]
+    fn balanced_branches_pass() {
+        let cfg = make_branching_cfg(vec![Instruction::Nonary(Opcode::PushTrue), Instruction::Nonary(Opcode::Pop)]);
+
+        assert!(check_stack_balance(&cfg, 0, &HashMap::new()));
+    }
+
+    #[test]
+    fn branches_leaving_temporaries_fail() {
+        let cfg = make_branching_cfg(vec![Instruction::Nonary(Opcode::PushTrue)]);
+
+        assert!(!check_stack_balance(&cfg, 0, &HashMap::new()));
+    }
+
+    #[test]
+    fn pops_past_arguments_fail() {
+        let mut cfg = CFG::new();
+        cfg.set_param_count(1);
+        cfg.add_node(Node::new(vec![Instruction::Nonary(Opcode::Pop), Instruction::Unary(Opcode::Return, (Region::Immediate, 0))], -1, -1));
+
+        assert!(!check_stack_balance(&cfg, 0, &HashMap::new()));
+    }
+
+    #[test]
+    fn operands_above_the_top_fail() {
+        let mut cfg = CFG::new();
+        cfg.add_node(Node::new(vec![Instruction::Unary(Opcode::Return, (Region::TempStack, 0))], -1, -1));
+
+        assert!(!check_stack_balance(&cfg, 0, &HashMap::new()));
+    }
+}
@@ -2,19 +2,21 @@ use std::{
     collections::{
         HashMap, HashSet, VecDeque
     },
-    fs
+    fmt::{Display, Formatter},
+    fs,
+    time::Instant
 };
 
 // NOTE: allow unused import for bytecode_printer::disassemble_program until a dump flag is added for the driver.
 #[allow(unused_imports)]
 use crate::{
     codegen::{
-        bytecode_emitter::BytecodeEmitter, bytecode_printer::disassemble_program, ir_emitter::{IREmitter, IRResult}/*, ir_printer::print_cfg*/
+        bytecode_emitter::BytecodeEmitter, bytecode_printer::disassemble_program, cfg_checker::check_all_cfgs, stack_checker::check_all_stacks, ir_emitter::{IREmitter, IRResult}, ir_printer::cfg_to_dot/*, ir_printer::print_cfg*/
     },
     frontend::{
-        ast::Stmt, lexer::Lexer, parser::Parser, token::TokenType
+        ast::Stmt, lexer::{DEFAULT_OPERATOR_CHARS, DEFAULT_TAB_WIDTH, Lexer}, parser::{DEFAULT_PARSE_ERROR_MAX, Parser}, token::TokenType
     },
-    semantics::analyzer::Analyzer, utils::bundle::NativeBrief, vm::bytecode,
+    log_verbose, semantics::analyzer::Analyzer, utils::{bundle::NativeBrief, logging::LogLevel}, vm::bytecode,
 };
 
 /// ### NOTE
@@ -25,6 +27,38 @@ pub type SourceIndexedAST = (i32, Box<dyn Stmt>);
 
 pub type FullSourceIndexedAST = (VecDeque<SourceIndexedAST>, HashMap<i32, String>);
 
+/// ### NOTE
+/// Names a compile phase that failed, in the order the failures happened. The diagnostics behind each one are already reported to stderr by then, so these only tell embedders where & why compilation stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompileError {
+    /// NOTE: a source could not be read, was empty, or had syntax errors.
+    Parse,
+    /// NOTE: holds how many semantic errors were reported.
+    Semantics(usize),
+    /// NOTE: the IR emitter rejected a declaration.
+    IREmit,
+    /// NOTE: a CFG failed the structural checks of `cfg_checker`.
+    MalformedCFG,
+    /// NOTE: a CFG failed the stack depth checks of `stack_checker`.
+    UnbalancedStack,
+    /// NOTE: a checked CFG still could not be lowered to bytecode.
+    BytecodeEmit,
+}
+
+impl Display for CompileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse => write!(f, "parsing failed."),
+            Self::Semantics(0) => write!(f, "found an unknown semantic error."),
+            Self::Semantics(error_count) => write!(f, "found {error_count} semantic error(s), see above."),
+            Self::IREmit => write!(f, "failed to emit IR."),
+            Self::MalformedCFG => write!(f, "found a malformed CFG, see errors above."),
+            Self::UnbalancedStack => write!(f, "found a CFG with an unbalanced stack, see errors above."),
+            Self::BytecodeEmit => write!(f, "failed to emit bytecode."),
+        }
+    }
+}
+
 /**
  ### BRIEF
  This logical entity contains all major stages of the bytecode compiler:
@@ -42,18 +76,112 @@ pub struct CompilerMain<'cml_1> {
     ir_emitter: IREmitter<'cml_1>,
     bc_emitter: BytecodeEmitter,
     first_source_name: &'cml_1 str,
+
+    /// NOTE: Holds the text of the first source, so it's compiled as given instead of read again from its file.
+    first_source: &'cml_1 str,
+
+    /// NOTE: Indicates that the sources form a library module, so a missing `main` is allowed.
+    library_mode: bool,
+
+    /// NOTE: Symbols the lexer groups into operators, which default to Loxie's own.
+    operator_chars: Vec<char>,
+
+    /// NOTE: Columns between tab stops for the reported source positions.
+    tab_width: usize,
+
+    /// NOTE: How many syntax errors each source reports before its parsing stops.
+    parse_error_max: i32,
+
+    /// NOTE: Controls progress messages such as parsed TUs, which only verbose runs show along with the disassembly. Diagnostics are always reported.
+    log_level: LogLevel,
+
+    /// NOTE: Maps each native's ID to its parameter count for checking the stack effects of native calls.
+    native_arities: HashMap<i32, i32>,
+
+    /// NOTE: Makes `compile_from_start` check & emit each declaration's body in turn, dropping its AST and finished sources right after, so all ASTs and all IR are never held together.
+    streaming: bool,
+
+    /// NOTE: Maps each TU's source ID to its module name, which is `""` for the main program & the import name for library modules.
+    module_names: HashMap<i32, String>,
+
+    /// NOTE: Holds how many microseconds each finished phase of `compile_from_start` took in order, e.g `("parse", 120)`.
+    phase_timings: Vec<(&'static str, u128)>,
+
+    /// NOTE: Holds every failed phase reported so far, which `take_compile_errors` hands out.
+    compile_errors: Vec<CompileError>,
 }
 
 impl<'cml_2> CompilerMain<'cml_2> {
-    pub fn new(first_source_name_arg: &'cml_2 str, main_source: &'cml_2 str, native_catalog: &'cml_2 HashMap<&'static str, NativeBrief>) -> Self {
+    pub fn new(first_source_name_arg: &'cml_2 str, main_source: &'cml_2 str, native_catalog: &'cml_2 HashMap<String, NativeBrief>) -> Self {
+        let mut temp_semanator = Analyzer::new(String::from(main_source));
+        temp_semanator.load_native_signatures(native_catalog);
+
         Self {
-           semanator: Analyzer::new(String::from(main_source)),
+           semanator: temp_semanator,
            ir_emitter: IREmitter::<'cml_2>::new(main_source, native_catalog),
            bc_emitter: BytecodeEmitter::default(),
            first_source_name: first_source_name_arg,
+           first_source: main_source,
+           library_mode: false,
+           operator_chars: DEFAULT_OPERATOR_CHARS.to_vec(),
+           tab_width: DEFAULT_TAB_WIDTH,
+           parse_error_max: DEFAULT_PARSE_ERROR_MAX,
+           log_level: LogLevel::Normal,
+           native_arities: native_catalog.values().map(|brief| (brief.id, brief.arity)).collect(),
+           streaming: false,
+           module_names: HashMap::new(),
+           phase_timings: Vec::new(),
+           compile_errors: Vec::new(),
         }
     }
 
+    pub fn set_library_mode(&mut self, flag: bool) {
+        self.library_mode = flag;
+    }
+
+    pub fn set_streaming(&mut self, flag: bool) {
+        self.streaming = flag;
+    }
+
+    pub fn set_log_level(&mut self, level: LogLevel) {
+        self.log_level = level;
+        self.semanator.set_log_level(level);
+        self.bc_emitter.set_log_level(level);
+    }
+
+    pub fn get_phase_timings(&self) -> &[(&'static str, u128)] {
+        &self.phase_timings
+    }
+
+    /// NOTE: Gives away the failed phases reported since the last call, e.g `[CompileError::Parse]` after a syntax error.
+    pub fn take_compile_errors(&mut self) -> Vec<CompileError> {
+        std::mem::take(&mut self.compile_errors)
+    }
+
+    fn report_failure(&mut self, error: CompileError) {
+        eprintln!("CompileError: {error}");
+        self.compile_errors.push(error);
+    }
+
+    fn record_phase_timing(&mut self, phase_name: &'static str, phase_start: Instant) {
+        self.phase_timings.push((phase_name, phase_start.elapsed().as_micros()));
+    }
+
+    /// NOTE: Replaces the operator symbols for lexing, e.g adding `'%'` before registering a `"%"` lexical item. Each operator lexeme must still be in the lexical items to not lex as `Unknown`.
+    pub fn set_operator_chars(&mut self, operator_chars: &[char]) {
+        self.operator_chars = operator_chars.to_vec();
+    }
+
+    /// NOTE: Sets the columns between tab stops, so diagnostics for tab-indented sources give the columns an editor with that tab width shows.
+    pub fn set_tab_width(&mut self, tab_width: usize) {
+        self.tab_width = tab_width;
+    }
+
+    /// NOTE: Sets how many syntax errors each source reports before its parsing stops, e.g `1` for just the first. Any value below `1` is treated as `1`.
+    pub fn set_parse_error_max(&mut self, max_count: i32) {
+        self.parse_error_max = max_count;
+    }
+
     fn step_parse<'cml_3>(&'cml_3 mut self, lexicals: HashMap<String, TokenType>) -> Option<FullSourceIndexedAST> {   
         let mut local_src_map = HashMap::<i32, String>::new();
         let mut source_frontier = VecDeque::<String>::new();
@@ -65,7 +193,10 @@ impl<'cml_2> CompilerMain<'cml_2> {
         while !source_frontier.is_empty() {
             let next_src_name = source_frontier.pop_back().unwrap();
             
-            let temp_tu_src_opt = if next_src_name.starts_with("./") {
+            // NOTE: only imports are read from files, as the first source's text was given up front.
+            let temp_tu_src_opt = if local_src_map.is_empty() {
+                Ok(String::from(self.first_source))
+            } else if next_src_name.starts_with("./") {
                 fs::read_to_string(next_src_name.clone())
             } else {
                 fs::read_to_string(format!("./loxie_lib/{next_src_name}.loxie"))
@@ -79,15 +210,24 @@ impl<'cml_2> CompilerMain<'cml_2> {
             let next_src_id = local_src_map.len() as i32;
             let temp_src = temp_tu_src_opt.unwrap();
             local_src_map.insert(next_src_id, temp_src.clone());
+            self.module_names.insert(next_src_id, if next_src_id == 0 { String::new() } else { next_src_name.clone() });
             let tu_src_view = temp_src.as_str();
 
-            let temp_lexer = Lexer::<'cml_3>::new("");
+            let mut temp_lexer = Lexer::<'cml_3>::new("", &self.operator_chars);
+            temp_lexer.set_tab_width(self.tab_width);
             let mut temp_parser = Parser::<'cml_3>::new(temp_lexer);
+            temp_parser.set_parse_error_max(self.parse_error_max);
 
             temp_parser.reset_with(tu_src_view);
             let (tu_ast_opt, tu_successors) = temp_parser.parse_file(&lexicals);
             
             tu_ast_opt.as_deref()?;
+
+            // NOTE: an empty or comment-only source would compile to nothing & only fail later by a missing `main`, so it's reported right away.
+            if tu_ast_opt.as_ref().is_some_and(Vec::is_empty) {
+                eprintln!("CompileError: The source '{next_src_name}' contains no declarations- Declare `fun main(): int` for a program, or functions, constants, & classes for a module.");
+                return None;
+            }
             
             // NOTE: Why do I reverse each TU's decls? For each TU, push top declarations in a certain order to ensure proper semantic scan ordering:
             // TU Main: | D Main | -- (imports) --> TU 1: | A B C | 
@@ -101,7 +241,7 @@ impl<'cml_2> CompilerMain<'cml_2> {
                 );
             }
             
-            println!("parsed TU #{next_src_id} for file '{next_src_name}'...");
+            log_verbose!(self.log_level, "parsed TU #{next_src_id} for file '{next_src_name}'...");
             finished_srcs.insert(next_src_name);
             
             for successor_src in tu_successors {
@@ -111,27 +251,99 @@ impl<'cml_2> CompilerMain<'cml_2> {
             }
         }
 
+        self.ir_emitter.set_module_names(self.module_names.clone());
+
         Some((full_sourced_ast_seq, local_src_map))
     }
 
+    fn switch_sema_source(&mut self, src_idx: i32, srcs_table: &HashMap<i32, String>) {
+        let module_name = self.module_names.get(&src_idx).map_or("", String::as_str);
+
+        self.semanator.reset_source(srcs_table.get(&src_idx).unwrap().clone(), module_name);
+    }
+
+    /// NOTE: Every top-level declaration is checked even after one fails, so all semantic errors are reported in one run. Bodies are skipped if any declaration failed to be recorded, as they would only give follow-up errors.
     fn step_sema(&mut self, full_ast: &VecDeque<SourceIndexedAST>, srcs_table: &HashMap<i32, String>) -> bool {
+        if !self.step_sema_decls(full_ast, srcs_table) {
+            return false;
+        }
+
+        let mut bodies_ok = true;
+
         for (temp_ast_src_idx, temp_ast) in full_ast {
-            self.semanator.reset_source(srcs_table.get(temp_ast_src_idx).unwrap().clone());
-            if !self.semanator.check_top_ast(temp_ast.as_ref()) {
-                return false;
-            }
+            self.switch_sema_source(*temp_ast_src_idx, srcs_table);
+            bodies_ok &= self.semanator.check_top_ast(temp_ast.as_ref());
         }
 
-        self.semanator.clear_preprocess_decls_flag();
+        log_verbose!(self.log_level, "{}", self.semanator.dump_type_info());
+
+        // NOTE: some errors keep their expression's type to avoid follow-up errors e.g a literal out of range, so any reported error still fails the pass.
+        self.check_main_entry() && bodies_ok && self.semanator.get_error_count() == 0
+    }
+
+    /// NOTE: Records every top-level declaration before any body is checked, leaving the analyzer ready for its body pass.
+    fn step_sema_decls(&mut self, full_ast: &VecDeque<SourceIndexedAST>, srcs_table: &HashMap<i32, String>) -> bool {
+        let mut decls_ok = true;
 
         for (temp_ast_src_idx, temp_ast) in full_ast {
-            self.semanator.reset_source(srcs_table.get(temp_ast_src_idx).unwrap().clone());
-            if !self.semanator.check_top_ast(temp_ast.as_ref()) {
-                return false;
+            self.switch_sema_source(*temp_ast_src_idx, srcs_table);
+            decls_ok &= self.semanator.check_top_ast(temp_ast.as_ref());
+        }
+
+        self.semanator.clear_preprocess_decls_flag();
+
+        decls_ok
+    }
+
+    fn check_main_entry(&self) -> bool {
+        self.library_mode || self.semanator.check_main_entry()
+    }
+
+    /// NOTE: Checks & emits each declaration's body in turn after both passes have seen every declaration, consuming the ASTs so each one is freed once its code exists. A TU's source is freed after its last declaration, as declarations stay grouped by TU. After a failed body, the rest are still checked for their diagnostics but not emitted.
+    fn step_stream_bodies(&mut self, mut full_ast: VecDeque<SourceIndexedAST>, mut srcs_table: HashMap<i32, String>) -> Option<IRResult> {
+        if !self.step_sema_decls(&full_ast, &srcs_table) || !self.check_main_entry() {
+            self.report_sema_failure();
+            return None;
+        }
+
+        if !self.ir_emitter.track_all_decls(&full_ast, &srcs_table) {
+            self.report_failure(CompileError::IREmit);
+            return None;
+        }
+
+        let mut bodies_ok = true;
+        let mut emit_ok = true;
+
+        while let Some((temp_ast_src_idx, temp_ast)) = full_ast.pop_front() {
+            self.switch_sema_source(temp_ast_src_idx, &srcs_table);
+            bodies_ok &= self.semanator.check_top_ast(temp_ast.as_ref());
+
+            if bodies_ok && emit_ok {
+                emit_ok = self.ir_emitter.emit_decl_ir(temp_ast_src_idx, temp_ast.as_ref(), &srcs_table);
+            }
+
+            if full_ast.front().is_none_or(|(next_src_idx, _)| *next_src_idx != temp_ast_src_idx) {
+                srcs_table.remove(&temp_ast_src_idx);
             }
         }
 
-        true
+        log_verbose!(self.log_level, "{}", self.semanator.dump_type_info());
+
+        if !bodies_ok {
+            self.report_sema_failure();
+            return None;
+        }
+
+        if !emit_ok {
+            self.report_failure(CompileError::IREmit);
+            return None;
+        }
+
+        Some(self.ir_emitter.take_ir_result())
+    }
+
+    fn report_sema_failure(&mut self) {
+        self.report_failure(CompileError::Semantics(self.semanator.get_error_count()));
     }
 
     fn step_ir_emit(&mut self, full_ast: &VecDeque<SourceIndexedAST>, srcs_table: &HashMap<i32, String>) -> Option<IRResult> {
@@ -152,36 +364,138 @@ impl<'cml_2> CompilerMain<'cml_2> {
     fn step_bc_emit(&mut self, full_ir: &mut IRResult) -> Option<bytecode::Program> {
         let (full_cfg_list, full_const_groups, main_id, heap_preloadables) = full_ir;
 
-        self.bc_emitter.generate_bytecode(full_cfg_list, full_const_groups, *main_id, heap_preloadables)
+        if !check_all_cfgs(full_cfg_list) {
+            self.report_failure(CompileError::MalformedCFG);
+            return None;
+        }
+
+        if !check_all_stacks(full_cfg_list, &self.native_arities) {
+            self.report_failure(CompileError::UnbalancedStack);
+            return None;
+        }
+
+
+        let program_opt = self.bc_emitter.generate_bytecode(full_cfg_list, full_const_groups, *main_id, heap_preloadables);
+
+        if program_opt.is_none() {
+            self.report_failure(CompileError::BytecodeEmit);
+        }
+
+        program_opt
     }
 
-    pub fn compile_from_start(&mut self, lexicals: HashMap<String, TokenType>) -> Option<bytecode::Program> {
+    /// NOTE: Only parses & analyzes all sources for their diagnostics, skipping IR / bytecode emission. This is for fast checks e.g from editors.
+    pub fn check_only(&mut self, lexicals: HashMap<String, TokenType>) -> bool {
         let full_program_ast_opt = self.step_parse(lexicals);
 
         if full_program_ast_opt.is_none() {
-            eprintln!("CompileError: parsing failed.");
+            self.report_failure(CompileError::Parse);
+            return false;
+        }
+
+        let (full_asts, full_src_table) = full_program_ast_opt.unwrap();
+
+        if !self.step_sema(&full_asts, &full_src_table) {
+            self.report_sema_failure();
+            return false;
+        }
+
+        true
+    }
+
+    /// NOTE: Compiles all sources only up to checked CFGs, giving every procedure's CFG as a DOT graph in procedure order for `--dump-cfg-dot`.
+    pub fn dump_cfg_dot(&mut self, lexicals: HashMap<String, TokenType>) -> Option<String> {
+        let full_program_ast_opt = self.step_parse(lexicals);
+
+        if full_program_ast_opt.is_none() {
+            self.report_failure(CompileError::Parse);
             return None;
         }
 
         let (full_asts, full_src_table) = full_program_ast_opt.unwrap();
 
         if !self.step_sema(&full_asts, &full_src_table) {
-            eprintln!("CompileError: found an unknown semantic error.");
+            self.report_sema_failure();
             return None;
         }
 
-        let full_program_ir_opt = self.step_ir_emit(&full_asts, &full_src_table);
+        let Some((full_cfg_list, _, _, _)) = self.step_ir_emit(&full_asts, &full_src_table) else {
+            self.report_failure(CompileError::IREmit);
+            return None;
+        };
 
-        if full_program_ir_opt.is_none() {
-            eprintln!("CompileError: failed to emit IR.");
+        if !check_all_cfgs(&full_cfg_list) {
+            self.report_failure(CompileError::MalformedCFG);
             return None;
         }
 
-        let mut full_program_ir = full_program_ir_opt.unwrap();
+        let mut full_dot_text = String::new();
+
+        for (cfg_id, fun_cfg) in full_cfg_list.iter().enumerate() {
+            full_dot_text.push_str(format!("// CFG #{cfg_id}\n").as_str());
+            full_dot_text.push_str(cfg_to_dot(fun_cfg).as_str());
+        }
+
+        Some(full_dot_text)
+    }
+
+    /// NOTE: Each finished phase gets its timing recorded, where parsing includes lexing & reading imported sources.
+    pub fn compile_from_start(&mut self, lexicals: HashMap<String, TokenType>) -> Option<bytecode::Program> {
+        let parse_start = Instant::now();
+        let full_program_ast_opt = self.step_parse(lexicals);
+        self.record_phase_timing("parse", parse_start);
+
+        if full_program_ast_opt.is_none() {
+            self.report_failure(CompileError::Parse);
+            return None;
+        }
+
+        let (full_asts, full_src_table) = full_program_ast_opt.unwrap();
+
+        let full_program_ir_opt = if self.streaming {
+            let stream_start = Instant::now();
+            let streamed_ir_opt = self.step_stream_bodies(full_asts, full_src_table);
+            self.record_phase_timing("streamed semantics & ir", stream_start);
+
+            streamed_ir_opt
+        } else {
+            let sema_start = Instant::now();
+            let sema_passed = self.step_sema(&full_asts, &full_src_table);
+            self.record_phase_timing("semantics", sema_start);
+
+            if !sema_passed {
+                self.report_sema_failure();
+                return None;
+            }
+
+            let ir_start = Instant::now();
+            let full_ir_opt = self.step_ir_emit(&full_asts, &full_src_table);
+            self.record_phase_timing("ir", ir_start);
+
+            if full_ir_opt.is_none() {
+                self.report_failure(CompileError::IREmit);
+            }
+
+            full_ir_opt
+        };
+
+        let mut full_program_ir = full_program_ir_opt?;
 
         // NOTE: Debug with below calls only!
-        let temp_bc = self.step_bc_emit(&mut full_program_ir);
-        disassemble_program(temp_bc.as_ref().unwrap());
+        let bc_start = Instant::now();
+        let mut temp_bc = self.step_bc_emit(&mut full_program_ir);
+        self.record_phase_timing("bytecode", bc_start);
+
+        if let Some(program_mut) = temp_bc.as_mut() {
+            program_mut.set_function_exports(self.ir_emitter.take_function_exports());
+        }
+
+        if let Some(program_ref) = temp_bc.as_ref()
+            && self.log_level.allows(LogLevel::Verbose) {
+            disassemble_program(program_ref);
+            println!("{}", program_ref.summary());
+        }
+
         temp_bc
         // None
 
@@ -2,15 +2,23 @@ use crate::codegen::ir::Locator;
 use crate::frontend::token::{Token, TokenType};
 use crate::semantics::types::{self, AccessFlag};
 use crate::semantics::scope::SemanticNote;
+use crate::vm::value::Value;
 
 pub struct ParamDecl {
     name_token: Token,
     typing: Box<dyn types::TypeKind>,
+
+    /// INFO: constructor parameters marked with `let` e.g `ctor(let value: int)` are stored into the same-named field before the body runs.
+    binds_field: bool,
 }
 
 impl ParamDecl {
-    pub fn new(name_token: Token, typing: Box<dyn types::TypeKind>) -> Self {
-        Self { name_token, typing }
+    pub fn new(name_token: Token, typing: Box<dyn types::TypeKind>, binds_field: bool) -> Self {
+        Self { name_token, typing, binds_field }
+    }
+
+    pub fn binds_field(&self) -> bool {
+        self.binds_field
     }
 
     pub fn get_name_token(&self) -> &Token {
@@ -29,6 +37,7 @@ pub trait ExprVisitor<'evl, Res> {
     // fn visit_lambda(&self) -> Res;
     fn visit_unary(&mut self, e: &Unary) -> Res;
     fn visit_binary(&mut self, e: &Binary) -> Res;
+    fn visit_index(&mut self, e: &Index) -> Res;
 }
 
 pub trait Expr {
@@ -37,6 +46,22 @@ pub trait Expr {
     fn try_deduce_type(&self) -> Box<dyn types::TypeKind>;
     fn accept_visitor(&self, visitor: &mut dyn ExprVisitor<Option<Locator>>) -> Option<Locator>;
     fn accept_visitor_sema(&self, visitor: &mut dyn ExprVisitor<SemanticNote>) -> SemanticNote;
+    fn accept_visitor_const(&self, visitor: &mut dyn ExprVisitor<Option<Value>>) -> Option<Value>;
+
+    /// NOTE: Gives this expr as an `Index` if it is one, e.g for lowering `s[i] = c` differently from other assignments.
+    fn as_index(&self) -> Option<&Index> {
+        None
+    }
+
+    /// NOTE: Gives this expr as a `Binary` if it is one, e.g for checking the inner assignment of a chain like `a = s[i] = c`.
+    fn as_binary(&self) -> Option<&Binary> {
+        None
+    }
+
+    /// NOTE: Gives this expr as a `Call` if it is one, e.g for finding the class of a chained call's result like `obj.next()` in `obj.next().get()`.
+    fn as_call(&self) -> Option<&Call> {
+        None
+    }
 }
 
 pub struct Primitive {
@@ -69,6 +94,7 @@ impl Expr for Primitive {
             TokenType::LiteralBool => types::PrimitiveTag::Boolean,
             TokenType::LiteralInt => types::PrimitiveTag::Integer,
             TokenType::LiteralFloat => types::PrimitiveTag::Floating,
+            TokenType::LiteralLong => types::PrimitiveTag::Long,
             _ => types::PrimitiveTag::Unknown,
         };
 
@@ -82,6 +108,10 @@ impl Expr for Primitive {
     fn accept_visitor_sema(&self, visitor: &mut dyn ExprVisitor<SemanticNote>) -> SemanticNote {
         visitor.visit_primitive(self)
     }
+
+    fn accept_visitor_const(&self, visitor: &mut dyn ExprVisitor<Option<Value>>) -> Option<Value> {
+        visitor.visit_primitive(self)
+    }
 }
 
 pub struct Call {
@@ -123,6 +153,14 @@ impl Expr for Call {
     fn accept_visitor_sema(&self, visitor: &mut dyn ExprVisitor<SemanticNote>) -> SemanticNote {
         visitor.visit_call(self)
     }
+
+    fn accept_visitor_const(&self, visitor: &mut dyn ExprVisitor<Option<Value>>) -> Option<Value> {
+        visitor.visit_call(self)
+    }
+
+    fn as_call(&self) -> Option<&Call> {
+        Some(self)
+    }
 }
 
 // pub struct Array {
@@ -171,6 +209,8 @@ impl Expr for Unary {
                 types::OperatorTag::Minus => inner_type_box,
                 types::OperatorTag::Increment => inner_type_box,
                 types::OperatorTag::Decrement => inner_type_box,
+                types::OperatorTag::PostIncrement => inner_type_box,
+                types::OperatorTag::PostDecrement => inner_type_box,
                 _ => Box::new(types::PrimitiveInfo::new(types::PrimitiveTag::Unknown)),
             };
         }
@@ -185,6 +225,10 @@ impl Expr for Unary {
     fn accept_visitor_sema(&self, visitor: &mut dyn ExprVisitor<SemanticNote>) -> SemanticNote {
         visitor.visit_unary(self)
     }
+
+    fn accept_visitor_const(&self, visitor: &mut dyn ExprVisitor<Option<Value>>) -> Option<Value> {
+        visitor.visit_unary(self)
+    }
 }
 
 pub struct Binary {
@@ -228,11 +272,77 @@ impl Expr for Binary {
     fn accept_visitor_sema(&self, visitor: &mut dyn ExprVisitor<SemanticNote>) -> SemanticNote {
         visitor.visit_binary(self)
     }
+
+    fn accept_visitor_const(&self, visitor: &mut dyn ExprVisitor<Option<Value>>) -> Option<Value> {
+        visitor.visit_binary(self)
+    }
+
+    fn as_binary(&self) -> Option<&Binary> {
+        Some(self)
+    }
+}
+
+/// NOTE: Denotes an indexing expr like `s[i]`, which reads a char of a varchar. As an assignment's LHS e.g `s[i] = c`, it writes that char instead.
+pub struct Index {
+    target: Box<dyn Expr>,
+    index: Box<dyn Expr>,
+
+    /// INFO: the opening `[` token, which locates the expr for diagnostics.
+    bracket_token: Token,
+}
+
+impl Index {
+    pub fn new(target: Box<dyn Expr>, index: Box<dyn Expr>, bracket_token: Token) -> Self {
+        Self { target, index, bracket_token }
+    }
+
+    pub fn get_target(&self) -> &dyn Expr {
+        &*self.target
+    }
+
+    pub fn get_index(&self) -> &dyn Expr {
+        &*self.index
+    }
+
+    pub fn get_bracket_token(&self) -> &Token {
+        &self.bracket_token
+    }
+}
+
+impl Expr for Index {
+    fn get_operator(&self) -> types::OperatorTag {
+        types::OperatorTag::Index
+    }
+
+    fn get_token_opt(&self) -> Option<Token> {
+        None
+    }
+
+    fn try_deduce_type(&self) -> Box<dyn types::TypeKind> {
+        Box::new(types::PrimitiveInfo::new(types::PrimitiveTag::Char))
+    }
+
+    fn accept_visitor(&self, visitor: &mut dyn ExprVisitor<Option<Locator>>) -> Option<Locator> {
+        visitor.visit_index(self)
+    }
+
+    fn accept_visitor_sema(&self, visitor: &mut dyn ExprVisitor<SemanticNote>) -> SemanticNote {
+        visitor.visit_index(self)
+    }
+
+    fn accept_visitor_const(&self, visitor: &mut dyn ExprVisitor<Option<Value>>) -> Option<Value> {
+        visitor.visit_index(self)
+    }
+
+    fn as_index(&self) -> Option<&Index> {
+        Some(self)
+    }
 }
 
 pub trait StmtVisitor<Res> {
     fn visit_import(&mut self, s: &Import) -> Res;
     fn visit_foreign_stub(&mut self, s: &ForeignStub) -> Res;
+    fn visit_const_decl(&mut self, s: &ConstDecl) -> Res;
     fn visit_function_decl(&mut self, s: &FunctionDecl) -> Res;
     fn visit_field_decl(&mut self, s: &FieldDecl) -> Res;
     fn visit_constructor_decl(&mut self, s: &ConstructorDecl) -> Res;
@@ -242,6 +352,7 @@ pub trait StmtVisitor<Res> {
     fn visit_variable_decl(&mut self, s: &VariableDecl) -> Res;
     fn visit_if(&mut self, s: &If) -> Res;
     fn visit_while(&mut self, s: &While) -> Res;
+    fn visit_foreach(&mut self, s: &Foreach) -> Res;
     fn visit_return(&mut self, s: &Return) -> Res;
     fn visit_expr_stmt(&mut self, s: &ExprStmt) -> Res;
 }
@@ -291,17 +402,25 @@ pub struct ForeignStub {
     name_token: Token,
     params: Vec<ParamDecl>,
     result_typing: Box<dyn types::TypeKind>,
+
+    /// INFO: text of the `#` comment lines just above this declaration for documentation tooling, or `None` if there are none.
+    doc: Option<String>,
 }
 
 impl ForeignStub {
-    pub fn new(name_token_arg: Token, params_arg: Vec<ParamDecl>, result_typing_arg: Box<dyn types::TypeKind>) -> Self {
+    pub fn new(name_token_arg: Token, params_arg: Vec<ParamDecl>, result_typing_arg: Box<dyn types::TypeKind>, doc_arg: Option<String>) -> Self {
         Self {
             name_token: name_token_arg,
             params: params_arg,
             result_typing: result_typing_arg,
+            doc: doc_arg,
         }
     }
 
+    pub fn get_doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
     pub fn get_name_token(&self) -> &Token {
         &self.name_token
     }
@@ -333,23 +452,94 @@ impl Stmt for ForeignStub {
     }
 }
 
+/// NOTE: A module-level `const NAME: type = <literal expr>;` whose folded value replaces every use of `NAME`.
+pub struct ConstDecl {
+    name_token: Token,
+    typing: Box<dyn types::TypeKind>,
+    init_expr: Box<dyn Expr>,
+
+    /// INFO: text of the `#` comment lines just above this declaration for documentation tooling, or `None` if there are none.
+    doc: Option<String>,
+}
+
+impl ConstDecl {
+    pub fn new(name_token: Token, typing: Box<dyn types::TypeKind>, init_expr: Box<dyn Expr>, doc: Option<String>) -> Self {
+        Self {
+            name_token,
+            typing,
+            init_expr,
+            doc,
+        }
+    }
+
+    pub fn get_doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
+    pub fn get_name_token(&self) -> &Token {
+        &self.name_token
+    }
+
+    pub fn get_typing(&self) -> &dyn types::TypeKind {
+        &*self.typing
+    }
+
+    pub fn get_init_expr(&self) -> &dyn Expr {
+        &*self.init_expr
+    }
+}
+
+impl Stmt for ConstDecl {
+    fn is_directive(&self) -> bool {
+        false
+    }
+
+    fn is_declaration(&self) -> bool {
+        true
+    }
+
+    fn is_expr_stmt(&self) -> bool {
+        false
+    }
+
+    fn accept_visitor(&self, v: &mut dyn StmtVisitor<bool>) -> bool {
+        v.visit_const_decl(self)
+    }
+}
+
 pub struct FunctionDecl {
     name_token: Token,
     params: Vec<ParamDecl>,
     result_typing: Box<dyn types::TypeKind>,
     body: Box<dyn Stmt>,
+
+    /// INFO: text of the `#` comment lines just above this declaration for documentation tooling, or `None` if there are none.
+    doc: Option<String>,
+
+    /// INFO: a `private` function may only be called within its own module, while unmarked functions stay public.
+    access: AccessFlag,
 }
 
 impl FunctionDecl {
-    pub fn new(name_token: Token, params: Vec<ParamDecl>, result_typing: Box<dyn types::TypeKind>, body: Box<dyn Stmt>) -> Self {
+    pub fn new(name_token: Token, params: Vec<ParamDecl>, result_typing: Box<dyn types::TypeKind>, body: Box<dyn Stmt>, doc: Option<String>, access: AccessFlag) -> Self {
         Self {
             name_token,
             params,
             result_typing,
             body,
+            doc,
+            access,
         }
     }
 
+    pub fn get_access(&self) -> AccessFlag {
+        self.access
+    }
+
+    pub fn get_doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
     pub fn get_name_token(&self) -> &Token {
         &self.name_token
     }
@@ -471,18 +661,26 @@ pub struct MethodDecl {
     params: Vec<ParamDecl>,
     result_typing: Box<dyn types::TypeKind>,
     body: Box<dyn Stmt>,
+
+    /// INFO: static methods belong to the class itself, so they're called without an instance e.g `Box.make(1)`.
+    is_static: bool,
 }
 
 impl MethodDecl {
-    pub fn new(name_token: Token, params: Vec<ParamDecl>, result_typing: Box<dyn types::TypeKind>, body: Box<dyn Stmt>) -> Self {
+    pub fn new(name_token: Token, params: Vec<ParamDecl>, result_typing: Box<dyn types::TypeKind>, body: Box<dyn Stmt>, is_static: bool) -> Self {
         Self {
             name_token,
             params,
             result_typing,
             body,
+            is_static,
         }
     }
 
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+
     pub fn get_name_token(&self) -> &Token {
         &self.name_token
     }
@@ -523,16 +721,24 @@ pub type ClassMemberDecl = (Box<dyn Stmt>, AccessFlag);
 pub struct ClassDecl {
     members: Vec<ClassMemberDecl>,
     named_type: Box<dyn types::TypeKind>,
+
+    /// INFO: text of the `#` comment lines just above this declaration for documentation tooling, or `None` if there are none.
+    doc: Option<String>,
 }
 
 impl ClassDecl {
-    pub fn new(members_arg: Vec<ClassMemberDecl>, named_type_arg: Box<dyn types::TypeKind>) -> Self {
+    pub fn new(members_arg: Vec<ClassMemberDecl>, named_type_arg: Box<dyn types::TypeKind>, doc_arg: Option<String>) -> Self {
         Self {
             members: members_arg,
             named_type: named_type_arg,
+            doc: doc_arg,
         }
     }
 
+    pub fn get_doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
     pub fn get_members(&self) -> &Vec<ClassMemberDecl> {
         &self.members
     }
@@ -646,17 +852,25 @@ pub struct If {
     truthy: Box<dyn Stmt>,
     falsy: Box<dyn Stmt>,
     check: Box<dyn Expr>,
+
+    /// INFO: the `else` keyword if one was written, since a missing `else` still gets an empty falsy body.
+    else_token: Option<Token>,
 }
 
 impl If {
-    pub fn new(truthy: Box<dyn Stmt>, falsy: Box<dyn Stmt>, check: Box<dyn Expr>) -> Self {
+    pub fn new(truthy: Box<dyn Stmt>, falsy: Box<dyn Stmt>, check: Box<dyn Expr>, else_token: Option<Token>) -> Self {
         Self {
             truthy,
             falsy,
             check,
+            else_token,
         }
     }
 
+    pub fn get_else_token(&self) -> Option<&Token> {
+        self.else_token.as_ref()
+    }
+
     pub fn get_truthy_body(&self) -> &dyn Stmt {
         &*self.truthy
     }
@@ -728,17 +942,70 @@ impl Stmt for While {
     }
 }
 
+/// NOTE: A `foreach (c : s) { ... }` loop binding each char of the varchar `s` to `c` in order.
+pub struct Foreach {
+    item_token: Token,
+    target: Box<dyn Expr>,
+    body: Box<dyn Stmt>,
+}
+
+impl Foreach {
+    pub fn new(item_token_arg: Token, target_arg: Box<dyn Expr>, body_arg: Box<dyn Stmt>) -> Self {
+        Self {
+            item_token: item_token_arg,
+            target: target_arg,
+            body: body_arg,
+        }
+    }
+
+    pub fn get_item_token(&self) -> &Token {
+        &self.item_token
+    }
+
+    pub fn get_target(&self) -> &dyn Expr {
+        &*self.target
+    }
+
+    pub fn get_body(&self) -> &dyn Stmt {
+        &*self.body
+    }
+}
+
+impl Stmt for Foreach {
+    fn is_directive(&self) -> bool {
+        false
+    }
+
+    fn is_declaration(&self) -> bool {
+        false
+    }
+
+    fn is_expr_stmt(&self) -> bool {
+        false
+    }
+
+    fn accept_visitor(&self, v: &mut dyn StmtVisitor<bool>) -> bool {
+        v.visit_foreach(self)
+    }
+}
+
+/// NOTE: A bare `return;` has no result, which only constructors allow as they implicitly give their new instance.
 pub struct Return {
-    result: Box<dyn Expr>,
+    return_token: Token,
+    result_opt: Option<Box<dyn Expr>>,
 }
 
 impl Return {
-    pub fn new(result: Box<dyn Expr>) -> Self {
-        Self { result }
+    pub fn new(return_token: Token, result_opt: Option<Box<dyn Expr>>) -> Self {
+        Self { return_token, result_opt }
+    }
+
+    pub fn get_return_token(&self) -> &Token {
+        &self.return_token
     }
 
-    pub fn get_result(&self) -> &dyn Expr {
-        &*self.result
+    pub fn get_result(&self) -> Option<&dyn Expr> {
+        self.result_opt.as_deref()
     }
 }
 
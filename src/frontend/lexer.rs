@@ -30,25 +30,44 @@ pub mod matchers {
     }
 }
 
+/// NOTE: The symbols Loxie's own operators are made of. A maximal run of these is lexed as one operator, e.g `++` or `!=`.
+pub const DEFAULT_OPERATOR_CHARS: [char; 9] = ['.', '+', '-', '*', '/', '!', '=', '<', '>'];
+
+/// NOTE: How many columns apart tab stops are, so a tab moves the column to the next one like editors do.
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
 pub struct Lexer<'ll_1> {
     source: &'ll_1 str,
     pos: usize,
     end: usize,
     line: usize,
-    column: usize
+    column: usize,
+
+    /// INFO: symbols which may form operators, so embedders can lex extra operators e.g `**` by adding its lexeme to the lexical items too.
+    operator_chars: Vec<char>,
+
+    /// INFO: columns between tab stops, which a tab advances the column to.
+    tab_width: usize,
 }
 
 impl<'ll_2> Lexer<'ll_2> {
-    pub fn new(source_view: &'ll_2 str) -> Self {
+    pub fn new(source_view: &'ll_2 str, operator_chars: &[char]) -> Self {
         Self {
             source: source_view,
             pos: 0,
             end: source_view.len(),
             line: 1,
             column: 1,
+            operator_chars: operator_chars.to_vec(),
+            tab_width: DEFAULT_TAB_WIDTH,
         }
     }
 
+    /// NOTE: Sets the columns between tab stops e.g `8`, which should match the editor showing the reported positions. Any value below `1` is treated as `1`.
+    pub fn set_tab_width(&mut self, tab_width: usize) {
+        self.tab_width = tab_width.max(1);
+    }
+
     pub fn reset_with(&mut self, next_source: &'ll_2 str) {
         self.source = next_source;
         self.pos = 0;
@@ -65,6 +84,7 @@ impl<'ll_2> Lexer<'ll_2> {
         self.pos >= self.end
     }
 
+    /// NOTE: Peeks by byte since token positions are byte offsets. Any non-ASCII byte matches no symbol class, so it's lexed as a `StrayChar` unless it's within a comment or literal.
     fn peek_off(&self, offset: usize) -> char {
         let raw_src_pos = self.pos + offset;
 
@@ -72,7 +92,7 @@ impl<'ll_2> Lexer<'ll_2> {
             return '\0';
         }
 
-        self.source.chars().nth(raw_src_pos).expect("Could not peek symbol at {raw_src_pos}")
+        self.source.as_bytes().get(raw_src_pos).map_or('\0', |b| *b as char)
     }
 
     fn update_source_location(&mut self, c: char) {
@@ -81,6 +101,9 @@ impl<'ll_2> Lexer<'ll_2> {
                 self.line += 1;
                 self.column = 1;
             },
+            '\t' => {
+                self.column += self.tab_width - (self.column - 1) % self.tab_width;
+            },
             _ => {
                 self.column += 1;
             }
@@ -98,6 +121,22 @@ impl<'ll_2> Lexer<'ll_2> {
         token_from!(tag, temp_start, 1, temp_line, temp_column)
     }
 
+    /// NOTE: Lexes a character that begins no token e.g `@`, spanning all bytes of a non-ASCII one so it's reported once by the parser.
+    fn lex_stray_char(&mut self) -> Token {
+        let temp_start = self.pos;
+        let temp_line = self.line;
+        let temp_column = self.column;
+
+        let temp_len = self.source.get(temp_start..)
+            .and_then(|rest| rest.chars().next())
+            .map_or(1, char::len_utf8);
+
+        self.update_source_location(self.peek_off(0));
+        self.pos += temp_len;
+
+        token_from!(TokenType::StrayChar, temp_start, temp_len, temp_line, temp_column)
+    }
+
     fn lex_spaces(&mut self) -> Token {
         let temp_start = self.pos;
         let mut temp_len: usize = 0;
@@ -164,12 +203,10 @@ impl<'ll_2> Lexer<'ll_2> {
         let result: Token = token_from!(TokenType::Unknown, temp_start, temp_len, temp_line, temp_column);
         let source_view = self.source;
 
-        let result_lexeme = result.to_lexeme_str(source_view).or(Some("")).expect("Lexer::lex_word panicked while getting lexeme");
+        let result_lexeme = result.to_lexeme_str(source_view).unwrap_or("");
 
         token_from!(
-            *items.get(result_lexeme).or(
-                Some(&TokenType::Identifier)
-            ).expect("Lexer::lex_word panicked while deducing lexical tag"),
+            *items.get(result_lexeme).unwrap_or(&TokenType::Identifier),
             temp_start,
             temp_len,
             temp_line,
@@ -177,6 +214,35 @@ impl<'ll_2> Lexer<'ll_2> {
         )
     }
 
+    /// NOTE: Lexes a backtick-escaped name e.g `` `if` `` as an `Identifier` spanning only the inner word, so reserved words can still name things. An empty, unclosed, or non-word escape is `Unknown`.
+    fn lex_escaped_word(&mut self) -> Token {
+        // Skip first '`' symbol here
+        self.update_source_location(self.peek_off(0));
+        self.pos += 1;
+
+        let temp_start = self.pos;
+        let mut temp_len: usize = 0;
+        let temp_line = self.line;
+        let temp_column = self.column;
+
+        while !self.at_end() && matchers::check_alpha(self.peek_off(0)) {
+            self.update_source_location(self.peek_off(0));
+            temp_len += 1;
+            self.pos += 1;
+        }
+
+        let closed = self.peek_off(0) == '`';
+
+        if closed {
+            self.update_source_location('`');
+            self.pos += 1;
+        }
+
+        let temp_tag = if closed && temp_len > 0 { TokenType::Identifier } else { TokenType::Unknown };
+
+        token_from!(temp_tag, temp_start, temp_len.max(1), temp_line, temp_column)
+    }
+
     fn lex_char(&mut self) -> Token {
         // Skip first '\'' symbol here
         self.update_source_location(self.peek_off(0));
@@ -188,6 +254,7 @@ impl<'ll_2> Lexer<'ll_2> {
         let temp_column = self.column;
         let mut escapes = 0;
         let mut closed = false;
+        let mut hex_escape_ok = true;
 
         while !self.at_end() {
             let s = self.peek_off(0);
@@ -201,12 +268,17 @@ impl<'ll_2> Lexer<'ll_2> {
 
             if s == '\\' {
                 escapes += 1;
+
+                // NOTE: a hex escape like `'\x41'` must be exactly 2 hex digits of an ASCII byte with nothing after them.
+                if self.peek_off(0) == 'x' {
+                    hex_escape_ok = temp_len == 0 && self.check_hex_escape() && self.peek_off(3) == '\'';
+                }
             }
 
             temp_len += 1;
         }
 
-        let temp_tag = if escapes <= 1 && closed { TokenType::LiteralChar } else { TokenType::Unknown };
+        let temp_tag = if escapes <= 1 && closed && hex_escape_ok { TokenType::LiteralChar } else { TokenType::Unknown };
 
         token_from!(temp_tag, temp_start, temp_len, temp_line, temp_column)
     }
@@ -234,6 +306,20 @@ impl<'ll_2> Lexer<'ll_2> {
             self.pos += 1;
         }
 
+        // NOTE: an exponent e.g the `e-3` of `2.5e-3` makes a float literal, but it needs digits after its optional sign.
+        if dots <= 1 && !self.at_end() && matchers::check_multi(self.peek_off(0), ['e', 'E']) {
+            return self.lex_exponent(temp_start, temp_len, temp_line, temp_column);
+        }
+
+        // NOTE: an `L` suffix marks a `long` integer literal e.g `100L`, and it stays in the lexeme.
+        if dots == 0 && !self.at_end() && self.peek_off(0) == 'L' {
+            self.update_source_location('L');
+            temp_len += 1;
+            self.pos += 1;
+
+            return token_from!(TokenType::LiteralLong, temp_start, temp_len, temp_line, temp_column);
+        }
+
         match dots {
             0 => token_from!(TokenType::LiteralInt, temp_start, temp_len, temp_line, temp_column),
             1 => token_from!(TokenType::LiteralFloat, temp_start, temp_len, temp_line, temp_column),
@@ -241,6 +327,45 @@ impl<'ll_2> Lexer<'ll_2> {
         }
     }
 
+    /// NOTE: Lexes the rest of a float literal from its exponent's `e` or `E`. A malformed exponent e.g `1e` or `1e+` keeps its consumed symbols in an `Unknown` token so the parser reports all of it.
+    fn lex_exponent(&mut self, temp_start: usize, mut temp_len: usize, temp_line: usize, temp_column: usize) -> Token {
+        self.update_source_location(self.peek_off(0));
+        temp_len += 1;
+        self.pos += 1;
+
+        if !self.at_end() && matchers::check_multi(self.peek_off(0), ['+', '-']) {
+            self.update_source_location(self.peek_off(0));
+            temp_len += 1;
+            self.pos += 1;
+        }
+
+        let mut exponent_digits: usize = 0;
+
+        while !self.at_end() && matchers::check_digit(self.peek_off(0)) {
+            self.update_source_location(self.peek_off(0));
+            exponent_digits += 1;
+            temp_len += 1;
+            self.pos += 1;
+        }
+
+        let temp_tag = if exponent_digits > 0 { TokenType::LiteralFloat } else { TokenType::Unknown };
+
+        token_from!(temp_tag, temp_start, temp_len, temp_line, temp_column)
+    }
+
+    /// NOTE: checks the 2 hex digits after a `\x` escape, which must be an ASCII byte to keep varchars ASCII-only.
+    fn check_hex_escape(&self) -> bool {
+        let hex_hi = self.peek_off(1);
+        let hex_lo = self.peek_off(2);
+
+        if !hex_hi.is_ascii_hexdigit() || !hex_lo.is_ascii_hexdigit() {
+            return false;
+        }
+
+        hex_hi.to_digit(16).unwrap_or(8) < 8
+    }
+
+    /// NOTE: A string may span lines, keeping each newline in its text. Every consumed symbol still updates the source location, so tokens after the string get accurate lines & columns.
     fn lex_string(&mut self) -> Token {
         self.update_source_location(self.peek_off(0));
         self.pos += 1;
@@ -250,6 +375,7 @@ impl<'ll_2> Lexer<'ll_2> {
         let temp_line = self.line;
         let temp_column = self.column;
         let mut closed = false;
+        let mut bad_escapes = 0;
 
         while !self.at_end() {
             let s = self.peek_off(0);
@@ -262,10 +388,23 @@ impl<'ll_2> Lexer<'ll_2> {
                 break;
             }
 
+            // NOTE: the symbol after a `\` belongs to its escape, so `"\\xZZ"` is a backslash before `xZZ` and `"\""` doesn't end the string.
+            if s == '\\' && !self.at_end() {
+                let escaped = self.peek_off(0);
+
+                if escaped == 'x' && !self.check_hex_escape() {
+                    bad_escapes += 1;
+                }
+
+                self.update_source_location(escaped);
+                self.pos += 1;
+                temp_len += 1;
+            }
+
             temp_len += 1;
         }
 
-        let temp_tag = if closed { TokenType::LiteralVarchar } else { TokenType::Unknown };
+        let temp_tag = if closed && bad_escapes == 0 { TokenType::LiteralVarchar } else { TokenType::Unknown };
 
         token_from!(temp_tag, temp_start, temp_len, temp_line, temp_column)
     }
@@ -279,7 +418,7 @@ impl<'ll_2> Lexer<'ll_2> {
         while !self.at_end() {
             let s = self.peek_off(0);
 
-            if !matchers::check_multi(s, ['.', '+', '-', '*', '/', '!', '=', '<', '>']) {
+            if !self.operator_chars.contains(&s) {
                 break;
             }
 
@@ -291,12 +430,10 @@ impl<'ll_2> Lexer<'ll_2> {
         let result: Token = token_from!(TokenType::Unknown, temp_start, temp_len, temp_line, temp_column);
         let source_view = self.source;
 
-        let result_lexeme = result.to_lexeme_str(source_view).or(Some("")).expect("Lexer::lex_operator panicked while getting lexeme");
+        let result_lexeme = result.to_lexeme_str(source_view).unwrap_or("");
 
         token_from!(
-            *items.get(result_lexeme).or(
-                Some(&TokenType::Unknown)
-            ).expect("Lexer::lex_operator panicked while deducing lexical tag"),
+            *items.get(result_lexeme).unwrap_or(&TokenType::Unknown),
             temp_start,
             temp_len,
             temp_line,
@@ -311,10 +448,10 @@ impl<'ll_2> Lexer<'ll_2> {
             self.lex_word(items)
         } else if matchers::check_digit(c) {
             self.lex_numbers()
-        } else if matchers::check_multi(c, ['.', '+', '-', '*', '/', '!', '=', '<', '>']) {
+        } else if self.operator_chars.contains(&c) {
             self.lex_operator(items)
         } else {
-            self.lex_single(TokenType::Unknown)
+            self.lex_stray_char()
         }
     }
 
@@ -334,6 +471,7 @@ impl<'ll_2> Lexer<'ll_2> {
         match next_symbol {
             '#' => self.lex_comment(),
             '\'' => self.lex_char(),
+            '`' => self.lex_escaped_word(),
             '\"' => self.lex_string(),
             ':' => self.lex_single(TokenType::Colon),
             ',' => self.lex_single(TokenType::Comma),
@@ -347,4 +485,77 @@ impl<'ll_2> Lexer<'ll_2> {
             _ => self.lex_complex(next_symbol, items)
         }
     }
-}
\ No newline at end of file
+
+    /// NOTE: Yields every token including spaces & comments, stopping before `Eof`. This is meant for tooling that needs the raw token stream.
+    pub fn tokens<'tl>(&'tl mut self, items: &'ll_2 HashMap<String, TokenType>) -> impl Iterator<Item = Token> + 'tl {
+        std::iter::from_fn(move || {
+            let next_token = self.lex_next(items);
+
+            if next_token.tag == TokenType::Eof {
+                None
+            } else {
+                Some(next_token)
+            }
+        })
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_keep_spaces_and_comments_until_eof() {
+        let lexicals = crate::setup_lexical_items();
+        let source = "# note\nlet x";
+        let mut lexer = Lexer::new(source, &DEFAULT_OPERATOR_CHARS);
+
+        let tags: Vec<TokenType> = lexer.tokens(&lexicals).map(|token| token.tag).collect();
+
+        assert_eq!(tags, [TokenType::Comment, TokenType::Spaces, TokenType::Keyword, TokenType::Spaces, TokenType::Identifier]);
+        assert!(lexer.lex_next(&lexicals).tag == TokenType::Eof);
+    }
+
+    #[test]
+    fn tokens_track_lines_and_columns() {
+        let lexicals = crate::setup_lexical_items();
+        let source = "let x\n  = 1;";
+        let mut lexer = Lexer::new(source, &DEFAULT_OPERATOR_CHARS);
+
+        let assign_token = lexer.tokens(&lexicals).find(|token| token.tag == TokenType::OpAssign).unwrap();
+
+        assert_eq!((assign_token.line_no, assign_token.col_no), (2, 3));
+        assert_eq!(assign_token.to_lexeme_str(source), Some("="));
+    }
+
+    fn lex_tags(source: &str, operator_chars: &[char], lexicals: &HashMap<String, TokenType>) -> Vec<(TokenType, usize)> {
+        let mut lexer = Lexer::new(source, operator_chars);
+
+        lexer.tokens(lexicals).filter(|token| token.tag != TokenType::Spaces).map(|token| (token.tag, token.length)).collect()
+    }
+
+    #[test]
+    fn custom_operators_lex_as_registered() {
+        let mut lexicals = crate::setup_lexical_items();
+
+        assert_eq!(lex_tags("2 ** 3", &DEFAULT_OPERATOR_CHARS, &lexicals)[1], (TokenType::Unknown, 2));
+
+        lexicals.insert(String::from("**"), TokenType::OpTimes);
+
+        assert_eq!(lex_tags("2 ** 3", &DEFAULT_OPERATOR_CHARS, &lexicals), [(TokenType::LiteralInt, 1), (TokenType::OpTimes, 2), (TokenType::LiteralInt, 1)]);
+        assert_eq!(lex_tags("2 * 3", &DEFAULT_OPERATOR_CHARS, &lexicals)[1], (TokenType::OpTimes, 1));
+    }
+
+    #[test]
+    fn custom_operator_chars_group_into_operators() {
+        let mut lexicals = crate::setup_lexical_items();
+        lexicals.insert(String::from("%"), TokenType::OpSlash);
+
+        let mut operator_chars = DEFAULT_OPERATOR_CHARS.to_vec();
+
+        assert_eq!(lex_tags("7 % 2", &operator_chars, &lexicals)[1].0, TokenType::StrayChar);
+
+        operator_chars.push('%');
+
+        assert_eq!(lex_tags("7 % 2", &operator_chars, &lexicals)[1], (TokenType::OpSlash, 1));
+    }
+}
@@ -9,6 +9,9 @@ use crate::token_from;
 pub type ASTDecls = Vec<Box<dyn Stmt>>;
 pub type ParseResult = (Option<ASTDecls>, VecDeque<String>);
 
+/// NOTE: How many syntax errors a parser reports by default before it stops.
+pub const DEFAULT_PARSE_ERROR_MAX: i32 = 5;
+
 pub struct Parser<'pl_1> {
     tokenizer: Lexer<'pl_1>,
     next_sources: VecDeque<String>,
@@ -16,6 +19,18 @@ pub struct Parser<'pl_1> {
     current: Token,
     error_count: i32,
     parse_error_max: i32,
+
+    /// INFO: text & last line of the run of own-line comments lexed so far, which becomes the doc of a declaration starting on the very next line.
+    pending_doc: Option<(String, usize)>,
+
+    /// INFO: doc comment right above the current token, taken by top-level declarations.
+    current_doc: Option<String>,
+
+    /// INFO: line of the last non-comment token, which tells apart trailing comments from own-line ones.
+    last_code_line: usize,
+
+    /// INFO: line of the last stray character reported, whose later errors on that line likely follow from it & are left out.
+    stray_char_line: usize,
 }
 
 impl<'pl_2> Parser<'pl_2> {
@@ -26,10 +41,19 @@ impl<'pl_2> Parser<'pl_2> {
             previous: token_from!(TokenType::Unknown, 0, 1, 1, 1),
             current: token_from!(TokenType::Unknown, 0, 1, 1, 1),
             error_count: 0,
-            parse_error_max: 5,
+            parse_error_max: DEFAULT_PARSE_ERROR_MAX,
+            pending_doc: None,
+            current_doc: None,
+            last_code_line: 0,
+            stray_char_line: 0,
         }
     }
 
+    /// NOTE: Sets how many syntax errors get reported before parsing stops, e.g `1` for just the first or `i32::MAX` for all of them. Any value below `1` is treated as `1`.
+    pub fn set_parse_error_max(&mut self, max_count: i32) {
+        self.parse_error_max = max_count.max(1);
+    }
+
     fn previous(&self) -> &Token {
         &self.previous
     }
@@ -48,35 +72,88 @@ impl<'pl_2> Parser<'pl_2> {
             let temp_tag = temp.tag;
 
             match temp_tag {
-                TokenType::Spaces | TokenType::Comment => {
+                TokenType::Spaces => {
+                    continue;
+                }
+                TokenType::Comment => {
+                    self.collect_doc_line(&temp);
+                    continue;
+                }
+                // NOTE: a stray character is reported right where it is & then skipped, so it can't confuse the parsing of what's around it.
+                TokenType::StrayChar => {
+                    let stray_lexeme = temp.to_lexeme_str(self.tokenizer.view_source()).unwrap_or("<invalid>").to_string();
+
+                    if !self.report_at(&temp, &format!("Unknown character '{stray_lexeme}' begins no token- It may be a typo.")) {
+                        return token_from!(TokenType::Eof, self.tokenizer.view_source().len(), 1, temp.line_no, temp.col_no);
+                    }
+
+                    self.stray_char_line = temp.line_no;
                     continue;
                 }
                 _ => {
+                    self.current_doc = self.pending_doc.take()
+                        .filter(|(_, doc_end_line)| doc_end_line + 1 == temp.line_no)
+                        .map(|(doc_text, _)| doc_text);
+                    self.last_code_line = temp.line_no;
+
                     return temp;
                 }
             }
         }
     }
 
+    /// NOTE: Joins adjacent own-line comments into one doc text, while a trailing comment after code on the same line or a gap of lines discards what was collected.
+    fn collect_doc_line(&mut self, comment: &Token) {
+        if comment.line_no == self.last_code_line {
+            self.pending_doc = None;
+            return;
+        }
+
+        let comment_text = comment.to_lexeme_str(self.tokenizer.view_source()).unwrap_or("").trim();
+
+        self.pending_doc = match self.pending_doc.take() {
+            Some((mut doc_text, doc_end_line)) if doc_end_line + 1 == comment.line_no => {
+                doc_text.push('\n');
+                doc_text.push_str(comment_text);
+                Some((doc_text, comment.line_no))
+            },
+            _ => Some((String::from(comment_text), comment.line_no)),
+        };
+    }
+
     fn match_here<const N: usize>(&self, picks: [TokenType; N]) -> bool {
         picks.contains(&self.current().tag)
     }
 
-    fn recover_and_report(&mut self, msg: &str, items: &'pl_2 HashMap<String, TokenType>) {
-        if self.error_count > self.parse_error_max {
-            return;
+    /// NOTE: Reports a syntax error at the current token without skipping any tokens, returning `false` once the error cap is hit & parsing jumps to EOF.
+    fn report_here(&mut self, msg: &str) -> bool {
+        let culprit = *self.current();
+
+        if !self.report_at(&culprit, msg) {
+            // NOTE: Once the cap is hit, jump to EOF so that parsing stops promptly instead of consuming the rest of the source.
+            self.current = token_from!(TokenType::Eof, self.tokenizer.view_source().len(), 1, self.current.line_no, self.current.col_no);
+            return false;
         }
 
-        let culprit_line = self.current().line_no;
-        let culprit_col = self.current().col_no;
-        let culprit_lexeme_opt = self.current().to_lexeme_str(self.tokenizer.view_source());
+        true
+    }
 
-        if culprit_lexeme_opt.is_none() {
-            return;
+    /// NOTE: Reports a syntax error at the given token, returning `false` without printing once the error cap is hit.
+    fn report_at(&mut self, culprit: &Token, msg: &str) -> bool {
+        if culprit.tag != TokenType::StrayChar && culprit.line_no == self.stray_char_line {
+            return true;
+        }
+
+        if self.error_count >= self.parse_error_max {
+            self.error_count += 1;
+            return false;
         }
 
-        let culprit_lexeme =
-            culprit_lexeme_opt.expect("Unexpected invalid lexeme, out of source bound!");
+        let culprit_line = culprit.line_no;
+        let culprit_col = culprit.col_no;
+
+        // NOTE: A culprit cut mid-character (e.g a stray byte of a non-ASCII symbol) has no valid lexeme, but the error is still counted & recovered from so that parsing keeps moving forward.
+        let culprit_lexeme = culprit.to_lexeme_str(self.tokenizer.view_source()).unwrap_or("<invalid>");
 
         println!(
             "Syntax error #{}:\nCulprit: '{}' at [{}:{}]\nReason: {}",
@@ -85,6 +162,14 @@ impl<'pl_2> Parser<'pl_2> {
 
         self.error_count += 1;
 
+        true
+    }
+
+    fn recover_and_report(&mut self, msg: &str, items: &'pl_2 HashMap<String, TokenType>) {
+        if !self.report_here(msg) {
+            return;
+        }
+
         while !self.at_eof() {
             if self.match_here([TokenType::Keyword]) {
                 break;
@@ -94,6 +179,17 @@ impl<'pl_2> Parser<'pl_2> {
         }
     }
 
+    /// NOTE: Reports a stray `=` after a value e.g `if (x = 1)`, as assignments are only statements and this is usually a mistyped `==` comparison. The stray RHS is skipped so that parsing goes on to find any later errors.
+    fn skip_stray_assign(&mut self, items: &'pl_2 HashMap<String, TokenType>) {
+        if !self.match_here([TokenType::OpAssign]) {
+            return;
+        }
+
+        self.report_here("An assignment cannot be used as a value or condition- Use `==` to compare values instead.");
+        self.consume_any(items);
+        self.parse_compare(items);
+    }
+
     fn consume_any(&mut self, items: &'pl_2 HashMap<String, TokenType>) {
         self.previous = self.current;
         self.current = self.advance(items);
@@ -110,6 +206,22 @@ impl<'pl_2> Parser<'pl_2> {
         false
     }
 
+    /// NOTE: Consumes a declared name, giving a targeted error for reserved words e.g `let if: int = 1;` as they're lexed as keywords, typenames, or bool literals instead of identifiers.
+    fn consume_name(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> bool {
+        if self.match_here([TokenType::Keyword, TokenType::Typename, TokenType::LiteralBool]) {
+            let reserved_lexeme = self.current().to_lexeme_str(self.tokenizer.view_source()).unwrap_or("").to_string();
+
+            // NOTE: Only the reserved word is skipped, since the rest of the declaration is likely fine e.g `: int = 1;` after it.
+            if self.report_here(&format!("Keyword '{reserved_lexeme}' cannot be used as a name, but `{reserved_lexeme}` escapes it into one.")) {
+                self.consume_any(items);
+            }
+
+            return false;
+        }
+
+        self.consume_of([TokenType::Identifier], items)
+    }
+
     fn parse_type(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Box<dyn TypeKind> {
         self.consume_any(items);
         let typename_lexeme = self
@@ -122,8 +234,11 @@ impl<'pl_2> Parser<'pl_2> {
             "char" => Box::new(PrimitiveInfo::new(PrimitiveTag::Char)),
             "int" => Box::new(PrimitiveInfo::new(PrimitiveTag::Integer)),
             "float" => Box::new(PrimitiveInfo::new(PrimitiveTag::Floating)),
+            "long" => Box::new(PrimitiveInfo::new(PrimitiveTag::Long)),
             "varchar" => Box::new(PrimitiveInfo::new(PrimitiveTag::Varchar)),
             "any" => Box::new(PrimitiveInfo::new(PrimitiveTag::Any)),
+            "callback" => Box::new(PrimitiveInfo::new(PrimitiveTag::Callback)),
+            "buffer" => Box::new(PrimitiveInfo::new(PrimitiveTag::Buffer)),
             _ => Box::new(ClassInfo::new(String::from(typename_lexeme))),
         }
     }
@@ -133,6 +248,9 @@ impl<'pl_2> Parser<'pl_2> {
             // println!("parse_primitive ((expr))...");
             self.consume_any(items);
             let parenthesized_expr = self.parse_compare(items);
+
+            self.skip_stray_assign(items);
+
             self.consume_of([TokenType::ParenClose], items);
 
             return parenthesized_expr;
@@ -146,6 +264,7 @@ impl<'pl_2> Parser<'pl_2> {
             TokenType::LiteralChar,
             TokenType::LiteralInt,
             TokenType::LiteralFloat,
+            TokenType::LiteralLong,
             TokenType::LiteralVarchar,
         ], items) {
             // println!("parse_primitive ERROR :(");
@@ -159,109 +278,135 @@ impl<'pl_2> Parser<'pl_2> {
         // TODO ...
     // }
 
-    fn parse_access(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Expr>> {
-        // println!("parse_access --> parse_primitive");
-        let lhs_opt = self.parse_primitive(items);
+    /// NOTE: Parses the args after a call's `(`, which is already consumed.
+    fn parse_call_args(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Vec<Box<dyn Expr>>> {
+        let mut calling_args = Vec::<Box<dyn Expr>>::new();
 
-        lhs_opt.as_ref()?;
+        if self.match_here([TokenType::ParenClose]) {
+            self.consume_any(items);
+            return Some(calling_args);
+        }
+
+        let first_arg_opt = self.parse_compare(items);
 
-        let mut lhs = lhs_opt.unwrap();
+        calling_args.push(first_arg_opt?);
 
         while !self.at_eof() {
-            if !self.match_here([TokenType::OpAccess]) {
-                // println!("stopped parse_access at token of: {}", self.current().to_info_str());
+            if self.match_here([TokenType::ParenClose]) {
+                self.consume_any(items);
                 break;
             }
 
-            self.consume_any(items);
-
-            // println!("parse_access --> parse_primitive");
-            let rhs_box = self.parse_primitive(items);
-
-            rhs_box.as_ref()?;
+            self.consume_of([TokenType::Comma], items);
 
-            let rhs = rhs_box.unwrap();
+            let next_arg_opt = self.parse_compare(items);
 
-            lhs = Box::new(Binary::new(lhs, rhs, OperatorTag::Access));
+            calling_args.push(next_arg_opt?);
         }
 
-        Some(lhs)
+        Some(calling_args)
     }
 
+    /// NOTE: Parses member accesses & calls in any order from left to right, so chains like `obj.a().b()` or `obj.make().field` nest their earlier steps as the LHS / callee of later ones.
     fn parse_call(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Expr>> {
-        // println!("parse_call --> parse_access");
-        let callee_opt = self.parse_access(items);
+        // println!("parse_call --> parse_primitive");
+        let lhs_opt = self.parse_primitive(items);
 
-        callee_opt.as_ref()?;
+        let mut lhs = lhs_opt?;
 
-        let callee_expr = callee_opt.unwrap();
+        while !self.at_eof() {
+            if self.match_here([TokenType::OpAccess]) {
+                self.consume_any(items);
 
-        if !self.match_here([TokenType::ParenOpen]) {
-            return Some(callee_expr);
-        }
+                // println!("parse_call --> parse_primitive");
+                let rhs_box = self.parse_primitive(items);
 
-        self.consume_any(items);
+                lhs = Box::new(Binary::new(lhs, rhs_box?, OperatorTag::Access));
+            } else if self.match_here([TokenType::ParenOpen]) {
+                self.consume_any(items);
 
-        let mut calling_args = Vec::<Box<dyn Expr>>::new();
+                let calling_args = self.parse_call_args(items)?;
 
-        if self.match_here([TokenType::ParenClose]) {
-            self.consume_any(items);
-            return Some(Box::new(Call::new(callee_expr, calling_args)));
+                lhs = Box::new(Call::new(lhs, calling_args));
+            } else {
+                // println!("stopped parse_call at token of: {}", self.current());
+                break;
+            }
         }
 
-        let first_arg_opt = self.parse_compare(items);
+        Some(lhs)
+    }
 
-        first_arg_opt.as_ref()?;
+    fn parse_index(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Expr>> {
+        let target_opt = self.parse_call(items);
 
-        calling_args.push(first_arg_opt.unwrap());
+        let mut target = target_opt?;
 
-        while !self.at_eof() {
-            if self.match_here([TokenType::ParenClose]) {
-                self.consume_any(items);
-                break;
+        while self.match_here([TokenType::BracketOpen]) {
+            let bracket_token = *self.current();
+            self.consume_any(items);
+
+            let index_opt = self.parse_compare(items);
+
+            let index = index_opt?;
+
+            if !self.consume_of([TokenType::BracketClose], items) {
+                return None;
             }
 
-            self.consume_of([TokenType::Comma], items);
+            target = Box::new(Index::new(target, index, bracket_token));
+        }
 
-            let next_arg_opt = self.parse_compare(items);
+        Some(target)
+    }
 
-            next_arg_opt.as_ref()?;
+    fn parse_postfix(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Expr>> {
+        let temp_inner_opt = self.parse_index(items);
 
-            calling_args.push(next_arg_opt.unwrap());
+        let temp_inner = temp_inner_opt?;
+
+        if !self.match_here([TokenType::OpIncrement, TokenType::OpDecrement]) {
+            return Some(temp_inner);
         }
 
-        Some(Box::new(Call::new(callee_expr, calling_args)))
+        let postfixed_op = match self.current().tag {
+            TokenType::OpIncrement => OperatorTag::PostIncrement,
+            _ => OperatorTag::PostDecrement,
+        };
+
+        self.consume_any(items);
+
+        Some(Box::new(Unary::new(temp_inner, postfixed_op)))
     }
 
     fn parse_unary(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Expr>> {
-        if !self.match_here([TokenType::OpMinus]) {
+        if !self.match_here([TokenType::OpMinus, TokenType::OpNot, TokenType::OpIncrement, TokenType::OpDecrement]) {
             // println!("parse_unary (no negation)...");
-            return self.parse_call(items);
+            return self.parse_postfix(items);
         }
 
         let current_tag = self.current().tag;
         let prefixed_op = match current_tag {
             TokenType::OpMinus => OperatorTag::Negate,
+            TokenType::OpNot => OperatorTag::Not,
+            TokenType::OpIncrement => OperatorTag::Increment,
+            TokenType::OpDecrement => OperatorTag::Decrement,
             _ => OperatorTag::Noop,
         };
 
         self.consume_any(items);
 
-        // println!("parse_unary (negation) --> parse_call");
-        let temp_inner_opt = self.parse_call(items);
+        // println!("parse_unary (prefix op) --> parse_postfix");
+        let temp_inner_opt = self.parse_postfix(items);
 
-        temp_inner_opt.as_ref()?;
-
-        Some(Box::new(Unary::new(temp_inner_opt.unwrap(), prefixed_op)))
+        Some(Box::new(Unary::new(temp_inner_opt?, prefixed_op)))
     }
 
     fn parse_factor(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Expr>> {
         // println!("parse_factor...");
         let lhs_opt = self.parse_unary(items);
 
-        lhs_opt.as_ref()?;
-
-        let mut lhs = lhs_opt.unwrap();
+        let mut lhs = lhs_opt?;
 
         while !self.at_eof() {
             if !self.match_here([TokenType::OpTimes, TokenType::OpSlash]) {
@@ -280,9 +425,7 @@ impl<'pl_2> Parser<'pl_2> {
 
             let rhs_opt = self.parse_unary(items);
 
-            rhs_opt.as_ref()?;
-
-            lhs = Box::new(Binary::new(lhs, rhs_opt.unwrap(), temp_op));
+            lhs = Box::new(Binary::new(lhs, rhs_opt?, temp_op));
         }
 
         Some(lhs)
@@ -292,9 +435,7 @@ impl<'pl_2> Parser<'pl_2> {
         // println!("parse_term...");
         let lhs_opt: Option<Box<dyn Expr>> = self.parse_factor(items);
 
-        lhs_opt.as_ref()?;
-
-        let mut lhs = lhs_opt.unwrap();
+        let mut lhs = lhs_opt?;
 
         while !self.at_eof() {
             if !self.match_here([TokenType::OpPlus, TokenType::OpMinus]) {
@@ -313,9 +454,7 @@ impl<'pl_2> Parser<'pl_2> {
 
             let rhs_opt = self.parse_factor(items);
 
-            rhs_opt.as_ref()?;
-
-            lhs = Box::new(Binary::new(lhs, rhs_opt.unwrap(), temp_op));
+            lhs = Box::new(Binary::new(lhs, rhs_opt?, temp_op));
         }
 
         Some(lhs)
@@ -325,9 +464,7 @@ impl<'pl_2> Parser<'pl_2> {
         // println!("parse_equality...");
         let lhs_opt: Option<Box<dyn Expr>> = self.parse_term(items);
 
-        lhs_opt.as_ref()?;
-
-        let mut lhs = lhs_opt.unwrap();
+        let mut lhs = lhs_opt?;
 
         while !self.at_eof() {
             if !self.match_here([TokenType::OpEquality, TokenType::OpInequality]) {
@@ -346,9 +483,7 @@ impl<'pl_2> Parser<'pl_2> {
 
             let rhs_opt = self.parse_term(items);
 
-            rhs_opt.as_ref()?;
-
-            lhs = Box::new(Binary::new(lhs, rhs_opt.unwrap(), temp_op));
+            lhs = Box::new(Binary::new(lhs, rhs_opt?, temp_op));
         }
 
         Some(lhs)
@@ -358,30 +493,25 @@ impl<'pl_2> Parser<'pl_2> {
         // println!("parse_compare...");
         let lhs_opt: Option<Box<dyn Expr>> = self.parse_equality(items);
 
-        lhs_opt.as_ref()?;
-
-        let mut lhs = lhs_opt.unwrap();
+        let mut lhs = lhs_opt?;
 
         while !self.at_eof() {
-            if !self.match_here([TokenType::OpLessThan, TokenType::OpGreaterThan]) {
+            if !self.match_here([TokenType::OpLessThan, TokenType::OpGreaterThan, TokenType::OpLessEqual, TokenType::OpGreaterEqual]) {
                 break;
             }
 
-            let temp_tag = self.current().tag;
-
-            let temp_op = if temp_tag == TokenType::OpLessThan {
-                OperatorTag::LessThan
-            } else {
-                OperatorTag::GreaterThan
+            let temp_op = match self.current().tag {
+                TokenType::OpLessThan => OperatorTag::LessThan,
+                TokenType::OpGreaterThan => OperatorTag::GreaterThan,
+                TokenType::OpLessEqual => OperatorTag::LessEqual,
+                _ => OperatorTag::GreaterEqual,
             };
 
             self.consume_any(items);
 
             let rhs_opt = self.parse_equality(items);
 
-            rhs_opt.as_ref()?;
-
-            lhs = Box::new(Binary::new(lhs, rhs_opt.unwrap(), temp_op));
+            lhs = Box::new(Binary::new(lhs, rhs_opt?, temp_op));
         }
 
         Some(lhs)
@@ -391,22 +521,38 @@ impl<'pl_2> Parser<'pl_2> {
         // println!("parse_assign -> parse_access");
         let lhs_opt = self.parse_unary(items);
 
-        lhs_opt.as_ref()?;
+        let lhs_expr = lhs_opt?;
 
         if !self.match_here([TokenType::OpAssign]) {
-            return Some(lhs_opt.unwrap());
+            return Some(lhs_expr);
         }
 
         self.consume_any(items);
 
-        // println!("parse_assign -> parse_compare");
-        let rhs_opt = self.parse_compare(items);
+        // println!("parse_assign -> parse_assign_rhs");
+        let rhs_opt = self.parse_assign_rhs(items);
+        Some(Box::new(Binary::new(
+            lhs_expr,
+            rhs_opt?,
+            OperatorTag::Assign,
+        )))
+    }
+
+    /// NOTE: Parses the RHS of an assignment, which nests any further `=` to its right e.g `a = b = 3` as `a = (b = 3)`. The analyzer checks that each nested LHS is assignable.
+    fn parse_assign_rhs(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Expr>> {
+        let value_expr = self.parse_compare(items)?;
+
+        if !self.match_here([TokenType::OpAssign]) {
+            return Some(value_expr);
+        }
+
+        self.consume_any(items);
 
-        rhs_opt.as_ref()?;
+        let inner_rhs_expr = self.parse_assign_rhs(items)?;
 
         Some(Box::new(Binary::new(
-            lhs_opt.unwrap(),
-            rhs_opt.unwrap(),
+            value_expr,
+            inner_rhs_expr,
             OperatorTag::Assign,
         )))
     }
@@ -417,7 +563,7 @@ impl<'pl_2> Parser<'pl_2> {
 
         let var_name = *self.current();
 
-        self.consume_of([TokenType::Identifier], items);
+        self.consume_name(items);
         self.consume_of([TokenType::Colon], items);
 
         let var_type_box = self.parse_type(items);
@@ -427,9 +573,7 @@ impl<'pl_2> Parser<'pl_2> {
         // println!("parse_variable_decl --> parse_compare");
         let var_init_expr_opt = self.parse_compare(items);
 
-        var_init_expr_opt.as_ref()?;
-
-        let var_init_expr = var_init_expr_opt.unwrap();
+        let var_init_expr = var_init_expr_opt?;
 
         if !self.consume_of([TokenType::Semicolon], items) {
             self.recover_and_report("Expected ';' .", items);
@@ -448,91 +592,124 @@ impl<'pl_2> Parser<'pl_2> {
 
         let conds_opt = self.parse_compare(items);
 
-        conds_opt.as_ref()?;
+        let conds_expr = conds_opt?;
 
-        let conds_expr = conds_opt.unwrap();
+        self.skip_stray_assign(items);
 
         let truthy_body_opt = self.parse_block(items);
 
-        truthy_body_opt.as_ref()?;
-
-        let truthy_body = truthy_body_opt.unwrap();
+        let truthy_body = truthy_body_opt?;
 
         if self
             .current()
             .to_lexeme_str(self.tokenizer.view_source())
-            .expect("")
+            .unwrap_or("")
             == "else"
         {
+            let else_token = *self.current();
             self.consume_any(items);
 
             let falsy_body_opt = self.parse_block(items);
-
-            falsy_body_opt.as_ref()?;
-
             return Some(Box::new(If::new(
                 truthy_body,
-                falsy_body_opt.unwrap(),
+                falsy_body_opt?,
                 conds_expr,
+                Some(else_token),
             )));
         }
 
         let dud_falsy_body = Box::new(Block::new(Vec::new()));
 
-        Some(Box::new(If::new(truthy_body, dud_falsy_body, conds_expr)))
+        Some(Box::new(If::new(truthy_body, dud_falsy_body, conds_expr, None)))
     }
 
     fn parse_while(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Stmt>> {
         self.consume_any(items);
 
-        let check_expr = self.parse_compare(items);
+        let check_expr = self.parse_compare(items)?;
+
+        self.skip_stray_assign(items);
 
-        let body_stmt = self.parse_block(items);
+        let body_stmt = self.parse_block(items)?;
 
         Some(Box::new(While::new(
-            check_expr.unwrap(),
-            body_stmt.unwrap(),
+            check_expr,
+            body_stmt,
+        )))
+    }
+
+    fn parse_foreach(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Stmt>> {
+        self.consume_any(items);
+
+        if !self.consume_of([TokenType::ParenOpen], items) {
+            return None;
+        }
+
+        let item_token = *self.current();
+
+        if !self.consume_name(items) || !self.consume_of([TokenType::Colon], items) {
+            return None;
+        }
+
+        let target_expr = self.parse_compare(items)?;
+
+        if !self.consume_of([TokenType::ParenClose], items) {
+            return None;
+        }
+
+        let body_stmt = self.parse_block(items)?;
+
+        Some(Box::new(Foreach::new(
+            item_token,
+            target_expr,
+            body_stmt,
         )))
     }
 
     fn parse_return(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Stmt>> {
+        let return_token = *self.current();
         self.consume_any(items);
 
-        let result_expr_opt = self.parse_compare(items);
+        if self.match_here([TokenType::Semicolon]) {
+            self.consume_any(items);
+
+            return Some(Box::new(Return::new(return_token, None)));
+        }
 
-        result_expr_opt.as_ref()?;
+        let result_expr = self.parse_compare(items)?;
 
         if !self.consume_of([TokenType::Semicolon], items) {
             self.recover_and_report("Expected ';' .", items);
             return None;
         }
 
-        Some(Box::new(Return::new(result_expr_opt.unwrap())))
+        Some(Box::new(Return::new(return_token, Some(result_expr))))
     }
 
     fn parse_expr_stmt(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Stmt>> {
         let inner_expr_opt = self.parse_assign(items);
 
-        inner_expr_opt.as_ref()?;
+        let inner_expr = inner_expr_opt?;
 
         if !self.consume_of([TokenType::Semicolon], items) {
             self.recover_and_report("Expected ';' .", items);
             return None;
         }
 
-        Some(Box::new(ExprStmt::new(inner_expr_opt.unwrap())))
+        Some(Box::new(ExprStmt::new(inner_expr)))
     }
 
     fn parse_nestable(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Stmt>> {
         let keyword = self
             .current()
             .to_lexeme_str(self.tokenizer.view_source())
-            .expect("");
+            .unwrap_or("");
 
         match keyword {
             "let" => self.parse_variable_decl(items),
             "if" => self.parse_if(items),
             "while" => self.parse_while(items),
+            "foreach" => self.parse_foreach(items),
             "return" => self.parse_return(items),
             _ => self.parse_expr_stmt(items),
         }
@@ -551,9 +728,7 @@ impl<'pl_2> Parser<'pl_2> {
 
             let next_stmt_opt = self.parse_nestable(items);
 
-            next_stmt_opt.as_ref()?;
-
-            stmts.push(next_stmt_opt.unwrap());
+            stmts.push(next_stmt_opt?);
         }
 
         Some(Box::new(Block::new(stmts)))
@@ -577,12 +752,13 @@ impl<'pl_2> Parser<'pl_2> {
     }
 
     fn parse_foreign_stub(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Stmt>> {
+        let stub_doc = self.current_doc.take();
         self.consume_any(items);
 
         let stub_name_token = *self.current();
-        self.consume_of([TokenType::Identifier], items);
+        self.consume_name(items);
 
-        let stub_params = self.parse_function_params(items);
+        let stub_params = self.parse_function_params(items, false);
 
         self.consume_of([TokenType::Colon], items);
         let stub_ret_type_box = self.parse_type(items);
@@ -595,30 +771,77 @@ impl<'pl_2> Parser<'pl_2> {
         Some(Box::new(ForeignStub::new(
             stub_name_token,
             stub_params,
-            stub_ret_type_box
+            stub_ret_type_box,
+            stub_doc,
         )))
     }
 
-    fn parse_function_decl(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Stmt>> {
+    fn parse_const_decl(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Stmt>> {
+        let const_doc = self.current_doc.take();
+        self.consume_of([TokenType::Keyword], items);
+
+        let const_name_token = *self.current();
+
+        self.consume_name(items);
+        self.consume_of([TokenType::Colon], items);
+
+        let const_type_box = self.parse_type(items);
+
+        self.consume_of([TokenType::OpAssign], items);
+
+        let const_init_expr = self.parse_compare(items)?;
+
+        if !self.consume_of([TokenType::Semicolon], items) {
+            self.recover_and_report("Expected ';' .", items);
+            return None;
+        }
+
+        Some(Box::new(ConstDecl::new(
+            const_name_token,
+            const_type_box,
+            const_init_expr,
+            const_doc,
+        )))
+    }
+
+    /// NOTE: Parses a top-level function marked like `private fun helper(): int {...}`, keeping any doc text above the modifier.
+    fn parse_modified_function_decl(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Stmt>> {
+        let func_doc = self.current_doc.take();
+        self.consume_of([TokenType::Keyword], items);
+
+        let access_modify_word = self.previous().to_lexeme_str(self.tokenizer.view_source()).unwrap_or("public");
+        let access_modify_enum = if access_modify_word == "private" { AccessFlag::Hidden } else { AccessFlag::Exposed };
+
+        if self.current().to_lexeme_str(self.tokenizer.view_source()) != Some("fun") {
+            self.recover_and_report("Invalid top-level modifier- Only functions can be marked public or private outside of classes.", items);
+            return None;
+        }
+
+        self.current_doc = func_doc;
+
+        self.parse_function_decl(items, access_modify_enum)
+    }
+
+    fn parse_function_decl(&mut self, items: &'pl_2 HashMap<String, TokenType>, access: AccessFlag) -> Option<Box<dyn Stmt>> {
+        let func_doc = self.current_doc.take();
         self.consume_of([TokenType::Keyword], items);
 
         let func_name_token = *self.current();
-        self.consume_of([TokenType::Identifier], items);
+        self.consume_name(items);
 
-        let func_params = self.parse_function_params(items);
+        let func_params = self.parse_function_params(items, false);
 
         self.consume_of([TokenType::Colon], items);
         let func_type_box = self.parse_type(items);
 
         let func_body_opt = self.parse_block(items);
-
-        func_body_opt.as_ref()?;
-
         Some(Box::new(FunctionDecl::new(
             func_name_token,
             func_params,
             func_type_box,
-            func_body_opt.unwrap(),
+            func_body_opt?,
+            func_doc,
+            access,
         )))
     }
 
@@ -627,7 +850,7 @@ impl<'pl_2> Parser<'pl_2> {
 
         let field_name_token = *self.current();
 
-        self.consume_of([TokenType::Identifier], items);
+        self.consume_name(items);
         self.consume_of([TokenType::Colon], items);
 
         let field_typing = self.parse_type(items);
@@ -642,38 +865,34 @@ impl<'pl_2> Parser<'pl_2> {
     fn parse_constructor_decl(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Stmt>> {
         self.consume_of([TokenType::Keyword], items);
 
-        let ctor_params = self.parse_function_params(items);
-
-        let ctor_body = self.parse_block(items);
+        let ctor_params = self.parse_function_params(items, true);
 
-        ctor_body.as_deref()?;
+        let ctor_body = self.parse_block(items)?;
 
         Some(Box::new(ConstructorDecl::new(
             ctor_params,
-            ctor_body.unwrap(),
+            ctor_body,
         )))
     }
 
-    fn parse_method_decl(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Stmt>> {
+    fn parse_method_decl(&mut self, items: &'pl_2 HashMap<String, TokenType>, is_static: bool) -> Option<Box<dyn Stmt>> {
         self.consume_of([TokenType::Keyword], items);
 
         let method_name_token = *self.current();
-        self.consume_of([TokenType::Identifier], items);
+        self.consume_name(items);
 
-        let method_params = self.parse_function_params(items);
+        let method_params = self.parse_function_params(items, false);
 
         self.consume_of([TokenType::Colon], items);
         let method_type_box = self.parse_type(items);
 
         let method_body_opt = self.parse_block(items);
-
-        method_body_opt.as_ref()?;
-
         Some(Box::new(MethodDecl::new(
             method_name_token,
             method_params,
             method_type_box,
-            method_body_opt.unwrap(),
+            method_body_opt?,
+            is_static,
         )))
     }
 
@@ -683,27 +902,37 @@ impl<'pl_2> Parser<'pl_2> {
         let access_modify_word = self.previous().to_lexeme_str(self.tokenizer.view_source()).unwrap_or("private");
         let access_modify_enum = if access_modify_word == "public" { AccessFlag::Exposed } else { AccessFlag::Hidden };
 
-        let hint_keyword = self.current().to_lexeme_str(self.tokenizer.view_source()).unwrap_or("");
+        let mut hint_keyword = self.current().to_lexeme_str(self.tokenizer.view_source()).unwrap_or("");
+        let is_static = hint_keyword == "static";
+
+        if is_static {
+            self.consume_of([TokenType::Keyword], items);
+            hint_keyword = self.current().to_lexeme_str(self.tokenizer.view_source()).unwrap_or("");
+
+            if hint_keyword != "met" {
+                self.recover_and_report("Invalid static member- Only methods can be static.", items);
+                return None;
+            }
+        }
 
         let class_decl_opt = match hint_keyword {
             "let" => self.parse_field_decl(items),
             "ctor" => self.parse_constructor_decl(items),
-            "met" => self.parse_method_decl(items),
+            "met" => self.parse_method_decl(items, is_static),
             _ => {
                 self.recover_and_report("Invalid class member declaration- Only let, ctor, and met are valid for fields, a constructor, and methods are valid.", items);
                 None
             },
         };
 
-        class_decl_opt.as_deref()?;
-
         Some((
-            class_decl_opt.unwrap(),
+            class_decl_opt?,
             access_modify_enum
         ))
     }
 
     fn parse_class_decl(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Option<Box<dyn Stmt>> {
+        let class_doc = self.current_doc.take();
         self.consume_of([TokenType::Keyword], items);
 
         let class_typename = self.parse_type(items);
@@ -718,25 +947,35 @@ impl<'pl_2> Parser<'pl_2> {
             }
 
             let temp_member_stmt = self.parse_class_member(items);
-            temp_member_stmt.as_ref()?;
 
-            class_members.push(temp_member_stmt.unwrap());
+            class_members.push(temp_member_stmt?);
         }
 
-        Some(Box::new(ClassDecl::new(class_members, class_typename)))
+        Some(Box::new(ClassDecl::new(class_members, class_typename, class_doc)))
     }
 
-    fn parse_param_decl(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> ParamDecl {
+    fn parse_param_decl(&mut self, items: &'pl_2 HashMap<String, TokenType>, allows_field_binds: bool) -> ParamDecl {
+        let binds_field = self.match_here([TokenType::Keyword])
+            && self.current().to_lexeme_str(self.tokenizer.view_source()) == Some("let");
+
+        if binds_field {
+            self.consume_any(items);
+        }
+
+        if binds_field && !allows_field_binds {
+            self.recover_and_report("Only constructor parameters can be marked with 'let' to set a field.", items);
+        }
+
         let name_token = *self.current();
-        self.consume_of([TokenType::Identifier], items);
+        self.consume_name(items);
         self.consume_of([TokenType::Colon], items);
 
         let typing_box = self.parse_type(items);
 
-        ParamDecl::new(name_token, typing_box)
+        ParamDecl::new(name_token, typing_box, binds_field)
     }
 
-    fn parse_function_params(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> Vec<ParamDecl> {
+    fn parse_function_params(&mut self, items: &'pl_2 HashMap<String, TokenType>, allows_field_binds: bool) -> Vec<ParamDecl> {
         self.consume_of([TokenType::ParenOpen], items);
 
         let mut parameters = Vec::<ParamDecl>::new();
@@ -746,7 +985,7 @@ impl<'pl_2> Parser<'pl_2> {
             return parameters;
         }
 
-        parameters.push(self.parse_param_decl(items));
+        parameters.push(self.parse_param_decl(items, allows_field_binds));
 
         while !self.at_eof() {
             if self.match_here([TokenType::ParenClose]) {
@@ -756,7 +995,7 @@ impl<'pl_2> Parser<'pl_2> {
 
             self.consume_of([TokenType::Comma], items);
 
-            parameters.push(self.parse_param_decl(items));
+            parameters.push(self.parse_param_decl(items, allows_field_binds));
         }
 
         parameters
@@ -768,7 +1007,9 @@ impl<'pl_2> Parser<'pl_2> {
         match start_word {
             "import" => self.parse_import(items),
             "foreign" => self.parse_foreign_stub(items),
-            "fun" => self.parse_function_decl(items),
+            "const" => self.parse_const_decl(items),
+            "fun" => self.parse_function_decl(items, AccessFlag::Exposed),
+            "public" | "private" => self.parse_modified_function_decl(items),
             "class" => self.parse_class_decl(items),
             _ => None
         }
@@ -788,6 +1029,10 @@ impl<'pl_2> Parser<'pl_2> {
         self.previous = *self.current();
 
         self.error_count = 0;
+        self.pending_doc = None;
+        self.current_doc = None;
+        self.last_code_line = 0;
+        self.stray_char_line = 0;
     }
 
     pub fn parse_file(&mut self, items: &'pl_2 HashMap<String, TokenType>) -> ParseResult {
@@ -796,13 +1041,11 @@ impl<'pl_2> Parser<'pl_2> {
         let mut all_top_stmts = ASTDecls::new();
 
         while !self.at_eof() {
-            let func_decl_opt = self.parse_top_decl(items);
-
-            if func_decl_opt.is_none() {
+            let Some(func_decl) = self.parse_top_decl(items) else {
                 break;
-            }
+            };
 
-            all_top_stmts.push(func_decl_opt.unwrap());
+            all_top_stmts.push(func_decl);
         }
 
         let mut temp_src_targets = VecDeque::<String>::new();
@@ -816,3 +1059,104 @@ impl<'pl_2> Parser<'pl_2> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::lexer::DEFAULT_OPERATOR_CHARS;
+
+    const KEYWORD_NAMES_SOURCE: &str = "fun main(): int {\n    let if: int = 1;\n    let while: int = 2;\n    let else: int = 3;\n    let exit: int = 4;\n    return 0;\n}\n";
+
+    #[test]
+    fn every_error_under_the_cap_is_reported() {
+        let lexicals = crate::setup_lexical_items();
+        let mut parser = Parser::new(Lexer::new("", &DEFAULT_OPERATOR_CHARS));
+        parser.reset_with(KEYWORD_NAMES_SOURCE);
+
+        let (ast_opt, _) = parser.parse_file(&lexicals);
+
+        assert!(ast_opt.is_none());
+        assert_eq!(parser.error_count, 4);
+    }
+
+    #[test]
+    fn reports_stop_after_the_capped_errors() {
+        let mut parser = Parser::new(Lexer::new("", &DEFAULT_OPERATOR_CHARS));
+        parser.set_parse_error_max(2);
+        parser.reset_with(KEYWORD_NAMES_SOURCE);
+
+        let culprit = token_from!(TokenType::Identifier, 0, 3, 1, 1);
+
+        assert!(parser.report_at(&culprit, "First error."));
+        assert!(parser.report_at(&culprit, "Second error."));
+        assert!(!parser.report_at(&culprit, "Third error."));
+    }
+
+    #[test]
+    fn parsing_stops_at_the_capped_error() {
+        let lexicals = crate::setup_lexical_items();
+        let mut parser = Parser::new(Lexer::new("", &DEFAULT_OPERATOR_CHARS));
+        parser.set_parse_error_max(1);
+        parser.reset_with(KEYWORD_NAMES_SOURCE);
+
+        let (ast_opt, _) = parser.parse_file(&lexicals);
+
+        assert!(ast_opt.is_none());
+        assert!(parser.at_eof());
+        assert!(parser.error_count > 1);
+    }
+
+    /// NOTE: Collects the doc text of each top-level declaration by name, skipping bodies as only top-level docs are kept.
+    struct DocCollector {
+        docs: Vec<(String, Option<String>)>,
+        source: String,
+    }
+
+    impl DocCollector {
+        fn record(&mut self, name_token: &Token, doc_opt: Option<&str>) -> bool {
+            let name = name_token.to_lexeme_str(&self.source).unwrap_or("").to_string();
+            self.docs.push((name, doc_opt.map(String::from)));
+
+            true
+        }
+    }
+
+    impl StmtVisitor<bool> for DocCollector {
+        fn visit_import(&mut self, _: &Import) -> bool { true }
+        fn visit_foreign_stub(&mut self, s: &ForeignStub) -> bool { self.record(s.get_name_token(), s.get_doc()) }
+        fn visit_const_decl(&mut self, s: &ConstDecl) -> bool { self.record(s.get_name_token(), s.get_doc()) }
+        fn visit_function_decl(&mut self, s: &FunctionDecl) -> bool { self.record(s.get_name_token(), s.get_doc()) }
+        fn visit_field_decl(&mut self, _: &FieldDecl) -> bool { true }
+        fn visit_constructor_decl(&mut self, _: &ConstructorDecl) -> bool { true }
+        fn visit_method_decl(&mut self, _: &MethodDecl) -> bool { true }
+        fn visit_class_decl(&mut self, _: &ClassDecl) -> bool { true }
+        fn visit_block(&mut self, _: &Block) -> bool { true }
+        fn visit_variable_decl(&mut self, _: &VariableDecl) -> bool { true }
+        fn visit_if(&mut self, _: &If) -> bool { true }
+        fn visit_while(&mut self, _: &While) -> bool { true }
+        fn visit_foreach(&mut self, _: &Foreach) -> bool { true }
+        fn visit_return(&mut self, _: &Return) -> bool { true }
+        fn visit_expr_stmt(&mut self, _: &ExprStmt) -> bool { true }
+    }
+
+    #[test]
+    fn own_line_comments_become_decl_docs() {
+        let source = "# Doubles a number.\n# Works on ints.\nfun twice(n: int): int {\n    return n * 2;\n}\n\n# Lost, as a blank line follows.\n\nconst LIMIT: int = 3; # trailing\nforeign read_int(): int;\n";
+        let lexicals = crate::setup_lexical_items();
+        let mut parser = Parser::new(Lexer::new("", &DEFAULT_OPERATOR_CHARS));
+        parser.reset_with(source);
+
+        let (ast_opt, _) = parser.parse_file(&lexicals);
+        let mut collector = DocCollector { docs: Vec::new(), source: String::from(source) };
+
+        for decl in ast_opt.expect("test source should parse") {
+            decl.accept_visitor(&mut collector);
+        }
+
+        assert_eq!(collector.docs, [
+            (String::from("twice"), Some(String::from("Doubles a number.\nWorks on ints."))),
+            (String::from("LIMIT"), None),
+            (String::from("read_int"), None),
+        ]);
+    }
+}
@@ -1,7 +1,10 @@
+use std::fmt::{Debug, Display, Formatter};
+
 #[repr(i32)]
 #[derive(Clone, Copy, PartialEq)]
 pub enum TokenType {
     Unknown,
+    StrayChar,
     Spaces,
     Comment,
     Keyword,
@@ -11,16 +14,22 @@ pub enum TokenType {
     LiteralChar,
     LiteralInt,
     LiteralFloat,
+    LiteralLong,
     LiteralVarchar,
     OpAccess,
     OpTimes,
     OpSlash,
     OpPlus,
     OpMinus,
+    OpNot,
+    OpIncrement,
+    OpDecrement,
     OpEquality,
     OpInequality,
     OpLessThan,
     OpGreaterThan,
+    OpLessEqual,
+    OpGreaterEqual,
     OpAssign,
     Colon,
     Comma,
@@ -38,6 +47,7 @@ impl TokenType {
     pub fn name(&self) -> &'static str {
         match self {
             Self::Unknown => "Unknown",
+            Self::StrayChar => "StrayChar",
             Self::Spaces => "Spaces",
             Self::Comment => "Comment",
             Self::Keyword => "Keyword",
@@ -47,16 +57,22 @@ impl TokenType {
             Self::LiteralChar => "LiteralChar",
             Self::LiteralInt => "LiteralInt",
             Self::LiteralFloat => "LiteralFloat",
+            Self::LiteralLong => "LiteralLong",
             Self::LiteralVarchar => "LiteralVarchar",
             Self::OpAccess => "OpAccess",
             Self::OpTimes => "OpTimes",
             Self::OpSlash => "OpSlash",
             Self::OpPlus => "OpPlus",
             Self::OpMinus => "OpMinus",
+            Self::OpNot => "OpNot",
+            Self::OpIncrement => "OpIncrement",
+            Self::OpDecrement => "OpDecrement",
             Self::OpEquality => "OpEquality",
             Self::OpInequality => "OpInequality",
             Self::OpLessThan => "OpLessThan",
             Self::OpGreaterThan => "OpGreaterThan",
+            Self::OpLessEqual => "OpLessEqual",
+            Self::OpGreaterEqual => "OpGreaterEqual",
             Self::OpAssign => "OpAssign",
             Self::Colon => "Colon",
             Self::Comma => "Comma",
@@ -72,6 +88,18 @@ impl TokenType {
     }
 }
 
+impl Display for TokenType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Debug for TokenType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct Token {
     pub tag: TokenType,
@@ -89,6 +117,11 @@ impl Token {
         source.get(lexeme_start..(lexeme_start + lexeme_len))
     }
 
+    /// NOTE: Checks the token's kind & text within its source, which `==` leaves out e.g to tell apart the identifiers `a` and `b`.
+    pub fn is_lexeme(&self, source: &str, tag: TokenType, lexeme: &str) -> bool {
+        self.tag == tag && self.to_lexeme_str(source) == Some(lexeme)
+    }
+
     pub fn to_info_str(&self) -> String {
         format!(
             "Token ({}, {}, {}, {}, {})",
@@ -101,6 +134,31 @@ impl Token {
     }
 }
 
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_info_str())
+    }
+}
+
+impl Debug for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Token")
+            .field("tag", &self.tag)
+            .field("start", &self.start)
+            .field("length", &self.length)
+            .field("line_no", &self.line_no)
+            .field("col_no", &self.col_no)
+            .finish()
+    }
+}
+
+/// NOTE: Tokens are compared by kind and lexeme length only, so expected token sequences need no exact source positions. As tokens don't hold their source, the lexeme text isn't compared either e.g the identifiers `a` and `b` are equal, so use `Token::is_lexeme` to check the text too.
+impl PartialEq for Token {
+    fn eq(&self, other: &Self) -> bool {
+        self.tag == other.tag && self.length == other.length
+    }
+}
+
 #[macro_export]
 macro_rules! token_from {
     ($tag: expr, $begin: expr, $length: expr, $line: expr, $col: expr) => {
@@ -113,3 +171,51 @@ macro_rules! token_from {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::lexer::{DEFAULT_OPERATOR_CHARS, Lexer};
+
+    const SOURCE: &str = "fun main(): int {\n    return a + b;\n}\n";
+
+    fn lex_code_tokens(source: &str) -> Vec<Token> {
+        let lexicals = crate::setup_lexical_items();
+        let mut lexer = Lexer::new(source, &DEFAULT_OPERATOR_CHARS);
+
+        lexer.tokens(&lexicals).filter(|token| !matches!(token.tag, TokenType::Spaces | TokenType::Comment)).collect()
+    }
+
+    #[test]
+    fn token_sequences_match_by_kind_and_length() {
+        let expected = [
+            token_from!(TokenType::Keyword, 0, 3, 0, 0),
+            token_from!(TokenType::Identifier, 0, 4, 0, 0),
+            token_from!(TokenType::ParenOpen, 0, 1, 0, 0),
+            token_from!(TokenType::ParenClose, 0, 1, 0, 0),
+            token_from!(TokenType::Colon, 0, 1, 0, 0),
+            token_from!(TokenType::Typename, 0, 3, 0, 0),
+            token_from!(TokenType::BraceOpen, 0, 1, 0, 0),
+            token_from!(TokenType::Keyword, 0, 6, 0, 0),
+            token_from!(TokenType::Identifier, 0, 1, 0, 0),
+            token_from!(TokenType::OpPlus, 0, 1, 0, 0),
+            token_from!(TokenType::Identifier, 0, 1, 0, 0),
+            token_from!(TokenType::Semicolon, 0, 1, 0, 0),
+            token_from!(TokenType::BraceClose, 0, 1, 0, 0),
+        ];
+
+        assert_eq!(lex_code_tokens(SOURCE), expected);
+    }
+
+    #[test]
+    fn lexemes_tell_apart_equal_tokens() {
+        let tokens = lex_code_tokens(SOURCE);
+        let (lhs_token, rhs_token) = (tokens[8], tokens[10]);
+
+        assert_eq!(lhs_token, rhs_token);
+        assert!(lhs_token.is_lexeme(SOURCE, TokenType::Identifier, "a"));
+        assert!(rhs_token.is_lexeme(SOURCE, TokenType::Identifier, "b"));
+        assert!(!rhs_token.is_lexeme(SOURCE, TokenType::Identifier, "a"));
+        assert!(tokens[7].is_lexeme(SOURCE, TokenType::Keyword, "return"));
+    }
+}
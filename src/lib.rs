@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+pub mod frontend;
+pub mod semantics;
+pub mod codegen;
+pub mod compiler;
+pub mod utils;
+pub mod vm;
+
+use crate::compiler::driver::{CompileError, CompilerMain};
+use crate::frontend::token::TokenType;
+use crate::utils::bundle::Bundle;
+use crate::utils::{loxie_buffer, loxie_callback, loxie_convert, loxie_host, loxie_math, loxie_object, loxie_stdio, loxie_varchar};
+use crate::vm::bytecode::Program;
+
+/// NOTE: Binds native functions to the interpreter's global scope along with their signatures, giving `None` if a signature can't be set.
+pub fn setup_natives() -> Option<Bundle> {
+    let mut global_natives = Bundle::new();
+
+    global_natives.register_native("intrin_varchar_len", Box::new(loxie_varchar::native_intrin_varchar_len), 1);
+    global_natives.register_native("intrin_varchar_get", Box::new(loxie_varchar::native_intrin_varchar_get), 2);
+    global_natives.register_native("intrin_varchar_set", Box::new(loxie_varchar::native_intrin_varchar_set), 3);
+    global_natives.register_native("intrin_varchar_store", Box::new(loxie_varchar::native_intrin_varchar_store), 3);
+    global_natives.register_native("intrin_varchar_push", Box::new(loxie_varchar::native_intrin_varchar_push), 2);
+    global_natives.register_native("intrin_varchar_pop", Box::new(loxie_varchar::native_intrin_varchar_pop), 1);
+    global_natives.register_native("varchar_cmp", Box::new(loxie_varchar::native_varchar_cmp), 2);
+    global_natives.register_native("read_int", Box::new(loxie_stdio::native_read_int), 0);
+    global_natives.register_native("print_val", Box::new(loxie_stdio::native_print_val), 1);
+    global_natives.register_native("object_id", Box::new(loxie_object::native_object_id), 1);
+    global_natives.register_native("type_name", Box::new(loxie_object::native_type_name), 1);
+    global_natives.register_native("to_float", Box::new(loxie_convert::native_to_float), 1);
+    global_natives.register_native("to_int", Box::new(loxie_convert::native_to_int), 1);
+    global_natives.register_native("clone", Box::new(loxie_object::native_clone), 1);
+    global_natives.register_native("float_eq", Box::new(loxie_math::native_float_eq), 3);
+    global_natives.register_native("clamp", Box::new(loxie_math::native_clamp), 3);
+    global_natives.register_native("host_string", Box::new(loxie_host::native_host_string), 1);
+    global_natives.register_native("apply_times", Box::new(loxie_callback::native_apply_times), 3);
+    global_natives.register_native("char_code", Box::new(loxie_convert::native_char_code), 1);
+    global_natives.register_native("new_buffer", Box::new(loxie_buffer::native_new_buffer), 1);
+    global_natives.register_native("buffer_get", Box::new(loxie_buffer::native_buffer_get), 2);
+    global_natives.register_native("buffer_set", Box::new(loxie_buffer::native_buffer_set), 3);
+
+    // NOTE: native signatures are mapped to the analyzer's type IDs, so native calls get their argument types checked & any foreign stub declaring a native differently is rejected at compile time. Each `callback` parameter names the signature its functions must have.
+    let native_signatures: [(&str, &[&str], &str); 22] = [
+        ("intrin_varchar_len", &["varchar"], "int"),
+        ("intrin_varchar_get", &["varchar", "int"], "char"),
+        ("intrin_varchar_set", &["varchar", "int", "char"], "bool"),
+        ("intrin_varchar_store", &["varchar", "int", "char"], "bool"),
+        ("intrin_varchar_push", &["varchar", "char"], "bool"),
+        ("intrin_varchar_pop", &["varchar"], "char"),
+        ("varchar_cmp", &["varchar", "varchar"], "int"),
+        ("read_int", &[], "int"),
+        ("print_val", &["any"], "bool"),
+        ("object_id", &["any"], "int"),
+        ("type_name", &["any"], "varchar"),
+        ("clone", &["any"], "any"),
+        ("float_eq", &["float", "float", "float"], "bool"),
+        ("clamp", &["any", "any", "any"], "any"),
+        ("host_string", &["varchar"], "varchar"),
+        ("apply_times", &["callback(int): int", "int", "int"], "int"),
+        ("to_float", &["int"], "float"),
+        ("to_int", &["float"], "int"),
+        ("char_code", &["char"], "int"),
+        ("new_buffer", &["int"], "buffer"),
+        ("buffer_get", &["buffer", "int"], "int"),
+        ("buffer_set", &["buffer", "int", "int"], "bool"),
+    ];
+
+    for (native_name, param_type_names, result_type_name) in native_signatures {
+        if !global_natives.set_native_signature(native_name, param_type_names, result_type_name) {
+            eprintln!("Oops: could not set the signature of native '{native_name}'.");
+            return None;
+        }
+    }
+
+    global_natives.mark_native_mutating("intrin_varchar_set");
+    global_natives.mark_native_mutating("intrin_varchar_store");
+    global_natives.mark_native_mutating("intrin_varchar_push");
+    global_natives.mark_native_mutating("intrin_varchar_pop");
+    global_natives.mark_native_mutating("buffer_set");
+
+    Some(global_natives)
+}
+
+/// NOTE: Registers important lexical items to the lexer for parsing later.
+pub fn setup_lexical_items() -> HashMap<String, TokenType> {
+    let mut lexical_items = HashMap::<String, TokenType>::new();
+    lexical_items.insert(String::from("foreign"), TokenType::Keyword);
+    lexical_items.insert(String::from("fun"), TokenType::Keyword);
+    lexical_items.insert(String::from("ctor"), TokenType::Keyword);
+    lexical_items.insert(String::from("class"), TokenType::Keyword);
+    lexical_items.insert(String::from("static"), TokenType::Keyword);
+    lexical_items.insert(String::from("met"), TokenType::Keyword);
+    lexical_items.insert(String::from("private"), TokenType::Keyword);
+    lexical_items.insert(String::from("public"), TokenType::Keyword);
+    lexical_items.insert(String::from("let"), TokenType::Keyword);
+    lexical_items.insert(String::from("const"), TokenType::Keyword);
+    lexical_items.insert(String::from("if"), TokenType::Keyword);
+    lexical_items.insert(String::from("else"), TokenType::Keyword);
+    lexical_items.insert(String::from("while"), TokenType::Keyword);
+    lexical_items.insert(String::from("foreach"), TokenType::Keyword);
+    lexical_items.insert(String::from("return"), TokenType::Keyword);
+    lexical_items.insert(String::from("exit"), TokenType::Keyword);
+    lexical_items.insert(String::from("bool"), TokenType::Typename);
+    lexical_items.insert(String::from("char"), TokenType::Typename);
+    lexical_items.insert(String::from("int"), TokenType::Typename);
+    lexical_items.insert(String::from("float"), TokenType::Typename);
+    lexical_items.insert(String::from("long"), TokenType::Typename);
+    lexical_items.insert(String::from("varchar"), TokenType::Typename);
+    lexical_items.insert(String::from("true"), TokenType::LiteralBool);
+    lexical_items.insert(String::from("false"), TokenType::LiteralBool);
+    lexical_items.insert(String::from("."), TokenType::OpAccess);
+    lexical_items.insert(String::from("*"), TokenType::OpTimes);
+    lexical_items.insert(String::from("/"), TokenType::OpSlash);
+    lexical_items.insert(String::from("+"), TokenType::OpPlus);
+    lexical_items.insert(String::from("-"), TokenType::OpMinus);
+    lexical_items.insert(String::from("!"), TokenType::OpNot);
+    lexical_items.insert(String::from("++"), TokenType::OpIncrement);
+    lexical_items.insert(String::from("--"), TokenType::OpDecrement);
+    lexical_items.insert(String::from("=="), TokenType::OpEquality);
+    lexical_items.insert(String::from("!="), TokenType::OpInequality);
+    lexical_items.insert(String::from("<"), TokenType::OpLessThan);
+    lexical_items.insert(String::from(">"), TokenType::OpGreaterThan);
+    lexical_items.insert(String::from("<="), TokenType::OpLessEqual);
+    lexical_items.insert(String::from(">="), TokenType::OpGreaterEqual);
+    lexical_items.insert(String::from("="), TokenType::OpAssign);
+
+    lexical_items
+}
+
+/// NOTE: Compiles a program's source text with the default natives & lexical items, giving the `Program` for inspection e.g by its summary or disassembly without running it. Any diagnostics are reported to stderr as usual, while the result lists which phases failed.
+pub fn compile(source_name: &str, source_text: &str) -> Result<Program, Vec<CompileError>> {
+    // NOTE: a native signature that can't be set is already reported, and no compile phase ran yet.
+    let Some(global_natives) = setup_natives() else {
+        return Err(Vec::new());
+    };
+
+    let mut loxie_compiler = CompilerMain::new(source_name, source_text, global_natives.peek_registry());
+
+    loxie_compiler.compile_from_start(setup_lexical_items()).ok_or_else(|| loxie_compiler.take_compile_errors())
+}
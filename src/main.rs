@@ -1,31 +1,30 @@
-use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::Path;
 use std::process::ExitCode;
 use std::time::Instant;
 
-pub mod frontend;
-pub mod semantics;
-pub mod codegen;
-pub mod compiler;
-pub mod utils;
-pub mod vm;
-
-use crate::compiler::driver::CompilerMain;
-use crate::frontend::token::*;
-// use crate::codegen::bytecode_printer::disassemble_program;
-// use crate::codegen::ir_printer::print_cfg;
-use crate::utils::bundle::Bundle;
-use crate::utils::{loxie_stdio, loxie_varchar};
-use crate::vm::callable::ExecStatus;
-use crate::vm::engine::Engine;
-use crate::vm::heap::TOTAL_STRING_OVERHEAD;
+use loxim::compiler::driver::CompilerMain;
+use loxim::frontend::parser::DEFAULT_PARSE_ERROR_MAX;
+use loxim::{setup_lexical_items, setup_natives};
+// use loxim::codegen::bytecode_printer::disassemble_program;
+// use loxim::codegen::ir_printer::print_cfg;
+use loxim::utils::logging::LogLevel;
+use loxim::vm::callable::ExecStatus;
+use loxim::vm::engine::EngineBuilder;
+use loxim::vm::heap::TOTAL_STRING_OVERHEAD;
+use loxim::vm::value::Value;
 
 const LOXIM_VERSION_MAJOR: i32 = 0;
 const LOXIM_VERSION_MINOR: i32 = 4;
 const LOXIM_VERSION_PATCH: i32 = 3;
-const LOXIM_MAX_ARGC: usize = 2;
+const LOXIM_MAX_ARGC: usize = 8;
+
+// The most int arguments given after the file name to an `--entry` function.
+const LOXIM_MAX_ENTRY_ARGC: usize = 8;
+
+// The most `--host-string` flags, each of which also takes one argument.
+const LOXIM_MAX_HOST_STRINGS: usize = 4;
 
 // The default limit of stack slots for values.
 const LOXIM_STACK_LIMIT: i32 = 128;
@@ -33,16 +32,120 @@ const LOXIM_STACK_LIMIT: i32 = 128;
 // The default limit for the VM's heap memory size in estimated bytes.
 const LOXIM_HEAP_OVERHEAD_DEFAULT: usize = TOTAL_STRING_OVERHEAD * 128;
 
+// The default limit of active calls, which is kept within what the value stack can hold.
+const LOXIM_CALL_DEPTH_LIMIT: usize = 48;
+
+/// NOTE: Applies the compile flags to a compiler, so every program of one run e.g the `--then` program is compiled with the same options.
+fn configure_compiler(loxie_compiler: &mut CompilerMain, is_library: bool, log_level: LogLevel, is_streaming: bool, parse_error_max: i32) {
+    loxie_compiler.set_library_mode(is_library);
+    loxie_compiler.set_log_level(log_level);
+    loxie_compiler.set_streaming(is_streaming);
+    loxie_compiler.set_parse_error_max(parse_error_max);
+}
+
 fn main() -> ExitCode {
     let mut arg_list = env::args();
     let arg_count: usize = arg_list.len() - 1;
 
-    if arg_count > LOXIM_MAX_ARGC {
-        println!("usage: ./loxim [--help | --version | <file-name>]");
+    if arg_count > LOXIM_MAX_ARGC + LOXIM_MAX_ENTRY_ARGC + 2 * LOXIM_MAX_HOST_STRINGS {
+        println!("usage: ./loxim [--help | --version | [--check] [--lib] [--filter] [--dump-cfg-dot] [--compact-heap] [--timings] [--stream] [--verbose] [--host-string <name>=<text>]... [--entry <fun-name>] [--summary] [--then <file-name>] [--max-errors <count>] <file-name> [<int-arg>...]]");
         return ExitCode::FAILURE;
     }
 
-    let first_arg_str = arg_list.nth(1).unwrap_or(String::from(""));
+    let mut first_arg_str = arg_list.nth(1).unwrap_or(String::from(""));
+    let mut is_check_only = false;
+    let mut is_library = false;
+    let mut is_filter = false;
+    let mut is_cfg_dump = false;
+    let mut is_heap_compacting = false;
+    let mut is_timing_phases = false;
+    let mut is_streaming = false;
+    let mut is_verbose = false;
+    let mut is_summary = false;
+    let mut entry_name_opt: Option<String> = None;
+    let mut then_name_opt: Option<String> = None;
+    let mut max_errors_arg_opt: Option<String> = None;
+    let mut host_string_args = Vec::<String>::new();
+
+    loop {
+        match first_arg_str.as_str() {
+            // Only reports frontend & semantic diagnostics without running the program.
+            "--check" => is_check_only = true,
+            // Allows sources without a `main` e.g modules.
+            "--lib" => is_library = true,
+            // Leaves only the program's output on stdout for piping e.g `echo 5 | ./loxim --filter ./double.loxie`.
+            "--filter" => is_filter = true,
+            // Prints the CFGs as Graphviz DOT instead of running e.g `./loxim --dump-cfg-dot ./prog.loxie > prog.dot`.
+            "--dump-cfg-dot" => is_cfg_dump = true,
+            // Lets the VM move live objects to the lowest heap IDs once the heap gets fragmented.
+            "--compact-heap" => is_heap_compacting = true,
+            // Prints how many microseconds each compile phase & the run took to stderr.
+            "--timings" => is_timing_phases = true,
+            // Frees each declaration's AST once its code is emitted to lower peak memory for large programs.
+            "--stream" => is_streaming = true,
+            // Prints compiler progress, the disassembly, & a program summary unless stdout is kept for other output.
+            "--verbose" => is_verbose = true,
+            // Seeds a named string for the `host_string` native e.g `--host-string mode=fast`.
+            "--host-string" => host_string_args.push(arg_list.next().unwrap_or_default()),
+            // Runs the named function with the int args after the file name instead of `main` e.g `./loxim --entry add ./math.loxie 2 3`.
+            "--entry" => entry_name_opt = arg_list.next(),
+            // Only compiles the program to print its summary like the procedure count.
+            "--summary" => is_summary = true,
+            // Runs another program's `main` on the same engine once the first run succeeds e.g `./loxim --then ./second.loxie ./first.loxie`.
+            "--then" => then_name_opt = arg_list.next(),
+            // Stops parsing each source after this many syntax errors instead of 5 e.g `--max-errors 1` for just the first.
+            "--max-errors" => max_errors_arg_opt = arg_list.next(),
+            // Leaves `--help`, `--version`, & unknown flags to be handled below.
+            _ => break,
+        }
+
+        first_arg_str = arg_list.next().unwrap_or(String::from(""));
+    }
+
+    let entry_arg_strs: Vec<String> = arg_list.collect();
+
+    if entry_name_opt.is_none() && !entry_arg_strs.is_empty() {
+        println!("usage: ./loxim [--help | --version | [--check] [--lib] [--filter] [--dump-cfg-dot] [--compact-heap] [--timings] [--stream] [--verbose] [--host-string <name>=<text>]... [--entry <fun-name>] [--summary] [--then <file-name>] [--max-errors <count>] <file-name> [<int-arg>...]]");
+        return ExitCode::FAILURE;
+    }
+
+    if host_string_args.len() > LOXIM_MAX_HOST_STRINGS {
+        eprintln!("Oops: at most {LOXIM_MAX_HOST_STRINGS} host strings may be given, but {} were.", host_string_args.len());
+        return ExitCode::FAILURE;
+    }
+
+    let mut host_strings = Vec::<(&str, &str)>::with_capacity(host_string_args.len());
+
+    for host_string_arg in &host_string_args {
+        let Some(host_string_pair) = host_string_arg.split_once('=') else {
+            eprintln!("Oops: the --host-string argument '{host_string_arg}' is not like <name>=<text>.");
+            return ExitCode::FAILURE;
+        };
+
+        host_strings.push(host_string_pair);
+    }
+
+    let mut parse_error_max = DEFAULT_PARSE_ERROR_MAX;
+
+    if let Some(max_errors_arg) = max_errors_arg_opt {
+        let Ok(max_errors_n) = max_errors_arg.parse::<i32>() else {
+            eprintln!("Oops: the --max-errors argument '{max_errors_arg}' is not an int.");
+            return ExitCode::FAILURE;
+        };
+
+        parse_error_max = max_errors_n;
+    }
+
+    let mut entry_args = Vec::<Value>::with_capacity(entry_arg_strs.len());
+
+    for entry_arg_str in &entry_arg_strs {
+        let Ok(entry_arg_n) = entry_arg_str.parse::<i32>() else {
+            eprintln!("Oops: the --entry argument '{entry_arg_str}' is not an int.");
+            return ExitCode::FAILURE;
+        };
+
+        entry_args.push(Value::Int(entry_arg_n));
+    }
 
     if first_arg_str == "--version" {
         println!(
@@ -50,21 +153,10 @@ fn main() -> ExitCode {
         );
         return ExitCode::SUCCESS;
     } else if first_arg_str == "--help" {
-        println!("usage: ./loxim [--help | --version | <file-name>]");
+        println!("usage: ./loxim [--help | --version | [--check] [--lib] [--filter] [--dump-cfg-dot] [--compact-heap] [--timings] [--stream] [--verbose] [--host-string <name>=<text>]... [--entry <fun-name>] [--summary] [--then <file-name>] [--max-errors <count>] <file-name> [<int-arg>...]]");
         return ExitCode::SUCCESS;
     }
 
-    // Setup 1: Bind native functions to the interpreter's global scope.
-    let mut global_natives = Bundle::new();
-
-    global_natives.register_native("intrin_varchar_len", Box::new(loxie_varchar::native_intrin_varchar_len), 1);
-    global_natives.register_native("intrin_varchar_get", Box::new(loxie_varchar::native_intrin_varchar_get), 2);
-    global_natives.register_native("intrin_varchar_set", Box::new(loxie_varchar::native_intrin_varchar_set), 3);
-    global_natives.register_native("intrin_varchar_push", Box::new(loxie_varchar::native_intrin_varchar_push), 2);
-    global_natives.register_native("intrin_varchar_pop", Box::new(loxie_varchar::native_intrin_varchar_pop), 1);
-    global_natives.register_native("read_int", Box::new(loxie_stdio::native_read_int), 0);
-    global_natives.register_native("print_val", Box::new(loxie_stdio::native_print_val), 1);
-
     let first_arg_copy_str = first_arg_str.clone();
     let first_arg_str_view = first_arg_copy_str.as_str();
     let source_path = Path::new(first_arg_str_view);
@@ -81,42 +173,59 @@ fn main() -> ExitCode {
         return ExitCode::FAILURE;
     }
 
-    // Setup 2: Register important lexical items to the lexer for parsing later.
     let source_text = source_text_opt.expect("Failed to unbox source string?");
 
-    let mut lexical_items = HashMap::<String, TokenType>::new();
-    lexical_items.insert(String::from("foreign"), TokenType::Keyword);
-    lexical_items.insert(String::from("fun"), TokenType::Keyword);
-    lexical_items.insert(String::from("ctor"), TokenType::Keyword);
-    lexical_items.insert(String::from("class"), TokenType::Keyword);
-    lexical_items.insert(String::from("met"), TokenType::Keyword);
-    lexical_items.insert(String::from("private"), TokenType::Keyword);
-    lexical_items.insert(String::from("public"), TokenType::Keyword);
-    lexical_items.insert(String::from("let"), TokenType::Keyword);
-    lexical_items.insert(String::from("if"), TokenType::Keyword);
-    lexical_items.insert(String::from("else"), TokenType::Keyword);
-    lexical_items.insert(String::from("while"), TokenType::Keyword);
-    lexical_items.insert(String::from("return"), TokenType::Keyword);
-    lexical_items.insert(String::from("exit"), TokenType::Keyword);
-    lexical_items.insert(String::from("bool"), TokenType::Typename);
-    lexical_items.insert(String::from("char"), TokenType::Typename);
-    lexical_items.insert(String::from("int"), TokenType::Typename);
-    lexical_items.insert(String::from("float"), TokenType::Typename);
-    lexical_items.insert(String::from("varchar"), TokenType::Typename);
-    lexical_items.insert(String::from("true"), TokenType::LiteralBool);
-    lexical_items.insert(String::from("false"), TokenType::LiteralBool);
-    lexical_items.insert(String::from("."), TokenType::OpAccess);
-    lexical_items.insert(String::from("*"), TokenType::OpTimes);
-    lexical_items.insert(String::from("/"), TokenType::OpSlash);
-    lexical_items.insert(String::from("+"), TokenType::OpPlus);
-    lexical_items.insert(String::from("-"), TokenType::OpMinus);
-    lexical_items.insert(String::from("=="), TokenType::OpEquality);
-    lexical_items.insert(String::from("!="), TokenType::OpInequality);
-    lexical_items.insert(String::from("<"), TokenType::OpLessThan);
-    lexical_items.insert(String::from(">"), TokenType::OpGreaterThan);
-    lexical_items.insert(String::from("="), TokenType::OpAssign);
+    let Some(global_natives) = setup_natives() else {
+        return ExitCode::FAILURE;
+    };
+    let lexical_items = setup_lexical_items();
+
+    let log_level = if is_filter || is_cfg_dump {
+        LogLevel::Quiet
+    } else if is_verbose {
+        LogLevel::Verbose
+    } else {
+        LogLevel::Normal
+    };
 
     let mut loxie_compiler = CompilerMain::new(first_arg_str_view, source_text.as_str(), global_natives.peek_registry());
+    // NOTE: an entry function replaces `main`, so the program may not have one just like a library module.
+    configure_compiler(&mut loxie_compiler, is_library || entry_name_opt.is_some(), log_level, is_streaming, parse_error_max);
+
+    if is_summary {
+        let Some(program) = loxie_compiler.compile_from_start(lexical_items) else {
+            eprintln!("Compilation failed, see errors above.");
+            return ExitCode::FAILURE;
+        };
+
+        println!("{}", program.summary());
+
+        return ExitCode::SUCCESS;
+    }
+
+    if is_check_only {
+        if !loxie_compiler.check_only(lexical_items) {
+            eprintln!("Check failed, see errors above.");
+            return ExitCode::FAILURE;
+        }
+
+        if !is_filter {
+            println!("\x1b[1;32mCheck passed\x1b[0m");
+        }
+
+        return ExitCode::SUCCESS;
+    }
+
+    if is_cfg_dump {
+        let Some(cfg_dot_text) = loxie_compiler.dump_cfg_dot(lexical_items) else {
+            eprintln!("Compilation failed, see errors above.");
+            return ExitCode::FAILURE;
+        };
+
+        print!("{cfg_dot_text}");
+
+        return ExitCode::SUCCESS;
+    }
 
     let program_opt = loxie_compiler.compile_from_start(lexical_items);
 
@@ -127,21 +236,98 @@ fn main() -> ExitCode {
 
     let mut program = program_opt.unwrap();
 
-    // let mut engine = Engine::new(LOXIM_HEAP_OVERHEAD_DEFAULT, LOXIM_STACK_LIMIT);
-    let mut engine = Engine::new(&mut program, LOXIM_HEAP_OVERHEAD_DEFAULT, LOXIM_STACK_LIMIT);
+    for (host_name, host_text) in host_strings {
+        if !program.add_host_string(host_name, host_text) {
+            eprintln!("Oops: could not seed host string '{host_name}', as it was given twice or the heap has no room left.");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let entry_info_opt = if let Some(entry_name) = entry_name_opt.as_deref() {
+        let Some((entry_proc_id, entry_arity)) = program.find_function(entry_name) else {
+            eprintln!("Oops: no function named '{entry_name}' was found for --entry.");
+            return ExitCode::FAILURE;
+        };
+
+        if entry_arity != entry_args.len() as i32 {
+            eprintln!("Oops: function '{entry_name}' takes {entry_arity} argument(s), but {} were given for --entry.", entry_args.len());
+            return ExitCode::FAILURE;
+        }
+
+        Some(entry_proc_id)
+    } else { None };
+
+    let mut engine = EngineBuilder::new()
+        .heap_size(LOXIM_HEAP_OVERHEAD_DEFAULT)
+        .stack_size(LOXIM_STACK_LIMIT)
+        .max_call_depth(LOXIM_CALL_DEPTH_LIMIT)
+        .checked_arithmetic(true)
+        .interactive(!is_filter)
+        .compact_heap(is_heap_compacting)
+        .build(program);
+    engine.set_output_sinks(Box::new(std::io::stdout()), Box::new(std::io::stderr()));
 
     let pre_run_time = Instant::now();
-    // let engine_status = engine.run(&program, &global_natives);
-    let engine_status = engine.run(&program, &global_natives);
+    let engine_status = if let Some(entry_proc_id) = entry_info_opt {
+        let entry_result = engine.run_procedure(&global_natives, entry_proc_id, &entry_args);
+
+        if engine.get_status() == ExecStatus::Ok {
+            if is_filter {
+                println!("{entry_result}");
+            } else {
+                println!("Entry result: {entry_result}");
+            }
+        }
+
+        engine.get_status()
+    } else {
+        engine.run(&global_natives)
+    };
 
-    println!(
-        "\x1b[1;33mFinished in {} ms\x1b[0m",
-        pre_run_time.elapsed().as_millis()
-    );
+    // NOTE: the next program gets the engine reset to a fresh stack & a heap of its own preloadables, though without the host strings of the first one.
+    let engine_status = match then_name_opt.as_deref() {
+        Some(then_name) if engine_status == ExecStatus::Ok => {
+            let Ok(then_source_text) = fs::read_to_string(then_name) else {
+                eprintln!("Oops: could not read the --then program '{then_name}'.");
+                return ExitCode::FAILURE;
+            };
+
+            let mut then_compiler = CompilerMain::new(then_name, then_source_text.as_str(), global_natives.peek_registry());
+            configure_compiler(&mut then_compiler, is_library, log_level, is_streaming, parse_error_max);
+
+            let Some(then_program) = then_compiler.compile_from_start(setup_lexical_items()) else {
+                eprintln!("Compilation failed, see errors above.");
+                return ExitCode::FAILURE;
+            };
+
+            engine.reset(then_program);
+            engine.run(&global_natives)
+        },
+        _ => engine_status,
+    };
+
+    let run_micros = pre_run_time.elapsed().as_micros();
+
+    if !is_filter {
+        println!(
+            "\x1b[1;33mFinished in {} ms\x1b[0m",
+            run_micros / 1000
+        );
+    }
+
+    // NOTE: timings go to stderr, so `--filter` still leaves only the program's output on stdout.
+    if is_timing_phases {
+        for (phase_name, phase_micros) in loxie_compiler.get_phase_timings().iter().chain([&("run", run_micros)]) {
+            eprintln!("Timing: {phase_name} took {phase_micros} us");
+        }
+    }
 
     match engine_status {
         ExecStatus::Ok => {
-            println!("\x1b[1;32mOK\x1b[0m");
+            if !is_filter {
+                println!("\x1b[1;32mOK\x1b[0m");
+            }
+
             ExitCode::SUCCESS
         },
         ExecStatus::AccessError => {
@@ -157,7 +343,7 @@ fn main() -> ExitCode {
             ExitCode::FAILURE
         },
         ExecStatus::BadMath => {
-            eprintln!("\x1b[1;31mRunError: Division by zero.\x1b[0m");
+            eprintln!("\x1b[1;31mRunError: Illegal math e.g division by zero or int overflow.\x1b[0m");
             ExitCode::FAILURE
         },
         ExecStatus::IllegalInstruction => {
@@ -168,9 +354,29 @@ fn main() -> ExitCode {
             eprintln!("\x1b[1;31mRunError: Invalid argument passed to opcode.\x1b[0m");
             ExitCode::FAILURE
         },
-        ExecStatus::NotOk => {
-            eprintln!("\x1b[1;31mRunError: Exited with non-zero status.\x1b[0m");
+        ExecStatus::Recoverable => {
+            eprintln!("\x1b[1;31mRunError: Unhandled recoverable status.\x1b[0m");
             ExitCode::FAILURE
         },
+        ExecStatus::StackUnderflow => {
+            eprintln!("\x1b[1;31mRunError: Stack underflow, popped from an empty stack.\x1b[0m");
+            ExitCode::FAILURE
+        },
+        ExecStatus::CallDepthExceeded => {
+            eprintln!("\x1b[1;31mRunError: Call depth limit of {LOXIM_CALL_DEPTH_LIMIT} exceeded.\x1b[0m");
+            ExitCode::FAILURE
+        },
+        ExecStatus::HeapExhausted => {
+            eprintln!("\x1b[1;31mRunError: Heap exhausted, every heap cell is held by a live object.\x1b[0m");
+            ExitCode::FAILURE
+        },
+        ExecStatus::NotOk => {
+            let main_exit_code = engine.get_exit_code();
+            eprintln!("\x1b[1;31mRunError: Exited with non-zero status {main_exit_code}.\x1b[0m");
+
+            // NOTE: process exit codes only keep the low 8 bits, so a nonzero code like 256 must not wrap to a success.
+            let truncated_exit_code = main_exit_code as u8;
+            ExitCode::from(if truncated_exit_code != 0 { truncated_exit_code } else { 1 })
+        },
     }
 }
@@ -1,17 +1,43 @@
-use std::collections::HashMap;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
 
+use crate::codegen::const_eval::{eval_const_condition, eval_const_expr};
 use crate::frontend::token::*;
 use crate::frontend::ast::*;
 use crate::semantics::scope::*;
 use crate::semantics::types::{AccessFlag, OperatorTag, ValueCategoryTag};
 use crate::semantics::blueprint::*;
-
-const BOOLEAN_TYPE_ID_N: i32 = 0;
-const CHAR_TYPE_ID_N: i32 = 1;
-const INTEGER_TYPE_ID_N: i32 = 2;
-const FLOATING_TYPE_ID_N: i32 = 3;
-const VARCHAR_TYPE_ID_N: i32 = 4;
-const ANY_TYPE_ID_N: i32 = 5;
+use crate::semantics::modules::{ModuleTable, NameResolution};
+use crate::log_verbose;
+use crate::utils::bundle::{CallbackShape, NativeBrief};
+use crate::utils::logging::LogLevel;
+use crate::vm::value::Value;
+
+pub const BOOLEAN_TYPE_ID_N: i32 = 0;
+pub const CHAR_TYPE_ID_N: i32 = 1;
+pub const INTEGER_TYPE_ID_N: i32 = 2;
+pub const FLOATING_TYPE_ID_N: i32 = 3;
+pub const VARCHAR_TYPE_ID_N: i32 = 4;
+pub const ANY_TYPE_ID_N: i32 = 5;
+pub const LONG_TYPE_ID_N: i32 = 6;
+pub const CALLBACK_TYPE_ID_N: i32 = 7;
+pub const BUFFER_TYPE_ID_N: i32 = 8;
+
+/// NOTE: Maps a primitive type name to its fixed analyzer type ID e.g `"int"` to `INTEGER_TYPE_ID_N`, or `-1` for any other name such as a class.
+pub fn lookup_primitive_type_id(type_name: &str) -> i32 {
+    match type_name {
+        "bool" => BOOLEAN_TYPE_ID_N,
+        "char" => CHAR_TYPE_ID_N,
+        "int" => INTEGER_TYPE_ID_N,
+        "float" => FLOATING_TYPE_ID_N,
+        "varchar" => VARCHAR_TYPE_ID_N,
+        "any" => ANY_TYPE_ID_N,
+        "long" => LONG_TYPE_ID_N,
+        "callback" => CALLBACK_TYPE_ID_N,
+        "buffer" => BUFFER_TYPE_ID_N,
+        _ => -1,
+    }
+}
 
 /**
  * ### ABOUT
@@ -35,11 +61,11 @@ enum RecordInfoMode {
  This is a helper function to check types for homogeneously typed expressions e.g arithmetics, assignments, etc. However, this check will fail on any unknown types for things such as undeclared names.
  */
 fn check_binary_typing_homogeneously(lhs_info: &SemanticNote, rhs_info: &SemanticNote) -> bool {
-    let lhs_type_idx = if let SemanticNote::DataValue(idx, _) = lhs_info {
+    let lhs_type_idx = if let SemanticNote::DataValue(idx, _) | SemanticNote::ClassEntity(idx, _) = lhs_info {
         *idx
     } else { -1 };
 
-    let rhs_type_idx = if let SemanticNote::DataValue(idx, _) = rhs_info {
+    let rhs_type_idx = if let SemanticNote::DataValue(idx, _) | SemanticNote::ClassEntity(idx, _) = rhs_info {
         *idx
     } else { -1 };
 
@@ -47,11 +73,11 @@ fn check_binary_typing_homogeneously(lhs_info: &SemanticNote, rhs_info: &Semanti
 }
 
 fn check_assignment_value_groups(lhs_info: &SemanticNote, rhs_info: &SemanticNote) -> bool {
-    let lhs_value_group = if let SemanticNote::DataValue(_, value_group) = lhs_info {
+    let lhs_value_group = if let SemanticNote::DataValue(_, value_group) | SemanticNote::ClassEntity(_, value_group) = lhs_info {
         *value_group
     } else { ValueCategoryTag::Temporary };
 
-    let rhs_value_group = if let SemanticNote::DataValue(_, rhs_value_group) = rhs_info {
+    let rhs_value_group = if let SemanticNote::DataValue(_, rhs_value_group) | SemanticNote::ClassEntity(_, rhs_value_group) = rhs_info {
         *rhs_value_group
     } else { ValueCategoryTag::Unknown };
 
@@ -82,6 +108,9 @@ pub struct Analyzer {
     /// **NOTE:** Indicates the current class decl. being analyzed by its type ID.
     current_class_id: i32,
 
+    /// **NOTE:** Indicates the class decl. whose body is being analyzed by its type ID, which stays set within static methods unlike `current_class_id`.
+    enclosing_class_id: i32,
+
     /// **NOTE:** Indicates the current access modifier of a visiting member decl. in the currently visited class.
     current_class_mod: AccessFlag,
 
@@ -90,6 +119,48 @@ pub struct Analyzer {
 
     /// **NOTE:** Indicates that top-level decls. must be recorded before body processing. If `false`, body processing takes place instead.
     prepass_flag: bool,
+
+    /// **NOTE:** Indicates that the next visited name is a callee, so it may refer to a function. Otherwise, function names cannot be used as values since they aren't first-class yet.
+    callee_flag: bool,
+
+    /// **NOTE:** Indicates that the next visited name is a whole argument for a `callback` parameter, so it may name a plain function whose ID gets passed.
+    callback_arg_flag: bool,
+
+    /// **NOTE:** Holds the shape a native expects of the functions passed for the flagged `callback` argument, or `None` if no native signature gives one.
+    callback_arg_shape: Option<CallbackShape>,
+
+    /// **NOTE:** Indicates that the next visited name is the member of an access like `obj.name`, so `visit_binary()` reports it if the class lacks that member.
+    member_name_flag: bool,
+
+    /// **NOTE:** Indicates that a constructor body is being checked, so its returns must be bare as they implicitly give the new instance.
+    ctor_body_flag: bool,
+
+    /// **NOTE:** Holds the fields that every path through the checked constructor body has assigned so far, as the rest may not hold an instance yet.
+    ctor_assigned_field_names: HashSet<String>,
+
+    /// **NOTE:** Holds each module's imports & top-level names, which namespace the functions & constants of library modules e.g `math.abs`.
+    modules: ModuleTable,
+
+    /// **NOTE:** Names the module of the TU being analyzed, which is `""` for the main program.
+    current_module: String,
+
+    /// **NOTE:** Holds the names of all foreign stubs, as natives have no procedure ID to pass as a callback.
+    foreign_stub_names: HashSet<String>,
+
+    /// **NOTE:** Holds the type IDs of classes with a private constructor, which only code within that class may call e.g a static factory method.
+    private_ctor_class_ids: HashSet<i32>,
+
+    /// **NOTE:** Maps native names to their parameter & result type IDs, so foreign stubs can be checked against the real natives.
+    native_signatures: HashMap<String, (Vec<i32>, i32)>,
+
+    /// **NOTE:** Maps native names to the callback shapes of their parameters, see `NativeBrief::callback_shapes`.
+    native_callback_shapes: HashMap<String, Vec<Option<CallbackShape>>>,
+
+    /// **NOTE:** Controls progress messages such as recorded fields, which only verbose runs show. Diagnostics are always reported.
+    log_level: LogLevel,
+
+    /// **NOTE:** Counts every reported error, as checking goes on past a failed statement or declaration to report the rest in the same run.
+    error_count: Cell<usize>,
 }
 
 impl Analyzer {
@@ -101,6 +172,9 @@ impl Analyzer {
         temp_type_table.insert(INTEGER_TYPE_ID_N, String::from("int"));
         temp_type_table.insert(FLOATING_TYPE_ID_N, String::from("float"));
         temp_type_table.insert(VARCHAR_TYPE_ID_N, String::from("varchar"));
+        temp_type_table.insert(LONG_TYPE_ID_N, String::from("long"));
+        temp_type_table.insert(CALLBACK_TYPE_ID_N, String::from("callback"));
+        temp_type_table.insert(BUFFER_TYPE_ID_N, String::from("buffer"));
 
         Self {
             class_blueprints: BlueprintTable::default(),
@@ -115,14 +189,97 @@ impl Analyzer {
             scopes: ScopeStack::default(),
             source_str: source_view,
             current_class_id: -1,
+            enclosing_class_id: -1,
             current_class_mod: AccessFlag::Hidden,
             current_name_accessible: AccessFlag::Hidden,
             prepass_flag: true,
+            callee_flag: false,
+            callback_arg_flag: false,
+            callback_arg_shape: None,
+            member_name_flag: false,
+            ctor_body_flag: false,
+            ctor_assigned_field_names: HashSet::new(),
+            modules: ModuleTable::default(),
+            current_module: String::new(),
+            foreign_stub_names: HashSet::new(),
+            private_ctor_class_ids: HashSet::new(),
+            native_signatures: HashMap::new(),
+            native_callback_shapes: HashMap::new(),
+            log_level: LogLevel::Normal,
+            error_count: Cell::new(0),
+        }
+    }
+
+    pub fn set_log_level(&mut self, level: LogLevel) {
+        self.log_level = level;
+    }
+
+    /// NOTE: Only natives given a signature by `Bundle::set_native_signature` are recorded, as the rest can't be checked.
+    pub fn load_native_signatures(&mut self, native_catalog: &HashMap<String, NativeBrief>) {
+        for (native_name, brief) in native_catalog {
+            if brief.result_type_id != -1 {
+                self.native_signatures.insert(native_name.clone(), (brief.param_type_ids.clone(), brief.result_type_id));
+                self.native_callback_shapes.insert(native_name.clone(), brief.callback_shapes.clone());
+            }
+        }
+    }
+
+    fn help_format_signature(&self, param_type_ids: &[i32], result_type_id: i32) -> String {
+        let param_type_names: Vec<&str> = param_type_ids.iter()
+            .map(|type_id| self.type_table.get(type_id).map_or("?", |type_name| type_name.as_str()))
+            .collect();
+        let result_type_name = self.type_table.get(&result_type_id).map_or("?", |type_name| type_name.as_str());
+
+        format!("({}): {}", param_type_names.join(", "), result_type_name)
+    }
+
+    fn help_name_type(&self, type_id: i32) -> &str {
+        self.type_table.get(&type_id).map_or("?", |type_name| type_name.as_str())
+    }
+
+    fn help_format_member(&self, name: &str, member: &ClassMember) -> String {
+        let access_name = if member.access_mod == AccessFlag::Exposed { "public" } else { "private" };
+
+        let member_desc = match &member.note {
+            SemanticNote::DataValue(type_id, _) | SemanticNote::ClassEntity(type_id, _) => format!("let {name}: {} (type-id {type_id})", self.help_name_type(*type_id)),
+            SemanticNote::Constructor(param_type_ids, class_type_id, _) => format!("ctor{}", self.help_format_signature(param_type_ids, *class_type_id)),
+            SemanticNote::Method(param_type_ids, result_type_id, _, _) => format!("met {name}{}", self.help_format_signature(param_type_ids, *result_type_id)),
+            SemanticNote::Callable(param_type_ids, result_type_id, _) => format!("static met {name}{}", self.help_format_signature(param_type_ids, *result_type_id)),
+            SemanticNote::Dud => format!("{name} (unknown)"),
+        };
+
+        format!("{access_name} {member_desc}")
+    }
+
+    /// NOTE: Renders the type table by ascending type ID, then each class's members by name, which explains the type IDs in diagnostics e.g `(type-id 9)`.
+    pub fn dump_type_info(&self) -> String {
+        let mut type_ids: Vec<i32> = self.type_table.keys().copied().collect();
+        type_ids.sort();
+
+        let mut dump_lines = vec![String::from("type table:")];
+
+        for type_id in &type_ids {
+            dump_lines.push(format!("  type-id {type_id}: {}", self.help_name_type(*type_id)));
         }
+
+        for type_id in &type_ids {
+            let Some(blueprint_ref) = self.class_blueprints.try_get_entry(*type_id) else {
+                continue;
+            };
+
+            dump_lines.push(format!("class {} (type-id {type_id}):", self.help_name_type(*type_id)));
+
+            for (member_name, member_ref) in blueprint_ref.sorted_entries() {
+                dump_lines.push(format!("  {}", self.help_format_member(member_name, member_ref)));
+            }
+        }
+
+        dump_lines.join("\n")
     }
 
-    pub fn reset_source(&mut self, source_view_next: String) {
+    pub fn reset_source(&mut self, source_view_next: String, module_name: &str) {
         self.source_str = source_view_next;
+        self.current_module = module_name.to_string();
     }
 
     pub fn reset_with(&mut self) {
@@ -180,32 +337,108 @@ impl Analyzer {
     }
 
     fn lookup_name_info(&mut self, name: &str) -> SemanticNote {
-        if self.current_class_id != -1 {
-            if let Some(bp_ref) = self.class_blueprints.try_get_entry_mut(self.current_class_id) {
-                if let Some(bp_ref_member_ref) = bp_ref.try_get_entry_mut(name) {
-                    self.current_name_accessible = bp_ref_member_ref.0;
-                    return bp_ref_member_ref.1.note.clone();
-                }
-            }
+        if self.current_class_id != -1
+            && let Some(bp_ref) = self.class_blueprints.try_get_entry_mut(self.current_class_id)
+            && let Some(bp_ref_member_ref) = bp_ref.try_get_entry_mut(name) {
+            self.current_name_accessible = bp_ref_member_ref.0;
+            return bp_ref_member_ref.1.note.clone();
         }
 
-        let normal_info = self.scopes.lookup_name_info(name);
+        let normal_info = self.scopes.lookup_name_info(self.modules.resolve_global_key(&self.current_module, name).as_str());
         self.current_name_accessible = if normal_info.is_dud() { AccessFlag::Hidden } else { AccessFlag::Exposed };
 
         normal_info
     }
 
-    fn record_name_info(&mut self, name: &str, info: SemanticNote, mode: RecordInfoMode) -> bool {
+    /// NOTE: Finds the class type ID named by a plain name like `Box` in `Box.make()`, unless that name is also a variable.
+    fn lookup_class_name_id(&mut self, e: &dyn Expr) -> Option<i32> {
+        let name_token = e.get_token_opt()?;
+
+        if e.get_operator() != OperatorTag::Noop || name_token.tag != TokenType::Identifier {
+            return None;
+        }
+
+        let source_copy = self.source_str.clone();
+        let name_lexeme = name_token.to_lexeme_str(source_copy.as_str())?;
+
+        if self.scopes.lookup_name_info(name_lexeme).try_unbox_data_value().is_some() {
+            return None;
+        }
+
+        let class_type_id = *self.type_table.iter().find(|&item| item.1.as_str() == name_lexeme)?.0;
+
+        self.class_blueprints.try_get_entry_mut(class_type_id)?;
+        self.temp_token = name_token;
+
+        Some(class_type_id)
+    }
+
+    /// NOTE: Checks if a call by a class name like `Box(1)` reaches a private constructor from outside of that class.
+    fn is_private_ctor_call(&self, callee_name: &str, result_type_id: i32) -> bool {
+        self.private_ctor_class_ids.contains(&result_type_id)
+            && self.enclosing_class_id != result_type_id
+            && self.type_table.get(&result_type_id).is_some_and(|class_name| class_name.as_str() == callee_name)
+    }
+
+    /// NOTE: Only plain functions have a procedure ID to pass for a `callback` parameter, so natives, constructors, and methods are rejected. A function passed to a native must also have the shape its native signature gives for that parameter.
+    fn check_callback_name(&mut self, name: &str, name_info: &SemanticNote, expected_shape: Option<CallbackShape>) -> SemanticNote {
+        let is_plain_function = matches!(name_info, SemanticNote::Callable(_, _, _))
+            && !self.foreign_stub_names.contains(name)
+            && !self.type_table.values().any(|type_name| type_name.as_str() == name);
+
+        if !is_plain_function {
+            self.report_culprit_error(&self.temp_token, "Only plain functions can be passed as callbacks, not foreign stubs, constructors, or methods.");
+            return SemanticNote::Dud;
+        }
+
+        if let Some((shape_param_ids, shape_result_id)) = expected_shape
+            && !matches!(name_info, SemanticNote::Callable(param_type_ids, result_type_id, _) if *param_type_ids == shape_param_ids && *result_type_id == shape_result_id) {
+            let bad_shape_msg = format!("The callback '{name}' must be a function of '{}' to be passed here.", self.help_format_signature(&shape_param_ids, shape_result_id));
+            self.report_culprit_error(&self.temp_token, bad_shape_msg.as_str());
+            return SemanticNote::Dud;
+        }
+
+        SemanticNote::DataValue(CALLBACK_TYPE_ID_N, ValueCategoryTag::Temporary)
+    }
+
+    /// NOTE: Names of class-typed values are instances, so they're noted as class entities to allow member accesses on them.
+    fn make_identity_note(&mut self, type_id: i32) -> SemanticNote {
+        if self.class_blueprints.try_get_entry_mut(type_id).is_some() {
+            SemanticNote::ClassEntity(type_id, ValueCategoryTag::Identity)
+        } else {
+            SemanticNote::DataValue(type_id, ValueCategoryTag::Identity)
+        }
+    }
+
+    /// NOTE: Checks if a local name would hide a global function or native (foreign stub), which breaks calls to it in codegen.
+    fn is_shadowing_global_callable(&self, name: &str) -> bool {
+        if let Some(global_scope_ref) = self.scopes.global_scope() {
+            return matches!(global_scope_ref.try_get_entry(self.modules.resolve_global_key(&self.current_module, name).as_str()), Some(SemanticNote::Callable(_, _, _)));
+        }
+
+        false
+    }
+
+    /// NOTE: Global names are found before locals, so a local named like a module constant would always resolve to the constant instead.
+    fn is_shadowing_global_const(&self, name: &str) -> bool {
+        if let Some(global_scope_ref) = self.scopes.global_scope() {
+            return matches!(global_scope_ref.try_get_entry(self.modules.resolve_global_key(&self.current_module, name).as_str()), Some(SemanticNote::DataValue(_, ValueCategoryTag::Temporary)));
+        }
+
+        false
+    }
+
+    fn record_name_info(&mut self, name: &str, info: SemanticNote, mode: RecordInfoMode, site_opt: Option<&Token>) -> bool {
         match mode {
-            RecordInfoMode::Local => self.scopes.current_scope_mut().unwrap().try_set_entry(name, info),
-            RecordInfoMode::Global => self.scopes.global_scope_mut().unwrap().try_set_entry(name, info),
+            RecordInfoMode::Local => self.scopes.current_scope_mut().unwrap().try_set_entry(name, info, site_opt),
+            RecordInfoMode::Global => self.scopes.global_scope_mut().unwrap().try_set_entry(name, info, site_opt),
             RecordInfoMode::Member => {
-                self.record_class_member_info(self.current_class_id, name, self.current_class_mod, info)
+                self.record_class_member_info(self.current_class_id, name, self.current_class_mod, info, site_opt)
             }
         }
     }
 
-    fn record_class_member_info(&mut self, class_id: i32, name: &str, access_mod_arg: AccessFlag, note_arg: SemanticNote) -> bool {
+    fn record_class_member_info(&mut self, class_id: i32, name: &str, access_mod_arg: AccessFlag, note_arg: SemanticNote, site_opt: Option<&Token>) -> bool {
         let class_bp_ref_opt = self.class_blueprints.try_get_entry_mut(class_id);
 
         if class_bp_ref_opt.is_none() {
@@ -215,17 +448,314 @@ impl Analyzer {
         class_bp_ref_opt.unwrap().try_set_entry(name, ClassMember {
             note: note_arg,
             access_mod: access_mod_arg,
+            site: site_opt.copied(),
         })
     }
 
+    /// NOTE: Finds where an already recorded name was first declared, if its declaring token was kept.
+    fn lookup_decl_site(&mut self, name: &str, mode: RecordInfoMode) -> Option<Token> {
+        match mode {
+            RecordInfoMode::Local => self.scopes.current_scope()?.try_get_site(name).copied(),
+            RecordInfoMode::Global => {
+                let global_scope_ref = self.scopes.global_scope()?;
+
+                global_scope_ref.try_get_site(ModuleTable::qualify(&self.current_module, name).as_str())
+                    .or_else(|| global_scope_ref.try_get_site(name))
+                    .copied()
+            },
+            RecordInfoMode::Member => {
+                let (_, member_ref) = self.class_blueprints.try_get_entry_mut(self.current_class_id)?.try_get_entry_mut(name)?;
+
+                member_ref.site
+            }
+        }
+    }
+
+    /// NOTE: Checks that a `let` constructor parameter names a field of the current class with the same type, as its value is stored there.
+    fn check_field_binding(&mut self, field_name: &str, param_type_id: i32) -> bool {
+        let Some(bp_ref) = self.class_blueprints.try_get_entry_mut(self.current_class_id) else {
+            return false;
+        };
+
+        let Some((_, member_ref)) = bp_ref.try_get_entry_mut(field_name) else {
+            return false;
+        };
+
+        member_ref.note.try_unbox_data_value().is_some_and(|(field_type_id, _)| field_type_id == param_type_id)
+    }
+
+    /// NOTE: Records a top-level function or constant under its module's namespace e.g `math.abs`, which keeps the same name in other modules from colliding.
+    fn record_module_global(&mut self, name: &str, info: SemanticNote, site: &Token) -> bool {
+        let global_key = ModuleTable::qualify(&self.current_module, name);
+
+        if !self.scopes.global_scope_mut().unwrap().try_set_entry(global_key.as_str(), info, Some(site)) {
+            return false;
+        }
+
+        self.modules.record_owner(&self.current_module, name);
+
+        true
+    }
+
+    fn is_ambiguous_name(&self, name: &str) -> bool {
+        matches!(self.modules.resolve(&self.current_module, name), NameResolution::Ambiguous(_))
+    }
+
+    /// NOTE: Finds the module named by the object of an access like `math.abs`, unless a variable or another global has that name.
+    fn find_accessed_module(&self, e: &dyn Expr) -> Option<String> {
+        let name_token = e.get_token_opt()?;
+
+        if e.get_operator() != OperatorTag::Noop || name_token.tag != TokenType::Identifier {
+            return None;
+        }
+
+        let name_lexeme = name_token.to_lexeme_str(self.source_str.as_str())?;
+
+        if !self.modules.is_module_name(name_lexeme) || !self.scopes.lookup_name_info(name_lexeme).is_dud() {
+            return None;
+        }
+
+        Some(name_lexeme.to_string())
+    }
+
+    /// NOTE: Checks a qualified name like `math.abs`, whose module must be imported by the current one.
+    fn check_module_access(&mut self, module_name: &str, member: &dyn Expr, is_callee: bool) -> SemanticNote {
+        let Some(member_token) = member.get_token_opt() else {
+            return SemanticNote::Dud;
+        };
+
+        let source_copy = self.source_str.clone();
+        let member_name = member_token.to_lexeme_str(source_copy.as_str()).unwrap_or("...");
+        self.temp_token = member_token;
+
+        if !self.modules.is_imported_by(&self.current_module, module_name) {
+            let missing_import_msg = format!("Module '{module_name}' is not imported here, so `{module_name}.{member_name}` needs an `import {module_name};` first.");
+            self.report_culprit_error(&member_token, missing_import_msg.as_str());
+
+            return SemanticNote::Dud;
+        }
+
+        let member_info = self.scopes.global_scope()
+            .and_then(|global_scope_ref| global_scope_ref.try_get_entry(ModuleTable::qualify(module_name, member_name).as_str()))
+            .cloned()
+            .unwrap_or(SemanticNote::Dud);
+
+        if member_info.is_callable() && self.modules.is_hidden_from(&self.current_module, module_name, member_name) {
+            let private_fun_msg = format!("The function '{module_name}.{member_name}' is private, so only module '{module_name}' may call it.");
+            self.report_culprit_error(&member_token, private_fun_msg.as_str());
+
+            return SemanticNote::Dud;
+        }
+
+        if member_info.is_dud() {
+            let missing_member_msg = format!("Module '{module_name}' has no function, constant, or foreign stub named '{member_name}'.");
+            self.report_culprit_error(&member_token, missing_member_msg.as_str());
+
+            return SemanticNote::Dud;
+        }
+
+        if !is_callee && member_info.is_callable() {
+            self.report_culprit_error(&member_token, "Functions are not first-class; did you mean to call it?");
+            return SemanticNote::Dud;
+        }
+
+        self.current_name_accessible = AccessFlag::Exposed;
+
+        member_info
+    }
+
+    /// NOTE: Checks the `equals` method that `==` & `!=` call for instances of a class having one, which must take another instance of its class & give a bool.
+    fn check_equals_method(&mut self, operand_info: &SemanticNote, op_symbol: &str, expr_line_no: usize) -> bool {
+        let (SemanticNote::DataValue(class_id, _) | SemanticNote::ClassEntity(class_id, _)) = *operand_info else {
+            return true;
+        };
+
+        let Some((equals_access, equals_member)) = self.class_blueprints.try_get_entry_mut(class_id).and_then(|bp_ref| bp_ref.try_get_entry_mut("equals")) else {
+            return true;
+        };
+
+        let has_equals_signature = matches!(&equals_member.note, SemanticNote::Method(param_type_ids, BOOLEAN_TYPE_ID_N, 1, _) if param_type_ids.as_slice() == [class_id]);
+        let class_name = self.help_name_type(class_id).to_string();
+
+        if !has_equals_signature {
+            let bad_equals_msg = format!("Cannot compare instances of {class_name} by {op_symbol} around Ln. {expr_line_no}- Its `equals` method must be like `met equals(other: {class_name}): bool`.");
+            self.report_plain_error(bad_equals_msg.as_str());
+
+            return false;
+        }
+
+        if equals_access == AccessFlag::Hidden && self.current_class_id != class_id {
+            let private_equals_msg = format!("Cannot compare instances of {class_name} by {op_symbol} around Ln. {expr_line_no}- Its `equals` method is private to class {class_name}.");
+            self.report_plain_error(private_equals_msg.as_str());
+
+            return false;
+        }
+
+        true
+    }
+
+    /// NOTE: Finds a class-typed field of the current class named by a plain name like `next` in `next.get()`, if the checked constructor hasn't surely assigned it yet. Methods can't be checked this way, as they may run after any other method assigned the field.
+    fn find_unassigned_ctor_field(&mut self, e: &dyn Expr) -> Option<String> {
+        if !self.ctor_body_flag || e.get_operator() != OperatorTag::Noop {
+            return None;
+        }
+
+        let name_token = e.get_token_opt().filter(|name_token| name_token.tag == TokenType::Identifier)?;
+        let source_copy = self.source_str.clone();
+        let field_name = name_token.to_lexeme_str(source_copy.as_str())?;
+
+        if self.ctor_assigned_field_names.contains(field_name) {
+            return None;
+        }
+
+        let field_type_id = match self.class_blueprints.try_get_entry_mut(self.current_class_id)?.try_get_entry_mut(field_name)?.1.note {
+            SemanticNote::ClassEntity(type_id, _) | SemanticNote::DataValue(type_id, _) => type_id,
+            _ => {
+                return None;
+            },
+        };
+
+        self.class_blueprints.try_get_entry_mut(field_type_id)?;
+
+        Some(field_name.to_string())
+    }
+
+    /// NOTE: Fields, methods, and the constructor (named by its class) share one namespace per class, so a repeated member name is reported along with what it first named.
+    fn report_member_collision(&mut self, culprit_opt: Option<&Token>, name: &str, new_kind: &str) {
+        let old_kind = self.class_blueprints.try_get_entry_mut(self.current_class_id)
+            .and_then(|bp_ref| bp_ref.try_get_entry_mut(name))
+            .map_or("member", |(_, member_ref)| match member_ref.note {
+                SemanticNote::Constructor(_, _, _) => "constructor",
+                SemanticNote::Method(_, _, _, _) | SemanticNote::Callable(_, _, _) => "method",
+                _ => "field",
+            });
+        let class_name = self.type_table.get(&self.current_class_id).cloned().unwrap_or_default();
+
+        let collision_msg = match (old_kind, new_kind) {
+            ("constructor", "constructor") => format!("Cannot redeclare the '{class_name}' constructor"),
+            _ if old_kind == new_kind => format!("Invalid redeclaration of {new_kind} '{name}'"),
+            _ => format!("The {new_kind} '{name}' collides with the {old_kind} of the same name in class {class_name}, as its fields, methods, and constructor share one namespace."),
+        };
+
+        if let Some(culprit) = culprit_opt {
+            self.report_redeclaration_error(culprit, RecordInfoMode::Member, collision_msg.as_str());
+        } else {
+            self.report_plain_error(collision_msg.as_str());
+        }
+    }
+
+    /// NOTE: Reports a redeclared name at its new site, plus the original site when it's known. The original may be in another imported source.
+    fn report_redeclaration_error(&mut self, culprit: &Token, mode: RecordInfoMode, msg: &str) {
+        let culprit_name = String::from(culprit.to_lexeme_str(self.source_str.as_str()).unwrap_or(""));
+
+        if let Some(old_site) = self.lookup_decl_site(culprit_name.as_str(), mode) {
+            let full_msg = format!("{msg}\n\tNote: '{culprit_name}' was first declared at [Ln {}, Col {}].", old_site.line_no, old_site.col_no);
+            self.report_culprit_error(culprit, full_msg.as_str());
+        } else {
+            self.report_culprit_error(culprit, msg);
+        }
+    }
+
+    /// NOTE: Both prefix and postfix `++` / `--` need a named numeric variable to update, so temporaries & member accesses are rejected here.
+    fn help_check_step_unary(&mut self, e: &Unary, inner_info: SemanticNote) -> SemanticNote {
+        if e.get_inner().get_operator() != OperatorTag::Noop {
+            self.report_plain_error(format!("The operand of '{}' must be a variable name.", e.get_operator().as_symbol()).as_str());
+            return SemanticNote::Dud;
+        }
+
+        // NOTE: steps are only lowered for locals & params on the stack, so a field like `count` in a method needs `count = count + 1;` instead.
+        let source_copy = self.source_str.clone();
+        let operand_name = e.get_inner().get_token_opt().and_then(|name_token| name_token.to_lexeme_str(source_copy.as_str())).unwrap_or_default();
+
+        if self.current_class_id != -1
+            && let Some(bp_ref) = self.class_blueprints.try_get_entry_mut(self.current_class_id)
+            && bp_ref.try_get_entry_mut(operand_name).is_some() {
+            let step_sign = if matches!(e.get_operator(), OperatorTag::Increment | OperatorTag::PostIncrement) { '+' } else { '-' };
+            self.report_culprit_error(&self.temp_token, format!("The operand of '{}' must be a local variable or parameter, not a field- Assign it like `{operand_name} = {operand_name} {step_sign} 1;` instead.", e.get_operator().as_symbol()).as_str());
+            return SemanticNote::Dud;
+        }
+
+        if let SemanticNote::DataValue(inner_type_id, ValueCategoryTag::Identity) = inner_info {
+            if matches!(inner_type_id, INTEGER_TYPE_ID_N | FLOATING_TYPE_ID_N | LONG_TYPE_ID_N) {
+                return SemanticNote::DataValue(inner_type_id, ValueCategoryTag::Temporary);
+            }
+
+            self.report_plain_error(format!("The operand of '{}' was not a numeric type (int, long, or float).", e.get_operator().as_symbol()).as_str());
+        } else {
+            self.report_plain_error(format!("The operand of '{}' was not a numeric variable.", e.get_operator().as_symbol()).as_str());
+        }
+
+        SemanticNote::Dud
+    }
+
+    /// NOTE: Warns about literal-only conditions, which are usually copy-paste mistakes. These never fail the analysis.
+    fn help_warn_const_condition(&self, check: &dyn Expr, stmt_name: &str) {
+        if let Some((check_flag, check_line_no)) = eval_const_condition(check, self.source_str.as_str()) {
+            self.report_line_warning(check_line_no, format!("The {stmt_name} condition is always {check_flag}.").as_str());
+        }
+    }
+
+    /// NOTE: An `else` after an always-true condition can never run, so it's flagged for removal. An always-false condition's dead `if` body is already covered by `help_warn_const_condition`.
+    fn help_warn_unreachable_else(&self, s: &If) {
+        if let Some(else_token) = s.get_else_token()
+            && let Some((true, _)) = eval_const_condition(s.get_check(), self.source_str.as_str()) {
+            self.report_line_warning(else_token.line_no, "The else branch is unreachable, as its if condition is always true- Consider removing it.");
+        }
+    }
+
+    /// NOTE: Flags an assignment like `a = a;` whose sides name the same variable, as it does nothing and usually means another name was intended.
+    fn help_warn_self_assignment(&self, e: &Binary) {
+        let named_lhs_opt = e.get_lhs().get_token_opt().filter(|lhs_token| e.get_lhs().get_operator() == OperatorTag::Noop && lhs_token.tag == TokenType::Identifier);
+        let named_rhs_opt = e.get_rhs().get_token_opt().filter(|rhs_token| e.get_rhs().get_operator() == OperatorTag::Noop && rhs_token.tag == TokenType::Identifier);
+
+        let (Some(lhs_token), Some(rhs_token)) = (named_lhs_opt, named_rhs_opt) else {
+            return;
+        };
+
+        let lhs_name = lhs_token.to_lexeme_str(self.source_str.as_str()).unwrap_or_default();
+
+        if lhs_name == rhs_token.to_lexeme_str(self.source_str.as_str()).unwrap_or_default() {
+            self.report_line_warning(lhs_token.line_no, format!("The assignment of '{lhs_name}' to itself does nothing- Consider removing it.").as_str());
+        }
+    }
+
+    fn report_line_warning(&self, line_no: usize, msg: &str) {
+        eprintln!("SemaWarning around Ln. {line_no}:\n{msg}");
+    }
+
     fn report_plain_error(&self, msg: &str) {
+        self.error_count.set(self.error_count.get() + 1);
         eprintln!("SemaError:\n{msg}");
     }
 
     fn report_culprit_error(&self, culprit: &Token, msg: &str) {
+        self.error_count.set(self.error_count.get() + 1);
         eprintln!("SemaError at [Ln {}, Col {}]:\nCulprit token: '{}'\n{}", culprit.line_no, culprit.col_no, culprit.to_lexeme_str(self.source_str.as_str()).unwrap_or("..."), msg);
     }
 
+    fn has_main_entry(&self) -> bool {
+        if let Some(global_scope_ref) = self.scopes.global_scope() {
+            return matches!(global_scope_ref.try_get_entry("main"), Some(SemanticNote::Callable(_, _, _)));
+        }
+
+        false
+    }
+
+    /// NOTE: Runnable programs need a top-level `main` function, while library modules don't, so only programs are checked for one. A missing `main` counts as a semantic error.
+    pub fn check_main_entry(&self) -> bool {
+        if self.has_main_entry() {
+            return true;
+        }
+
+        self.report_plain_error("No `main` function was found for this program- Declare `fun main(): int`, or pass `--lib` to compile a library module.");
+
+        false
+    }
+
+    pub fn get_error_count(&self) -> usize {
+        self.error_count.get()
+    }
+
     pub fn check_top_ast(&mut self, func_ast: &dyn Stmt) -> bool {
         func_ast.accept_visitor(self)
     }
@@ -236,6 +766,10 @@ impl<'evl2> ExprVisitor<'evl2, SemanticNote> for Analyzer{
         let source_copy = self.source_str.clone();
         let literal_lexeme = e.get_token().to_lexeme_str(source_copy.as_str()).unwrap_or("");
         let literal_tag = e.get_token().tag;
+        let is_callee = std::mem::take(&mut self.callee_flag);
+        let is_callback_arg = std::mem::take(&mut self.callback_arg_flag);
+        let callback_arg_shape = self.callback_arg_shape.take();
+        let is_member_name = std::mem::take(&mut self.member_name_flag);
         self.temp_token = *e.get_token();
 
         match literal_tag {
@@ -245,17 +779,67 @@ impl<'evl2> ExprVisitor<'evl2, SemanticNote> for Analyzer{
             TokenType::LiteralChar => {
                 SemanticNote::DataValue(CHAR_TYPE_ID_N, ValueCategoryTag::Temporary)
             },
+            // NOTE: a literal out of range keeps its type after its error, so a declaration using it is not reported twice.
             TokenType::LiteralInt => {
+                if literal_lexeme.parse::<i32>().is_err() {
+                    self.report_culprit_error(&self.temp_token, format!("Int literals must be at most {}, so use a long like `{literal_lexeme}L` instead.", i32::MAX).as_str());
+                }
+
                 SemanticNote::DataValue(INTEGER_TYPE_ID_N, ValueCategoryTag::Temporary)
             },
             TokenType::LiteralFloat => {
                 SemanticNote::DataValue(FLOATING_TYPE_ID_N, ValueCategoryTag::Temporary)
             },
+            TokenType::LiteralLong => {
+                if literal_lexeme.trim_end_matches('L').parse::<i64>().is_err() {
+                    self.report_culprit_error(&self.temp_token, format!("Long literals must be at most {}L.", i64::MAX).as_str());
+                }
+
+                SemanticNote::DataValue(LONG_TYPE_ID_N, ValueCategoryTag::Temporary)
+            },
             TokenType::LiteralVarchar => {
                 SemanticNote::DataValue(VARCHAR_TYPE_ID_N, ValueCategoryTag::Temporary)
             },
             TokenType::Identifier => {
-                self.lookup_name_info(literal_lexeme)
+                let name_info = self.lookup_name_info(literal_lexeme);
+
+                // NOTE: a plain name only finds members of the class whose body is checked, so even its private fields are accessible e.g `next` in `next.get()`.
+                if !is_member_name && !name_info.is_dud() {
+                    self.current_name_accessible = AccessFlag::Exposed;
+                }
+
+                if !is_callee && is_callback_arg && name_info.is_callable() {
+                    return self.check_callback_name(literal_lexeme, &name_info, callback_arg_shape);
+                }
+
+                if !is_callee && name_info.is_callable() {
+                    self.report_culprit_error(&self.temp_token, "Functions are not first-class; did you mean to call it?");
+                    return SemanticNote::Dud;
+                }
+
+                if !is_member_name && name_info.is_dud()
+                    && let NameResolution::Ambiguous(owner_names) = self.modules.resolve(&self.current_module, literal_lexeme) {
+                    let ambiguous_name_msg = format!(
+                        "The name '{literal_lexeme}' is declared by the modules {}, so it must be qualified e.g `{}.{literal_lexeme}`.",
+                        owner_names.join(" & "),
+                        owner_names[0]
+                    );
+                    self.report_culprit_error(&self.temp_token, ambiguous_name_msg.as_str());
+
+                    return SemanticNote::Dud;
+                }
+
+                // NOTE: undeclared callees are reported by `visit_call()` instead, and missing members by `visit_binary()`.
+                if !is_callee && !is_member_name && name_info.is_dud() {
+                    if let Some(class_name) = self.type_table.get(&self.current_class_id) {
+                        let missing_member_msg = format!("The name is neither declared nor a member of class {class_name}, whose fields & methods may be declared anywhere in its body.");
+                        self.report_culprit_error(&self.temp_token, missing_member_msg.as_str());
+                    } else {
+                        self.report_culprit_error(&self.temp_token, "The name is likely undeclared, did you declare <name> before?");
+                    }
+                }
+
+                name_info
             },
             _ => {
                 SemanticNote::Dud
@@ -265,11 +849,27 @@ impl<'evl2> ExprVisitor<'evl2, SemanticNote> for Analyzer{
 
     fn visit_call(&mut self, e: &Call) -> SemanticNote {
         let source_copy = self.source_str.clone();
+        self.callee_flag = true;
         let callee_info = e.get_callee().accept_visitor_sema(self);
+        self.callee_flag = false;
         let callee_token = self.temp_token;
 
+        // NOTE: a missing method like `obj.met()` was already reported by `visit_binary()`, and an ambiguous name by `visit_primitive()`.
         if callee_info.is_dud() {
-            self.report_culprit_error(&callee_token, "The callee name is likely undeclared, did you declare <name> before?");
+            let callee_lexeme_0 = callee_token.to_lexeme_str(source_copy.as_str()).unwrap_or("");
+
+            if e.get_callee().get_operator() == OperatorTag::Access || self.is_ambiguous_name(callee_lexeme_0) {
+                return SemanticNote::Dud;
+            }
+
+            if let Some(owner_name) = self.modules.find_hiding_owner(&self.current_module, callee_lexeme_0) {
+                let owner_desc = if owner_name.is_empty() { String::from("the main program") } else { format!("module '{owner_name}'") };
+                let private_fun_msg = format!("The function '{callee_lexeme_0}' is private, so only {owner_desc} may call it.");
+                self.report_culprit_error(&callee_token, private_fun_msg.as_str());
+            } else {
+                self.report_culprit_error(&callee_token, "The callee name is likely undeclared, did you declare <name> before?");
+            }
+
             return SemanticNote::Dud;
         }
 
@@ -284,7 +884,15 @@ impl<'evl2> ExprVisitor<'evl2, SemanticNote> for Analyzer{
             
             let callee_lexeme_1 = callee_token.to_lexeme_str(source_copy.as_str()).unwrap_or("...");
             let callable_arity = proc_or_ctor_info.2;
+
+            if self.is_private_ctor_call(callee_lexeme_1, proc_or_ctor_info.1) {
+                let private_ctor_msg = format!("The constructor of '{callee_lexeme_1}' is private, so only code within class '{callee_lexeme_1}' may call it e.g a static factory method.");
+                self.report_culprit_error(&callee_token, private_ctor_msg.as_str());
+
+                return SemanticNote::Dud;
+            }
             let passed_arity = e.get_args().len() as i32;
+            let mut first_arg_type_id = ANY_TYPE_ID_N;
             
             if passed_arity != callable_arity {
                 let msg_string = format!("For callee '{callee_lexeme_1}'- Expected {callable_arity} arguments instead of {passed_arity}.");
@@ -294,18 +902,35 @@ impl<'evl2> ExprVisitor<'evl2, SemanticNote> for Analyzer{
             }
             
             for arg_it in 0..passed_arity {
-                let temp_arg_type_id = e.get_args().get(arg_it as usize).unwrap().accept_visitor_sema(self);
+                let arg_expr = e.get_args().get(arg_it as usize).unwrap();
+                let expected_type_id = *proc_or_ctor_info.0.get(arg_it as usize).unwrap();
+
+                self.callback_arg_flag = expected_type_id == CALLBACK_TYPE_ID_N && arg_expr.get_operator() == OperatorTag::Noop;
+                self.callback_arg_shape = if self.callback_arg_flag && self.foreign_stub_names.contains(callee_lexeme_1) {
+                    self.native_callback_shapes.get(callee_lexeme_1).and_then(|shapes| shapes.get(arg_it as usize).cloned().flatten())
+                } else {
+                    None
+                };
+                let temp_arg_type_id = arg_expr.accept_visitor_sema(self);
+                self.callback_arg_flag = false;
+                self.callback_arg_shape = None;
 
                 match temp_arg_type_id {
-                    SemanticNote::DataValue(argv_type_id, _) => {
-                        let expected_type_id = *proc_or_ctor_info.0.get(arg_it as usize).unwrap();
-                        
+                    SemanticNote::DataValue(argv_type_id, _) | SemanticNote::ClassEntity(argv_type_id, _) => {
                         if expected_type_id != ANY_TYPE_ID_N && expected_type_id != argv_type_id {
-                            let mismatch_err_msg = format!("For argument #{arg_it}, a mismatched type was found. Please check the declaration of 'fun {callee_lexeme_1}'.");
+                            let mismatch_err_msg = format!(
+                                "For argument #{arg_it}, a mismatched type was found- Expected '{}' but found '{}'. Please check the declaration of 'fun {callee_lexeme_1}'.",
+                                self.type_table.get(&expected_type_id).map_or("?", |type_name| type_name.as_str()),
+                                self.type_table.get(&argv_type_id).map_or("?", |type_name| type_name.as_str())
+                            );
                             
                             self.report_culprit_error(&callee_token, mismatch_err_msg.as_str());
                             return SemanticNote::Dud;
                         }
+
+                        if arg_it == 0 {
+                            first_arg_type_id = argv_type_id;
+                        }
                     },
                     _ => {
                         let invalid_arg_msg = format!("For argument {arg_it}, an invalid type was found. Please check the declaration of '{callee_lexeme_1}'.");
@@ -316,7 +941,12 @@ impl<'evl2> ExprVisitor<'evl2, SemanticNote> for Analyzer{
                 }
             }
 
-            let callable_result_type_id = proc_or_ctor_info.1;
+            // NOTE: a native giving `any` hands back a value of its first argument's type e.g `clone(p)` gives the class of `p`.
+            let callable_result_type_id = if proc_or_ctor_info.1 == ANY_TYPE_ID_N && self.foreign_stub_names.contains(callee_lexeme_1) {
+                first_arg_type_id
+            } else {
+                proc_or_ctor_info.1
+            };
             
             SemanticNote::DataValue(callable_result_type_id, ValueCategoryTag::Temporary)
         } else {
@@ -334,14 +964,21 @@ impl<'evl2> ExprVisitor<'evl2, SemanticNote> for Analyzer{
             }
 
             for arg_it in 0..passed_arity {
-                let temp_arg_type_id = e.get_args().get(arg_it as usize).unwrap().accept_visitor_sema(self);
+                let arg_expr = e.get_args().get(arg_it as usize).unwrap();
+                let expected_type_id = *method_info.0.get(arg_it as usize).unwrap();
+
+                self.callback_arg_flag = expected_type_id == CALLBACK_TYPE_ID_N && arg_expr.get_operator() == OperatorTag::Noop;
+                let temp_arg_type_id = arg_expr.accept_visitor_sema(self);
+                self.callback_arg_flag = false;
 
                 match temp_arg_type_id {
-                    SemanticNote::DataValue(argv_type_id, _) => {
-                        let expected_type_id = *method_info.0.get(arg_it as usize).unwrap();
-                        
+                    SemanticNote::DataValue(argv_type_id, _) | SemanticNote::ClassEntity(argv_type_id, _) => {
                         if expected_type_id != ANY_TYPE_ID_N && expected_type_id != argv_type_id {
-                            let mismatch_err_msg = format!("For argument #{arg_it}, a mismatched type was found. Please check the declaration of 'met {callee_lexeme_2}'.");
+                            let mismatch_err_msg = format!(
+                                "For argument #{arg_it}, a mismatched type was found- Expected '{}' but found '{}'. Please check the declaration of 'met {callee_lexeme_2}'.",
+                                self.type_table.get(&expected_type_id).map_or("?", |type_name| type_name.as_str()),
+                                self.type_table.get(&argv_type_id).map_or("?", |type_name| type_name.as_str())
+                            );
                             
                             self.report_culprit_error(&callee_token, mismatch_err_msg.as_str());
                             return SemanticNote::Dud;
@@ -367,10 +1004,15 @@ impl<'evl2> ExprVisitor<'evl2, SemanticNote> for Analyzer{
 
     fn visit_unary(&mut self, e: &Unary) -> SemanticNote {
         let expr_op = e.get_operator();
+        self.callee_flag = false;
         let expr_inner_type = e.get_inner().accept_visitor_sema(self);
 
+        if matches!(expr_op, OperatorTag::Increment | OperatorTag::Decrement | OperatorTag::PostIncrement | OperatorTag::PostDecrement) {
+            return self.help_check_step_unary(e, expr_inner_type);
+        }
+
         let inner_result_info_opt = match expr_op {
-            OperatorTag::Negate => {
+            OperatorTag::Negate | OperatorTag::Not => {
                 expr_inner_type.try_unbox_data_value()
             },
             _ => {
@@ -379,17 +1021,26 @@ impl<'evl2> ExprVisitor<'evl2, SemanticNote> for Analyzer{
         };
 
         if inner_result_info_opt.is_none() {
-            self.report_plain_error("Invalid unary expression- Only arithmetic negations are allowed for now.");
+            self.report_plain_error("Invalid unary expression- Only arithmetic & logical negations are allowed for now.");
+            return SemanticNote::Dud;
+        }
+
+        if expr_op == OperatorTag::Not {
+            if let SemanticNote::DataValue(BOOLEAN_TYPE_ID_N, _) = expr_inner_type {
+                return SemanticNote::DataValue(BOOLEAN_TYPE_ID_N, ValueCategoryTag::Temporary);
+            }
+
+            self.report_plain_error("The logically negated value was not a bool.");
             return SemanticNote::Dud;
         }
 
         if let SemanticNote::DataValue(inner_type_id, _) = expr_inner_type {
             match inner_type_id {
-                1 | 2=> {
+                INTEGER_TYPE_ID_N | FLOATING_TYPE_ID_N | LONG_TYPE_ID_N => {
                     return SemanticNote::DataValue(inner_type_id, ValueCategoryTag::Temporary);
                 },
                 _ => {
-                    self.report_plain_error("The negated value was not a numeric type (int or float).");
+                    self.report_plain_error("The negated value was not a numeric type (int, long, or float).");
                 },
             }
         } else {
@@ -403,37 +1054,113 @@ impl<'evl2> ExprVisitor<'evl2, SemanticNote> for Analyzer{
     /// Support member accesses.
     fn visit_binary(&mut self, e: &Binary) -> SemanticNote {
         let expr_op = e.get_operator();
-        let lhs_info = e.get_lhs().accept_visitor_sema(self);
+
+        // NOTE: only the member name of a callee like `obj.met` can be a callable, never its object.
+        let is_callee_access = std::mem::take(&mut self.callee_flag) && expr_op == OperatorTag::Access;
+
+        if expr_op == OperatorTag::Access
+            && let Some(module_name) = self.find_accessed_module(e.get_lhs()) {
+            return self.check_module_access(&module_name, e.get_rhs(), is_callee_access);
+        }
+
+        let static_class_id_opt = if expr_op == OperatorTag::Access { self.lookup_class_name_id(e.get_lhs()) } else { None };
+
+        if expr_op == OperatorTag::Access
+            && static_class_id_opt.is_none()
+            && let Some(field_name) = self.find_unassigned_ctor_field(e.get_lhs()) {
+            let unassigned_field_msg = format!("Field '{field_name}' may not hold an instance yet, as the constructor hasn't assigned it on every path before this access.");
+            self.report_culprit_error(&e.get_lhs().get_token_opt().unwrap(), unassigned_field_msg.as_str());
+
+            return SemanticNote::Dud;
+        }
+
+        let lhs_info = if let Some(static_class_id) = static_class_id_opt {
+            self.current_name_accessible = AccessFlag::Exposed;
+            SemanticNote::ClassEntity(static_class_id, ValueCategoryTag::Temporary)
+        } else {
+            e.get_lhs().accept_visitor_sema(self)
+        };
         let lhs_accessibility = self.current_name_accessible;
         let expr_line_no = self.temp_token.line_no;
 
-        if expr_op == OperatorTag::Access && self.current_class_id == -1 {
-            self.set_current_class_id(
-                lhs_info.try_unbox_class_info_id().unwrap_or(-1)
-            );
+        // NOTE: a call giving an instance e.g `obj.next()` in `obj.next().get()` has its members accessed like a named instance's.
+        let lhs_info = match lhs_info {
+            SemanticNote::DataValue(type_id, value_group) if expr_op == OperatorTag::Access && self.class_blueprints.try_get_entry_mut(type_id).is_some() => {
+                SemanticNote::ClassEntity(type_id, value_group)
+            },
+            _ => lhs_info,
+        };
+
+        // NOTE: the member name must be found in the accessed object's class, and the enclosing class (if any) comes back afterwards.
+        let outer_class_id = self.current_class_id;
+
+        if expr_op == OperatorTag::Access && lhs_info.try_unbox_class_info_id().is_some() {
+            self.set_current_class_id(lhs_info.try_unbox_class_info_id().unwrap());
         }
 
+        self.callee_flag = is_callee_access;
+        self.member_name_flag = expr_op == OperatorTag::Access;
         let rhs_info = e.get_rhs().accept_visitor_sema(self);
+        self.callee_flag = false;
+        self.member_name_flag = false;
         let rhs_accessibility = self.current_name_accessible;
 
+        if expr_op == OperatorTag::Access {
+            let accessed_class_id = self.current_class_id;
+            self.set_current_class_id(outer_class_id);
+
+            if static_class_id_opt.is_some() && !rhs_info.is_dud() && !matches!(rhs_info, SemanticNote::Callable(_, _, _)) {
+                let class_name = self.type_table.get(&accessed_class_id).unwrap().as_str();
+                let bad_static_access_msg = format!("Only static methods of {class_name} can be accessed by its class name around Ln. {expr_line_no}");
+                self.report_plain_error(bad_static_access_msg.as_str());
+
+                return SemanticNote::Dud;
+            }
+
+            let class_name = if accessed_class_id != -1 {
+                self.type_table.get(&accessed_class_id).unwrap().as_str()
+            } else { "(unknown-type)" };
+
+            if rhs_info.is_dud() && accessed_class_id != -1 {
+                let member_name = self.temp_token.to_lexeme_str(self.source_str.as_str()).unwrap_or("...");
+                let missing_member_msg = format!("Class {class_name} has no member named '{member_name}' around Ln. {expr_line_no}");
+                self.report_plain_error(missing_member_msg.as_str());
+
+                return SemanticNote::Dud;
+            }
+
+            if rhs_info.is_dud() || lhs_accessibility == AccessFlag::Hidden || rhs_accessibility == AccessFlag::Hidden {
+                let bad_member_access_msg = format!("Cannot access member of {class_name} by name around Ln. {expr_line_no}");
+                self.report_plain_error(bad_member_access_msg.as_str());
+
+                return SemanticNote::Dud;
+            }
+
+            return rhs_info;
+        }
+
         if expr_op.is_homogeneously_typed() {
             if !check_binary_typing_homogeneously(&lhs_info, &rhs_info) {
+                // NOTE: chars never promote to their int codes, so comparing one with an int gets the explicit conversion suggested.
+                let compares_char_with_int = expr_op.is_comparison() && matches!(
+                    (&lhs_info, &rhs_info),
+                    (SemanticNote::DataValue(CHAR_TYPE_ID_N, _), SemanticNote::DataValue(INTEGER_TYPE_ID_N, _))
+                        | (SemanticNote::DataValue(INTEGER_TYPE_ID_N, _), SemanticNote::DataValue(CHAR_TYPE_ID_N, _))
+                );
+
+                if compares_char_with_int {
+                    let op_symbol = expr_op.as_symbol();
+                    let char_int_msg = format!("Cannot compare a char with an int by {op_symbol} around Ln. {expr_line_no}- Compare its code like `char_code(c) {op_symbol} 65` after `import convert;` instead.");
+                    self.report_plain_error(char_int_msg.as_str());
+
+                    return SemanticNote::Dud;
+                }
+
                 let mismatched_opers_msg = format!("Found mismatched types for {} expression around Ln. {}", expr_op.as_symbol(), expr_line_no);
                 self.report_plain_error(mismatched_opers_msg.as_str());
 
                 return SemanticNote::Dud;
             }
-        } else if expr_op == OperatorTag::Access {
-            return if rhs_info.is_dud() || lhs_accessibility == AccessFlag::Hidden || rhs_accessibility == AccessFlag::Hidden {
-                let class_type_id = self.current_class_id;
-                let class_name = if self.current_class_id != -1 {
-                    self.type_table.get(&class_type_id).unwrap().as_str()
-                } else { "(unknown-type)" };
-                let bad_member_access_msg = format!("Cannot access member of {class_name} by name around Ln. {expr_line_no}");
-                self.report_plain_error(bad_member_access_msg.as_str());
-
-                SemanticNote::Dud
-            } else { rhs_info };
         } else {
             let unsupported_operator_msg = format!("Unsupported operator found around Ln. {}: {}", expr_line_no, expr_op.as_symbol());
             self.report_plain_error(unsupported_operator_msg.as_str());
@@ -449,6 +1176,38 @@ impl<'evl2> ExprVisitor<'evl2, SemanticNote> for Analyzer{
 
                     return SemanticNote::Dud;
                 }
+
+                // NOTE: field writes only target named instances, as codegen has no slot for the object of a chain like `obj.get().field`.
+                let lhs_is_chained_field = e.get_lhs().as_binary().is_some_and(|lhs_access| {
+                    lhs_access.get_operator() == OperatorTag::Access && lhs_access.get_lhs().get_operator() != OperatorTag::Noop
+                });
+
+                if lhs_is_chained_field {
+                    let bad_chain_msg = format!("Invalid assignment at Ln. {expr_line_no}- a field can only be assigned through a named instance, not a chained access like `obj.get().field`.");
+                    self.report_plain_error(bad_chain_msg.as_str());
+
+                    return SemanticNote::Dud;
+                }
+
+                // NOTE: an inner `s[i] = c` gives the varchar set native's bool flag instead of the char, so it can't pass a value along a chain.
+                let rhs_sets_index = e.get_rhs().as_binary().is_some_and(|inner_assign| {
+                    inner_assign.get_operator() == OperatorTag::Assign && inner_assign.get_lhs().as_index().is_some()
+                });
+
+                if rhs_sets_index {
+                    let bad_chain_msg = format!("Invalid assignment at Ln. {expr_line_no}- a chained assignment cannot pass through an indexed char like `s[i] = c`.");
+                    self.report_plain_error(bad_chain_msg.as_str());
+
+                    return SemanticNote::Dud;
+                }
+
+                self.help_warn_self_assignment(e);
+
+                if self.ctor_body_flag
+                    && let Some(lhs_token) = e.get_lhs().get_token_opt().filter(|_| e.get_lhs().get_operator() == OperatorTag::Noop) {
+                    let lhs_name = lhs_token.to_lexeme_str(self.source_str.as_str()).unwrap_or_default().to_string();
+                    self.ctor_assigned_field_names.insert(lhs_name);
+                }
             } else {
                 let bad_binary_op_msg = format!("Unsupported operator {} for binary expr. at Ln. {}", expr_op.as_symbol(), expr_line_no);
                 self.report_plain_error(bad_binary_op_msg.as_str());
@@ -457,15 +1216,50 @@ impl<'evl2> ExprVisitor<'evl2, SemanticNote> for Analyzer{
             }
         }
 
+        if matches!(expr_op, OperatorTag::Equality | OperatorTag::Inequality) && !self.check_equals_method(&lhs_info, expr_op.as_symbol(), expr_line_no) {
+            return SemanticNote::Dud;
+        }
+
+        if expr_op.is_comparison() {
+            return SemanticNote::DataValue(BOOLEAN_TYPE_ID_N, ValueCategoryTag::Temporary);
+        }
+
         let (unboxed_type_id, unboxed_value_group) = lhs_info.try_unbox_data_value().unwrap_or((-1, ValueCategoryTag::Unknown));
 
         SemanticNote::DataValue(unboxed_type_id, unboxed_value_group)
     }
+
+    /// NOTE: Indexing like `s[i]` is sugar for the varchar intrinsics, so it gives a char which is only assignable if the varchar itself is e.g a variable.
+    fn visit_index(&mut self, e: &Index) -> SemanticNote {
+        self.callee_flag = false;
+        let target_info = e.get_target().accept_visitor_sema(self);
+        let index_info = e.get_index().accept_visitor_sema(self);
+        let bracket_token = *e.get_bracket_token();
+
+        let Some((_, target_value_group)) = target_info.try_unbox_data_value().filter(|(type_id, _)| *type_id == VARCHAR_TYPE_ID_N) else {
+            self.report_culprit_error(&bracket_token, "Only varchars can be indexed by '[]'.");
+            return SemanticNote::Dud;
+        };
+
+        if index_info.try_unbox_data_value().is_none_or(|(index_type_id, _)| index_type_id != INTEGER_TYPE_ID_N) {
+            self.report_culprit_error(&bracket_token, "A varchar index must be an 'int' position.");
+            return SemanticNote::Dud;
+        }
+
+        let char_value_group = if target_value_group == ValueCategoryTag::Identity { ValueCategoryTag::Identity } else { ValueCategoryTag::Temporary };
+
+        SemanticNote::DataValue(CHAR_TYPE_ID_N, char_value_group)
+    }
 }
 
 impl StmtVisitor<bool> for Analyzer {
-    #[allow(unused_variables)]
     fn visit_import(&mut self, s: &Import) -> bool {
+        if self.prepass_flag {
+            let target_name = s.get_target().to_lexeme_str(self.source_str.as_str()).unwrap_or("").to_string();
+
+            self.modules.record_import(&self.current_module, target_name.as_str());
+        }
+
         true
     }
 
@@ -483,25 +1277,85 @@ impl StmtVisitor<bool> for Analyzer {
         let mut stub_param_types = Vec::<i32>::new();
         let stub_arity = s.get_params().len() as i32;
 
+        // NOTE: stub params are only type info since there's no body, so their names must not leak into any real scope.
         for param in s.get_params() {
             let param_type_name = param.get_typing().typename();
             let param_type_id = self.record_type(param_type_name.clone());
 
-            self.record_name_info(
-                param.get_name_token().to_lexeme_str(source_copy.as_str()).unwrap(),
-                SemanticNote::DataValue(param_type_id, ValueCategoryTag::Identity),
-                RecordInfoMode::Local
-            );
-            
             stub_param_types.push(param_type_id);
         }
 
-        if !self.record_name_info(
-            stub_name,
-            SemanticNote::Callable(stub_param_types, stub_ret_type_id, stub_arity), RecordInfoMode::Global
-        ) {
+        if let Some((native_param_types, native_ret_type_id)) = self.native_signatures.get(stub_name)
+            && (*native_param_types != stub_param_types || *native_ret_type_id != stub_ret_type_id) {
+            let stub_mismatch_msg = format!(
+                "Foreign stub '{stub_name}' does not match its native's signature- Expected '{}' but found '{}'.",
+                self.help_format_signature(native_param_types, *native_ret_type_id),
+                self.help_format_signature(&stub_param_types, stub_ret_type_id)
+            );
+            self.report_culprit_error(s.get_name_token(), stub_mismatch_msg.as_str());
+
+            return false;
+        }
+
+        let stub_note = SemanticNote::Callable(stub_param_types, stub_ret_type_id, stub_arity);
+
+        // NOTE: natives are global, so a stub keeps its plain name while its qualified name e.g `math.float_eq` is only an alias.
+        if !self.current_module.is_empty() {
+            let qualified_stub_name = ModuleTable::qualify(&self.current_module, stub_name);
+            self.scopes.global_scope_mut().unwrap().try_set_entry(qualified_stub_name.as_str(), stub_note.clone(), Some(s.get_name_token()));
+        }
+
+        if !self.record_name_info(stub_name, stub_note, RecordInfoMode::Global, Some(s.get_name_token())) {
             let redef_stub_msg = format!("Invalid redeclaration of foreign stub '{stub_name}'");
-            self.report_plain_error(redef_stub_msg.as_str());
+            self.report_redeclaration_error(s.get_name_token(), RecordInfoMode::Global, redef_stub_msg.as_str());
+
+            return false;
+        }
+
+        self.foreign_stub_names.insert(stub_name.to_string());
+
+        true
+    }
+
+    fn visit_const_decl(&mut self, s: &ConstDecl) -> bool {
+        if !self.prepass_flag {
+            return true;
+        }
+
+        let source_copy = self.source_str.clone();
+        let const_name_token_ref = s.get_name_token();
+        let const_name = const_name_token_ref.to_lexeme_str(source_copy.as_str()).unwrap_or("");
+
+        let const_type_name = s.get_typing().typename();
+        let const_type_id = self.record_type(const_type_name.clone());
+
+        // NOTE: the IR emitter folds the same initializer again for every use, so only literal-only expressions foldable by `eval_const_expr` are allowed.
+        let Some(const_value) = eval_const_expr(s.get_init_expr(), source_copy.as_str()) else {
+            let bad_init_msg = format!("Constant '{const_name}' must be initialized by only int, long, or bool literals e.g `60 * 60`.");
+            self.report_culprit_error(const_name_token_ref, bad_init_msg.as_str());
+
+            return false;
+        };
+
+        let init_type_id = match const_value {
+            Value::Bool(_) => BOOLEAN_TYPE_ID_N,
+            Value::Int(_) => INTEGER_TYPE_ID_N,
+            Value::Long(_) => LONG_TYPE_ID_N,
+            _ => -1,
+        };
+
+        if const_type_id != init_type_id {
+            let init_type_name = self.type_table.get(&init_type_id).map_or("?", |type_name| type_name.as_str());
+            let bad_type_msg = format!("Cannot set {const_type_name} constant '{const_name}' to {init_type_name} value.");
+            self.report_culprit_error(const_name_token_ref, bad_type_msg.as_str());
+
+            return false;
+        }
+
+        // NOTE: a constant is a temporary value wherever it's used, so it can never be assigned to.
+        if !self.record_module_global(const_name, SemanticNote::DataValue(const_type_id, ValueCategoryTag::Temporary), const_name_token_ref) {
+            let redef_const_msg = format!("Invalid redeclaration of constant '{const_name}'");
+            self.report_redeclaration_error(const_name_token_ref, RecordInfoMode::Global, redef_const_msg.as_str());
 
             return false;
         }
@@ -524,10 +1378,13 @@ impl StmtVisitor<bool> for Analyzer {
                 let param_type_name = param.get_typing().typename();
                 let param_type_id = self.record_type(param_type_name.clone());
 
+                let param_note = self.make_identity_note(param_type_id);
+
                 self.record_name_info(
                     param.get_name_token().to_lexeme_str(source_copy.as_str()).unwrap(),
-                    SemanticNote::DataValue(param_type_id, ValueCategoryTag::Identity),
-                    RecordInfoMode::Local
+                    param_note,
+                    RecordInfoMode::Local,
+                    Some(param.get_name_token())
                 );
             }
 
@@ -547,17 +1404,22 @@ impl StmtVisitor<bool> for Analyzer {
                 fun_param_types.push(param_type_id);
             }
 
-            if !self.record_name_info(fun_name, SemanticNote::Callable(fun_param_types, ret_type_id, fun_arity), RecordInfoMode::Global) {
+            if !self.record_module_global(fun_name, SemanticNote::Callable(fun_param_types, ret_type_id, fun_arity), s.get_name_token()) {
                 let redef_fun_msg = format!("Invalid redeclaration of procedure '{fun_name}'");
-                self.report_plain_error(redef_fun_msg.as_str());
+                self.report_redeclaration_error(s.get_name_token(), RecordInfoMode::Global, redef_fun_msg.as_str());
 
                 return false;
             }
+
+            if s.get_access() == AccessFlag::Hidden {
+                self.modules.record_private(&self.current_module, fun_name);
+            }
         }
 
         true
     }
 
+    /// NOTE: Fields of the enclosing class's own type are allowed since instances are reference types (a field only holds a `HeapRef`). Value-type cycles must be rejected here if value semantics for classes are ever added.
     fn visit_field_decl(&mut self, s: &FieldDecl) -> bool {
         if self.prepass_flag {
             let src_copy = self.source_str.clone();
@@ -565,8 +1427,13 @@ impl StmtVisitor<bool> for Analyzer {
             let field_type_id = self.record_type(field_typename.clone());
             let field_name_str = s.get_name_token().to_lexeme_str(&src_copy).unwrap_or("");
 
-            println!("recording field '{field_name_str}'...");
-            self.record_name_info(field_name_str, SemanticNote::DataValue(field_type_id, ValueCategoryTag::Identity), RecordInfoMode::Member);
+            log_verbose!(self.log_level, "recording field '{field_name_str}'...");
+
+            if !self.record_name_info(field_name_str, SemanticNote::DataValue(field_type_id, ValueCategoryTag::Identity), RecordInfoMode::Member, Some(s.get_name_token())) {
+                self.report_member_collision(Some(s.get_name_token()), field_name_str, "field");
+
+                return false;
+            }
         }
 
         true
@@ -595,22 +1462,26 @@ impl StmtVisitor<bool> for Analyzer {
                 ctor_param_type_ids.push(param_type_id);
             }
 
-            if !self.record_class_member_info(ctor_class_id, ctor_class_name.as_str(), ctor_access_mod, SemanticNote::Constructor(ctor_param_type_ids.clone(), ctor_class_id, ctor_arity)) {
-                let duped_ctor_msg = format!("Cannot redeclare the '{}' constructor", ctor_class_name.as_str());
-                self.report_plain_error(&duped_ctor_msg);
+            if !self.record_class_member_info(ctor_class_id, ctor_class_name.as_str(), ctor_access_mod, SemanticNote::Constructor(ctor_param_type_ids.clone(), ctor_class_id, ctor_arity), None) {
+                self.report_member_collision(None, ctor_class_name.as_str(), "constructor");
 
                 return false;
             }
 
-            if ctor_access_mod == AccessFlag::Hidden || !self.record_name_info(ctor_class_name.as_str(), SemanticNote::Callable(ctor_param_type_ids, ctor_class_id, ctor_arity), RecordInfoMode::Global) {
-                let top_ctor_decl_fail_msg = format!("Failed to record constructor at top-level for class '{}'\n\tNote: constructors must be public.", ctor_class_name.as_str());
+            if !self.record_name_info(ctor_class_name.as_str(), SemanticNote::Callable(ctor_param_type_ids, ctor_class_id, ctor_arity), RecordInfoMode::Global, None) {
+                let top_ctor_decl_fail_msg = format!("Failed to record constructor at top-level for class '{}'", ctor_class_name.as_str());
                 self.report_plain_error(&top_ctor_decl_fail_msg);
 
                 return false;
             }
+
+            if ctor_access_mod == AccessFlag::Hidden {
+                self.private_ctor_class_ids.insert(ctor_class_id);
+            }
         } else {
             let source_copy = self.source_str.clone();
             self.scopes.enter_scope(ctor_class_name.as_str());
+            self.ctor_assigned_field_names.clear();
 
             // println!("for class of type ID {}... processing ctor", self.current_class_id);
 
@@ -618,19 +1489,35 @@ impl StmtVisitor<bool> for Analyzer {
                 let param_type_name = param.get_typing().typename();
                 let param_type_id = self.record_type(param_type_name.clone());
 
+                let param_note = self.make_identity_note(param_type_id);
+                let param_name = param.get_name_token().to_lexeme_str(source_copy.as_str()).unwrap();
+
+                if param.binds_field() && !self.check_field_binding(param_name, param_type_id) {
+                    let bad_binding_msg = format!("Constructor parameter '{param_name}' is marked with 'let', but class '{ctor_class_name}' has no field '{param_name}' of type '{param_type_name}'.");
+                    self.report_culprit_error(param.get_name_token(), bad_binding_msg.as_str());
+                    self.scopes.leave_scope();
+
+                    return false;
+                }
+
+                if param.binds_field() {
+                    self.ctor_assigned_field_names.insert(param_name.to_string());
+                }
+
                 self.record_name_info(
-                    param.get_name_token().to_lexeme_str(source_copy.as_str()).unwrap(),
-                    SemanticNote::DataValue(param_type_id, ValueCategoryTag::Identity),
-                    RecordInfoMode::Local
+                    param_name,
+                    param_note,
+                    RecordInfoMode::Local,
+                    Some(param.get_name_token())
                 );
             }
 
-            if !s.get_body().accept_visitor(self) {
-                self.scopes.leave_scope();
-                return false;
-            }
-
+            self.ctor_body_flag = true;
+            let body_ok = s.get_body().accept_visitor(self);
+            self.ctor_body_flag = false;
             self.scopes.leave_scope();
+
+            return body_ok;
         }
 
         true
@@ -645,6 +1532,13 @@ impl StmtVisitor<bool> for Analyzer {
         let met_arity = s.get_params().len() as i32;
 
         if !self.prepass_flag {
+            // NOTE: static methods have no instance, so their bodies are checked like free functions without the class's members in view.
+            let met_class_id = self.current_class_id;
+
+            if s.is_static() {
+                self.set_current_class_id(-1);
+            }
+
             self.scopes.enter_scope(met_name);
             // println!("for class of type ID {}... processing method", self.current_class_id);
 
@@ -652,19 +1546,24 @@ impl StmtVisitor<bool> for Analyzer {
                 let param_type_name = param.get_typing().typename();
                 let param_type_id = self.record_type(param_type_name.clone());
 
+                let param_note = self.make_identity_note(param_type_id);
+
                 self.record_name_info(
                     param.get_name_token().to_lexeme_str(source_copy.as_str()).unwrap(),
-                    SemanticNote::DataValue(param_type_id, ValueCategoryTag::Identity),
-                    RecordInfoMode::Local
+                    param_note,
+                    RecordInfoMode::Local,
+                    Some(param.get_name_token())
                 );
             }
 
-            if !s.get_body().accept_visitor(self) {
-                self.scopes.leave_scope();
-                return false;
-            }
+            let met_body_ok = s.get_body().accept_visitor(self);
 
             self.scopes.leave_scope();
+            self.set_current_class_id(met_class_id);
+
+            if !met_body_ok {
+                return false;
+            }
         } else {
             let mut met_param_types = Vec::<i32>::new();
 
@@ -675,9 +1574,14 @@ impl StmtVisitor<bool> for Analyzer {
                 met_param_types.push(param_type_id);
             }
 
-            if !self.record_name_info(met_name, SemanticNote::Method(met_param_types, met_ret_type_id, met_arity, self.current_class_id), RecordInfoMode::Member) {
-                let redef_fun_msg = format!("Invalid redeclaration of method '{met_name}'");
-                self.report_plain_error(redef_fun_msg.as_str());
+            let met_note = if s.is_static() {
+                SemanticNote::Callable(met_param_types, met_ret_type_id, met_arity)
+            } else {
+                SemanticNote::Method(met_param_types, met_ret_type_id, met_arity, self.current_class_id)
+            };
+
+            if !self.record_name_info(met_name, met_note, RecordInfoMode::Member, Some(s.get_name_token())) {
+                self.report_member_collision(Some(s.get_name_token()), met_name, "method");
 
                 return false;
             }
@@ -701,40 +1605,46 @@ impl StmtVisitor<bool> for Analyzer {
         } else {
             self.set_preprocess_decls_flag();
             self.set_current_class_id(class_type_id);
+            self.enclosing_class_id = class_type_id;
+
+            let mut members_ok = true;
 
             for (member_stmt, member_mod) in s.get_members() {
                 self.update_current_class_mod(*member_mod);
 
-                if !member_stmt.as_ref().accept_visitor(self) {
-                    return false;
-                }
+                members_ok &= member_stmt.as_ref().accept_visitor(self);
             }
 
             self.clear_preprocess_decls_flag();
             // println!("processing class of type ID {class_type_id}...");
 
-            for (member_stmt, member_mod) in s.get_members() {
-                self.update_current_class_mod(*member_mod);
+            // NOTE: member bodies are only checked once all members are recorded, as a missing member would only cause follow-up errors.
+            if members_ok {
+                for (member_stmt, member_mod) in s.get_members() {
+                    self.update_current_class_mod(*member_mod);
 
-                if !member_stmt.as_ref().accept_visitor(self) {
-                    return false;
+                    members_ok &= member_stmt.as_ref().accept_visitor(self);
                 }
             }
 
             self.set_current_class_id(-1);
+            self.enclosing_class_id = -1;
+
+            return members_ok;
         }
 
         true
     }
 
+    /// NOTE: Every statement is checked even after one fails, so all of a block's errors are reported in one run.
     fn visit_block(&mut self, s: &Block) -> bool {
+        let mut block_ok = true;
+
         for stmt in s.get_items() {
-            if !stmt.accept_visitor(self) {
-                return false;
-            }
+            block_ok &= stmt.accept_visitor(self);
         }
 
-        true
+        block_ok
     }
 
     fn visit_variable_decl(&mut self, s: &VariableDecl) -> bool {
@@ -745,35 +1655,65 @@ impl StmtVisitor<bool> for Analyzer {
 
         let var_type_name = s.get_typing().typename();
         let var_type_id = self.record_type(var_type_name.clone());
-        
+
+        if self.is_shadowing_global_callable(var_name_lexeme) {
+            let shadow_fun_msg = format!("Variable '{var_name_lexeme}' cannot shadow the global function of the same name.");
+            self.report_culprit_error(var_name_token_ref, shadow_fun_msg.as_str());
+
+            return false;
+        }
+
+        if self.is_shadowing_global_const(var_name_lexeme) {
+            let shadow_const_msg = format!("Variable '{var_name_lexeme}' cannot shadow the module constant of the same name.");
+            self.report_culprit_error(var_name_token_ref, shadow_const_msg.as_str());
+
+            return false;
+        }
+
         if self.class_blueprints.try_get_entry_mut(var_type_id).is_none() {
             if !self.record_name_info(
                 var_name_lexeme,
                 SemanticNote::DataValue(var_type_id, ValueCategoryTag::Identity),
-                RecordInfoMode::Local
+                RecordInfoMode::Local,
+                Some(var_name_token_ref)
             ) {
                 let redef_var_msg = format!("Invalid redeclaration of non-instance variable '{var_name_lexeme}'");
-                self.report_plain_error(redef_var_msg.as_str());
+                self.report_redeclaration_error(var_name_token_ref, RecordInfoMode::Local, redef_var_msg.as_str());
                 
                 return false;
             }
         } else if !self.record_name_info(
             var_name_lexeme,
             SemanticNote::ClassEntity(var_type_id, ValueCategoryTag::Identity),
-            RecordInfoMode::Local
+            RecordInfoMode::Local,
+            Some(var_name_token_ref)
         ) {
             let redef_var_msg = format!("Invalid redeclaration of '{}' instance variable '{var_name_lexeme}'", var_type_name.as_str());
-            self.report_plain_error(redef_var_msg.as_str());
+            self.report_redeclaration_error(var_name_token_ref, RecordInfoMode::Local, redef_var_msg.as_str());
 
             return false;
         }
 
         let init_info = s.get_init_expr().accept_visitor_sema(self);
 
-        let init_type_id = if let SemanticNote::DataValue(type_id, _) = init_info {
+        let init_type_id = if let SemanticNote::DataValue(type_id, _) | SemanticNote::ClassEntity(type_id, _) = init_info {
             type_id
         } else { -1 };
 
+        // NOTE: ints & floats never coerce implicitly, so suggest the explicit conversion for this common mistake.
+        let numeric_cast_opt = match (var_type_id, init_type_id) {
+            (FLOATING_TYPE_ID_N, INTEGER_TYPE_ID_N) => Some(("an int", "float", "to_float")),
+            (INTEGER_TYPE_ID_N, FLOATING_TYPE_ID_N) => Some(("a float", "int", "to_int")),
+            _ => None,
+        };
+
+        if let Some((from_type_name, to_type_name, cast_name)) = numeric_cast_opt {
+            let bad_numeric_msg = format!("Cannot set {to_type_name} variable '{var_name_lexeme}' at Ln. {var_name_line_no} to {from_type_name} value- Use an explicit conversion like `{cast_name}(...)` after `import convert;`.");
+            self.report_culprit_error(var_name_token_ref, bad_numeric_msg.as_str());
+
+            return false;
+        }
+
         if var_type_id != init_type_id {
             let bad_rhs_msg = format!("Cannot set variable '{var_name_lexeme}' at Ln. {var_name_line_no} to the RHS expression- The RHS value type was mismatched (type-id {init_type_id}).");
             self.report_culprit_error(var_name_token_ref, bad_rhs_msg.as_str());
@@ -787,41 +1727,94 @@ impl StmtVisitor<bool> for Analyzer {
     /// # TODO
     /// Add a check for requiring bool condition types.
     fn visit_if(&mut self, s: &If) -> bool {
-        if s.get_check().accept_visitor_sema(self).is_dud() {
-            return false;
-        }
+        let check_ok = !s.get_check().accept_visitor_sema(self).is_dud();
 
-        if !s.get_truthy_body().accept_visitor(self) {
-            return false;
+        if check_ok {
+            self.help_warn_const_condition(s.get_check(), "if");
+            self.help_warn_unreachable_else(s);
         }
 
-        if !s.get_falsy_body().accept_visitor(self) {
-            return false;
-        }
+        // NOTE: only the fields assigned by both branches are surely assigned after the if.
+        let pre_if_field_names = self.ctor_assigned_field_names.clone();
+        let truthy_ok = s.get_truthy_body().accept_visitor(self);
+        let truthy_field_names = std::mem::replace(&mut self.ctor_assigned_field_names, pre_if_field_names);
+        let falsy_ok = s.get_falsy_body().accept_visitor(self);
+        self.ctor_assigned_field_names.retain(|field_name| truthy_field_names.contains(field_name));
 
-        true
+        check_ok && truthy_ok && falsy_ok
     }
 
     fn visit_while(&mut self, s: &While) -> bool {
-        if s.get_check().accept_visitor_sema(self).is_dud() {
-            return false;
+        let check_ok = !s.get_check().accept_visitor_sema(self).is_dud();
+
+        if check_ok {
+            self.help_warn_const_condition(s.get_check(), "while");
         }
 
-        if !s.get_body().accept_visitor(self) {
+        // NOTE: a loop body may run zero times, so its field assignments don't count after the loop.
+        let pre_loop_field_names = self.ctor_assigned_field_names.clone();
+        let body_ok = s.get_body().accept_visitor(self);
+        self.ctor_assigned_field_names = pre_loop_field_names;
+
+        check_ok && body_ok
+    }
+
+    /// NOTE: The loop char is a read-only copy in its own scope, which can't shadow any visible name as outer scopes are searched first.
+    fn visit_foreach(&mut self, s: &Foreach) -> bool {
+        let source_copy = self.source_str.clone();
+        let item_token_ref = s.get_item_token();
+        let item_name = item_token_ref.to_lexeme_str(source_copy.as_str()).unwrap_or("");
+
+        let target_info = s.get_target().accept_visitor_sema(self);
+        let mut target_ok = !target_info.is_dud();
+
+        if target_ok && !matches!(target_info, SemanticNote::DataValue(VARCHAR_TYPE_ID_N, _)) {
+            self.report_culprit_error(item_token_ref, "A foreach loop can only iterate over a varchar.");
+            target_ok = false;
+        }
+
+        if !self.lookup_name_info(item_name).is_dud() {
+            let shadow_item_msg = format!("Foreach variable '{item_name}' cannot shadow a visible name- Rename the loop variable.");
+            self.report_culprit_error(item_token_ref, shadow_item_msg.as_str());
+
             return false;
         }
 
-        true
+        self.scopes.enter_scope("foreach");
+        self.record_name_info(
+            item_name,
+            SemanticNote::DataValue(CHAR_TYPE_ID_N, ValueCategoryTag::Temporary),
+            RecordInfoMode::Local,
+            Some(item_token_ref)
+        );
+
+        let pre_loop_field_names = self.ctor_assigned_field_names.clone();
+        let body_ok = s.get_body().accept_visitor(self);
+        self.ctor_assigned_field_names = pre_loop_field_names;
+
+        self.scopes.leave_scope();
+
+        target_ok && body_ok
     }
 
     /// # TODO
     /// Add checks for return types against their parent function return type.
+    /// NOTE: A constructor implicitly gives its new instance, so it may only return early by a bare `return;` e.g after validating its arguments. Other procedures must always return a value.
     fn visit_return(&mut self, s: &Return) -> bool {
-        if s.get_result().accept_visitor_sema(self).is_dud() {
-            return false;
-        }
+        match (s.get_result(), self.ctor_body_flag) {
+            (Some(_), true) => {
+                self.report_culprit_error(s.get_return_token(), "A constructor cannot return a value, as it implicitly gives its new instance- Use a bare `return;` instead.");
 
-        true
+                false
+            },
+            (None, false) => {
+                self.report_culprit_error(s.get_return_token(), "Only constructors may use a bare `return;`, so functions and methods must return a value.");
+
+                false
+            },
+            (Some(result), false) => !result.accept_visitor_sema(self).is_dud(),
+            (None, true) => true,
+        }
     }
 
     fn visit_expr_stmt(&mut self, s: &ExprStmt) -> bool {
@@ -1,11 +1,15 @@
 use std::collections::HashMap;
 
+use crate::frontend::token::Token;
 use crate::semantics::scope::SemanticNote;
 use crate::semantics::types::AccessFlag;
 
 pub struct ClassMember {
     pub note: SemanticNote,
     pub access_mod: AccessFlag,
+
+    /// INFO: the declaring name token, which constructors lack since they're named by their class.
+    pub site: Option<Token>,
 }
 
 /**
@@ -37,8 +41,22 @@ impl ClassBlueprint {
         None
     }
 
+    /// NOTE: Gives the members sorted by name, so dumps of the same class always read alike.
+    pub fn sorted_entries(&self) -> Vec<(&String, &ClassMember)> {
+        let mut entries: Vec<(&String, &ClassMember)> = self.entries.iter().collect();
+        entries.sort_by_key(|(name, _)| *name);
+
+        entries
+    }
+
+    /// NOTE: A duplicate member keeps the original entry, so later lookups & diagnostics still see the first declaration.
     pub fn try_set_entry(&mut self, name_view: &str, note: ClassMember) -> bool {
-        self.entries.insert(String::from(name_view), note).is_none()
+        if self.entries.contains_key(name_view) {
+            return false;
+        }
+
+        self.entries.insert(String::from(name_view), note);
+        true
     }
 }
 
@@ -59,4 +77,8 @@ impl BlueprintTable {
     pub fn try_set_entry(&mut self, class_id: i32, blueprint: ClassBlueprint) -> bool {
         self.blueprints.insert(class_id, blueprint).is_none()
     }
+
+    pub fn try_get_entry(&self, class_id: i32) -> Option<&ClassBlueprint> {
+        self.blueprints.get(&class_id)
+    }
 }
@@ -1,4 +1,5 @@
 pub mod types;
 pub mod scope;
 pub mod blueprint;
+pub mod modules;
 pub mod analyzer;
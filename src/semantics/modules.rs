@@ -0,0 +1,111 @@
+use std::collections::{HashMap, HashSet};
+
+/**
+ * ### ABOUT
+ * Tracks which module declares each top-level function & constant, so each module gets its own namespace. The main program is the module named `""` whose names stay plain, while a library module like `math` declares qualified names like `math.abs`.
+ * ### RULES
+ * 1. A qualified name like `math.abs` needs its module to be imported by the module using it.
+ * 2. A plain name resolves to the using module's own declaration first, then the main program's, then the one of the only module declaring it.
+ * 3. A plain name declared by several other modules is ambiguous, so it must be qualified.
+ * 4. A `private` function is only visible to its own module, so other modules neither resolve nor collide with it.
+ * ### CAVEATS
+ * Classes & foreign stubs stay global, as types and natives are shared by the whole program. Foreign stubs may still be qualified by their module.
+ */
+#[derive(Default)]
+pub struct ModuleTable {
+    /// NOTE: maps each module's name to the names of the modules it imports.
+    imports: HashMap<String, HashSet<String>>,
+
+    /// NOTE: maps each plain name to the modules declaring it in declaration order.
+    owners: HashMap<String, Vec<String>>,
+
+    /// NOTE: holds the global names of `private` functions e.g `math.pick`, which only their own module may use.
+    private_keys: HashSet<String>,
+}
+
+pub enum NameResolution {
+    /// NOTE: holds the global name of the found declaration e.g `math.abs`, or `main` for the main program's.
+    Found(String),
+    /// NOTE: holds the names of every module declaring the name.
+    Ambiguous(Vec<String>),
+    /// NOTE: no module declares the name, so it may still be a local, class, or foreign stub.
+    Unowned,
+}
+
+impl ModuleTable {
+    pub fn qualify(module_name: &str, name: &str) -> String {
+        if module_name.is_empty() {
+            return name.to_string();
+        }
+
+        format!("{module_name}.{name}")
+    }
+
+    pub fn record_import(&mut self, module_name: &str, target_name: &str) {
+        self.imports.entry(module_name.to_string()).or_default().insert(target_name.to_string());
+    }
+
+    pub fn is_imported_by(&self, module_name: &str, target_name: &str) -> bool {
+        self.imports.get(module_name).is_some_and(|target_names| target_names.contains(target_name))
+    }
+
+    /// NOTE: Checks if any module imports the given one, which makes it a module of this program.
+    pub fn is_module_name(&self, name: &str) -> bool {
+        self.imports.values().any(|target_names| target_names.contains(name))
+    }
+
+    pub fn record_owner(&mut self, module_name: &str, name: &str) {
+        self.owners.entry(name.to_string()).or_default().push(module_name.to_string());
+    }
+
+    pub fn record_private(&mut self, module_name: &str, name: &str) {
+        self.private_keys.insert(Self::qualify(module_name, name));
+    }
+
+    /// NOTE: Checks if a declaration of the given module is private to it, which hides it from every other module.
+    pub fn is_hidden_from(&self, module_name: &str, owner_name: &str, name: &str) -> bool {
+        module_name != owner_name && self.private_keys.contains(&Self::qualify(owner_name, name))
+    }
+
+    /// NOTE: Finds the module keeping a plain name private, for diagnostics about names the using module can't see.
+    pub fn find_hiding_owner(&self, module_name: &str, name: &str) -> Option<&str> {
+        self.owners.get(name)?
+            .iter()
+            .find(|owner_name| self.is_hidden_from(module_name, owner_name, name))
+            .map(String::as_str)
+    }
+
+    pub fn resolve(&self, module_name: &str, name: &str) -> NameResolution {
+        let Some(all_owner_names) = self.owners.get(name) else {
+            return NameResolution::Unowned;
+        };
+
+        let owner_names: Vec<String> = all_owner_names.iter()
+            .filter(|owner_name| !self.is_hidden_from(module_name, owner_name, name))
+            .cloned()
+            .collect();
+
+        if owner_names.is_empty() {
+            return NameResolution::Unowned;
+        }
+
+        for preferred_owner in [module_name, ""] {
+            if owner_names.iter().any(|owner_name| owner_name == preferred_owner) {
+                return NameResolution::Found(Self::qualify(preferred_owner, name));
+            }
+        }
+
+        match owner_names.as_slice() {
+            [only_owner] => NameResolution::Found(Self::qualify(only_owner, name)),
+            _ => NameResolution::Ambiguous(owner_names),
+        }
+    }
+
+    /// NOTE: Gives the global name that a plain name refers to from the given module, which is the plain name itself unless a module declares it.
+    pub fn resolve_global_key(&self, module_name: &str, name: &str) -> String {
+        match self.resolve(module_name, name) {
+            NameResolution::Found(global_key) => global_key,
+            _ => name.to_string(),
+        }
+    }
+}
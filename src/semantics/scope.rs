@@ -1,5 +1,6 @@
 use std::collections::{HashMap};
 
+use crate::frontend::token::Token;
 use crate::semantics::types::ValueCategoryTag;
 
 /// See `SemanticNote::DataValue`
@@ -42,6 +43,10 @@ impl SemanticNote {
         false
     }
 
+    pub fn is_callable(&self) -> bool {
+        matches!(self, SemanticNote::Callable(_, _, _) | SemanticNote::Constructor(_, _, _) | SemanticNote::Method(_, _, _, _))
+    }
+
     pub fn try_unbox_data_value(&self) -> Option<RawDataValue> {
         if let SemanticNote::DataValue(type_id, value_category) = self {
             return Some((*type_id, *value_category));
@@ -50,7 +55,7 @@ impl SemanticNote {
         None
     }
 
-    pub fn try_unbox_callable_info(&self) -> Option<RawCallable> {
+    pub fn try_unbox_callable_info(&self) -> Option<RawCallable<'_>> {
         match self {
             Self::Callable(full_type_id, result_type_id, arity_n) => Some((full_type_id, *result_type_id, *arity_n)),
             Self::Constructor(args_type_ids, class_type_id, arity) => Some((args_type_ids, *class_type_id, *arity,)),
@@ -58,7 +63,7 @@ impl SemanticNote {
         }
     }
 
-    pub fn try_unbox_method_info(&self) -> Option<RawMethodCallable> {
+    pub fn try_unbox_method_info(&self) -> Option<RawMethodCallable<'_>> {
         if let Self::Method(arg_type_ids, ret_type_id, arity, class_type_id) = self {
             return Some((arg_type_ids, *ret_type_id, *arity, *class_type_id));
         }
@@ -77,6 +82,9 @@ impl SemanticNote {
 
 pub struct Scope {
     entries: HashMap<String, SemanticNote>,
+
+    /// INFO: maps each recorded name to its declaring token, so redeclarations can point back at the original.
+    sites: HashMap<String, Token>,
     name: String,
 }
 
@@ -84,6 +92,7 @@ impl Scope {
     pub fn new(scope_title: &str) -> Self {
         Self {
             entries: HashMap::new(),
+            sites: HashMap::new(),
             name: String::from(scope_title),
         }
     }
@@ -92,12 +101,21 @@ impl Scope {
         self.entries.get(name)
     }
 
-    pub fn try_set_entry(&mut self, name: &str, arg: SemanticNote) -> bool {
+    pub fn try_get_site(&self, name: &str) -> Option<&Token> {
+        self.sites.get(name)
+    }
+
+    pub fn try_set_entry(&mut self, name: &str, arg: SemanticNote, site_opt: Option<&Token>) -> bool {
         if self.entries.contains_key(name) {
             return false;
         }
 
         self.entries.insert(String::from(name), arg);
+
+        if let Some(site) = site_opt {
+            self.sites.insert(String::from(name), *site);
+        }
+
         true
     }
 
@@ -169,7 +187,7 @@ impl ScopeStack {
         SemanticNote::Dud
     }
 
-    pub fn record_name_info(&mut self, name: &str, arg: SemanticNote) -> bool {
-        self.scopes.last_mut().unwrap().try_set_entry(name, arg)
+    pub fn record_name_info(&mut self, name: &str, arg: SemanticNote, site_opt: Option<&Token>) -> bool {
+        self.scopes.last_mut().unwrap().try_set_entry(name, arg, site_opt)
     }
 }
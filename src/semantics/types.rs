@@ -8,6 +8,9 @@ pub enum PrimitiveTag {
     Integer,
     Floating,
     Varchar,
+    Long,
+    Callback,
+    Buffer,
 }
 
 #[repr(i32)]
@@ -33,8 +36,11 @@ pub enum OperatorTag {
     Access,
     Call,
     Negate,
+    Not,
     Increment,
     Decrement,
+    PostIncrement,
+    PostDecrement,
     Times,
     Slash,
     Plus,
@@ -43,7 +49,10 @@ pub enum OperatorTag {
     Inequality,
     LessThan,
     GreaterThan,
-    Assign
+    LessEqual,
+    GreaterEqual,
+    Assign,
+    Index,
 }
 
 impl OperatorTag {
@@ -53,8 +62,11 @@ impl OperatorTag {
             Self::Access => 2,
             Self::Call => 1,
             Self::Negate => 1,
+            Self::Not => 1,
             Self::Increment => 1,
             Self::Decrement => 1,
+            Self::PostIncrement => 1,
+            Self::PostDecrement => 1,
             Self::Times => 2,
             Self::Slash => 2,
             Self::Plus => 2,
@@ -63,7 +75,10 @@ impl OperatorTag {
             Self::Inequality => 2,
             Self::LessThan => 2,
             Self::GreaterThan => 2,
+            Self::LessEqual => 2,
+            Self::GreaterEqual => 2,
             Self::Assign => 2,
+            Self::Index => 2,
         }
     }
 
@@ -73,8 +88,11 @@ impl OperatorTag {
             Self::Access => ".",
             Self::Call => "(call)",
             Self::Negate => "- (negate)",
-            Self::Increment => "++",
-            Self::Decrement => "--",
+            Self::Not => "!",
+            Self::Increment => "++ (prefix)",
+            Self::Decrement => "-- (prefix)",
+            Self::PostIncrement => "++ (postfix)",
+            Self::PostDecrement => "-- (postfix)",
             Self::Times => "*",
             Self::Slash => "/",
             Self::Plus => "+",
@@ -83,12 +101,19 @@ impl OperatorTag {
             Self::Inequality => "!=",
             Self::LessThan => "<",
             Self::GreaterThan => ">",
+            Self::LessEqual => "<=",
+            Self::GreaterEqual => ">=",
             Self::Assign => "=",
+            Self::Index => "[]",
         }
     }
 
     pub fn is_homogeneously_typed(&self) -> bool {
-        matches!(self, Self::Noop | Self::Negate | Self::Times | Self::Slash | Self::Plus | Self::Minus | Self::Equality | Self::Inequality | Self::LessThan | Self::GreaterThan | Self::Assign)
+        matches!(self, Self::Noop | Self::Negate | Self::Not | Self::Times | Self::Slash | Self::Plus | Self::Minus | Self::Equality | Self::Inequality | Self::LessThan | Self::GreaterThan | Self::LessEqual | Self::GreaterEqual | Self::Assign)
+    }
+
+    pub fn is_comparison(&self) -> bool {
+        matches!(self, Self::Equality | Self::Inequality | Self::LessThan | Self::GreaterThan | Self::LessEqual | Self::GreaterEqual)
     }
 
     pub fn is_value_group_sensitive(&self) -> bool {
@@ -136,6 +161,9 @@ impl TypeKind for PrimitiveInfo {
             PrimitiveTag::Integer => String::from("int"),
             PrimitiveTag::Floating => String::from("float"),
             PrimitiveTag::Varchar => String::from("varchar"),
+            PrimitiveTag::Long => String::from("long"),
+            PrimitiveTag::Callback => String::from("callback"),
+            PrimitiveTag::Buffer => String::from("buffer"),
             _ => String::from("unknown"),
         }
     }
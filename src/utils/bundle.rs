@@ -7,13 +7,40 @@
 
 use std::collections::HashMap;
 
+use crate::semantics::analyzer::{CALLBACK_TYPE_ID_N, lookup_primitive_type_id};
 use crate::vm::{callable::Callable, engine::Engine};
 
-/// Stores information per native function in a Bundle: ID & expected arity.
-#[derive(Clone, Copy)]
+/// Parameter & result type IDs of the functions a native takes for one of its `callback` parameters.
+pub type CallbackShape = (Vec<i32>, i32);
+
+/// Stores information per native function in a Bundle: ID, expected arity, whether it may mutate heap arguments, & its parameter / result type IDs for the analyzer. A `result_type_id` of `-1` means no signature was given. Each `callback` parameter also has the shape of the functions it takes, while other parameters have `None`.
+#[derive(Clone)]
 pub struct NativeBrief {
     pub id: i32,
     pub arity: i32,
+    pub mutates_args: bool,
+    pub param_type_ids: Vec<i32>,
+    pub callback_shapes: Vec<Option<CallbackShape>>,
+    pub result_type_id: i32,
+}
+
+/// NOTE: Maps a parameter type name to its type ID & callback shape, where a `callback` must give its shape like a signature e.g `callback(int): int`. Gives `None` for unknown types.
+fn lookup_param_type(type_name: &str) -> Option<(i32, Option<CallbackShape>)> {
+    let Some(shape_text) = type_name.strip_prefix("callback(") else {
+        let param_type_id = lookup_primitive_type_id(type_name);
+
+        return if param_type_id != -1 && param_type_id != CALLBACK_TYPE_ID_N { Some((param_type_id, None)) } else { None };
+    };
+
+    let (shape_params_text, shape_result_name) = shape_text.split_once("): ")?;
+    let shape_param_ids: Vec<i32> = if shape_params_text.is_empty() { Vec::new() } else { shape_params_text.split(", ").map(lookup_primitive_type_id).collect() };
+    let shape_result_id = lookup_primitive_type_id(shape_result_name);
+
+    if shape_param_ids.contains(&-1) || shape_result_id == -1 {
+        return None;
+    }
+
+    Some((CALLBACK_TYPE_ID_N, Some((shape_param_ids, shape_result_id))))
 }
 
 /*
@@ -22,7 +49,7 @@ pub struct NativeBrief {
  */
 #[derive(Default)]
 pub struct Bundle {
-    registry: HashMap<&'static str, NativeBrief>,
+    registry: HashMap<String, NativeBrief>,
     routines: Vec<Callable<Engine>>,
     next_id: i32,
 }
@@ -36,21 +63,61 @@ impl Bundle {
         }
     }
 
-    /// NOTE: all arguments to `arity_arg` must match the number of arguments popped off the Engine stack.
-    pub fn register_native(&mut self, name: &'static str, callable_arg: Callable<Engine>, arity_arg: i32) -> bool {
-        if self.registry.contains_key(name) {
+    /// NOTE: all arguments to `arity_arg` must match the number of arguments popped off the Engine stack. The name is owned by the bundle, so names built at runtime (e.g from a plugin list) work as well as literals.
+    pub fn register_native(&mut self, name: impl Into<String>, callable_arg: Callable<Engine>, arity_arg: i32) -> bool {
+        let owned_name: String = name.into();
+
+        if self.registry.contains_key(&owned_name) {
             return false;
         }
 
         let next_callable_id = self.next_id;
 
-        self.registry.insert(name, NativeBrief { id: next_callable_id, arity: arity_arg });
+        self.registry.insert(owned_name, NativeBrief {
+            id: next_callable_id,
+            arity: arity_arg,
+            mutates_args: false,
+            param_type_ids: Vec::new(),
+            callback_shapes: Vec::new(),
+            result_type_id: -1,
+        });
         self.routines.push(callable_arg);
         self.next_id += 1;
 
         true
     }
 
+    /// NOTE: marks a registered native as modifying its heap arguments e.g varchars, so the compiler never passes it a shared literal.
+    pub fn mark_native_mutating(&mut self, name: &str) -> bool {
+        if let Some(brief_ref) = self.registry.get_mut(name) {
+            brief_ref.mutates_args = true;
+            return true;
+        }
+
+        false
+    }
+
+    /// NOTE: gives a registered native its parameter & result types by primitive names e.g `&["varchar", "int"]` and `"char"`, which are mapped to the analyzer's type IDs. The parameter count must match the registered arity, and `any` accepts every value. A `callback` parameter names the shape of the functions it takes e.g `"callback(int): int"`, which the analyzer checks each passed function against.
+    pub fn set_native_signature(&mut self, name: &str, param_type_names: &[&str], result_type_name: &str) -> bool {
+        let Some(param_types): Option<Vec<(i32, Option<CallbackShape>)>> = param_type_names.iter().map(|type_name| lookup_param_type(type_name)).collect() else {
+            return false;
+        };
+        let result_type_id = lookup_primitive_type_id(result_type_name);
+
+        if result_type_id == -1 {
+            return false;
+        }
+
+        if let Some(brief_ref) = self.registry.get_mut(name)
+            && brief_ref.arity == param_types.len() as i32 {
+            (brief_ref.param_type_ids, brief_ref.callback_shapes) = param_types.into_iter().unzip();
+            brief_ref.result_type_id = result_type_id;
+            return true;
+        }
+
+        false
+    }
+
     /// # SAFETY
     /// This Bundle method is unsafe for performance reasons, as index-checked dispatches to native functions would create unneeded slowdowns. Thus, all ID's passed must be valid!
     pub unsafe fn get_native(&self, native_id: i32) -> &Callable<Engine> {
@@ -59,7 +126,82 @@ impl Bundle {
         }
     }
 
-    pub fn peek_registry(&self) -> &HashMap<&'static str, NativeBrief> {
+    pub fn peek_registry(&self) -> &HashMap<String, NativeBrief> {
         &self.registry
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::driver::{CompileError, CompilerMain};
+    use crate::semantics::analyzer::{BOOLEAN_TYPE_ID_N, INTEGER_TYPE_ID_N};
+    use crate::utils::logging::LogLevel;
+    use crate::vm::callable::ExecStatus;
+    use crate::vm::engine::EngineBuilder;
+    use crate::vm::value::Value;
+
+    fn native_answer(engine_ref: &mut Engine) -> ExecStatus {
+        engine_ref.push_in(Value::Int(42));
+
+        ExecStatus::Ok
+    }
+
+    #[test]
+    fn natives_named_at_runtime_are_callable() {
+        let plugin_names = vec![String::from("answer")];
+        let mut natives = Bundle::new();
+
+        for plugin_name in &plugin_names {
+            let native_name = format!("plugin_{plugin_name}");
+
+            assert!(natives.register_native(native_name.clone(), Box::new(native_answer), 0));
+            assert!(!natives.register_native(native_name.as_str(), Box::new(native_answer), 0));
+            assert!(natives.set_native_signature(&native_name, &[], "int"));
+        }
+
+        let source = "foreign plugin_answer(): int;\n\nfun main(): int {\n    return plugin_answer() - 42;\n}\n";
+        let mut test_compiler = CompilerMain::new("./test.loxie", source, natives.peek_registry());
+        test_compiler.set_log_level(LogLevel::Quiet);
+
+        let program = test_compiler.compile_from_start(crate::setup_lexical_items()).expect("test source should compile");
+        let mut engine = EngineBuilder::new().build(program);
+
+        assert!(engine.run(&natives) == ExecStatus::Ok);
+    }
+
+    fn native_noop(engine_ref: &mut Engine) -> ExecStatus {
+        engine_ref.push_in(Value::Int(0));
+
+        ExecStatus::Ok
+    }
+
+    #[test]
+    fn callback_params_need_a_known_shape() {
+        let mut natives = Bundle::new();
+        natives.register_native("each_pair", Box::new(native_noop), 2);
+
+        assert!(!natives.set_native_signature("each_pair", &["callback", "int"], "int"));
+        assert!(!natives.set_native_signature("each_pair", &["callback(int, what): bool", "int"], "int"));
+        assert!(natives.set_native_signature("each_pair", &["callback(int, int): bool", "int"], "int"));
+
+        let brief = natives.peek_registry().get("each_pair").unwrap();
+
+        assert!(brief.callback_shapes[0] == Some((vec![INTEGER_TYPE_ID_N, INTEGER_TYPE_ID_N], BOOLEAN_TYPE_ID_N)));
+        assert!(brief.callback_shapes[1].is_none());
+    }
+
+    #[test]
+    fn callbacks_are_checked_against_their_native_shape() {
+        let mut natives = Bundle::new();
+        natives.register_native("each_pair", Box::new(native_noop), 2);
+        assert!(natives.set_native_signature("each_pair", &["callback(int, int): bool", "int"], "int"));
+
+        let source = "foreign each_pair(f: callback, n: int): int;\n\nfun is_less(a: int, b: int): bool {\n    return a < b;\n}\n\nfun twice(n: int): int {\n    return n * 2;\n}\n\nfun main(): int {\n    let checked: int = each_pair(is_less, 1);\n\n    return each_pair(twice, checked);\n}\n";
+        let mut test_compiler = CompilerMain::new("./test.loxie", source, natives.peek_registry());
+        test_compiler.set_log_level(LogLevel::Quiet);
+
+        assert!(test_compiler.compile_from_start(crate::setup_lexical_items()).is_none());
+        assert_eq!(test_compiler.take_compile_errors(), vec![CompileError::Semantics(2)]);
+    }
+}
@@ -0,0 +1,30 @@
+/**
+ * ### ABOUT
+ * Orders how much the compiler reports about its progress, where each level also allows the messages of the levels before it.
+ * 1. `Quiet`: only diagnostics, so stdout keeps just a program's output e.g for `--filter`.
+ * 2. `Normal`: also status lines like "Check passed", which is the default.
+ * 3. `Verbose`: also progress lines like parsed TUs and the program's disassembly for `--verbose`.
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Quiet,
+    #[default]
+    Normal,
+    Verbose,
+}
+
+impl LogLevel {
+    pub fn allows(&self, needed_level: LogLevel) -> bool {
+        *self >= needed_level
+    }
+}
+
+/// NOTE: Prints a progress line only for `LogLevel::Verbose`, so the message is never even formatted otherwise.
+#[macro_export]
+macro_rules! log_verbose {
+    ($level: expr, $($format_args: tt)*) => {
+        if $level.allows($crate::utils::logging::LogLevel::Verbose) {
+            println!($($format_args)*);
+        }
+    };
+}
@@ -0,0 +1,82 @@
+use crate::vm::{bytecode::ArgMode, callable::ExecStatus, engine::Engine, value::Value};
+
+/// NOTE: This caps how many ints a buffer may hold, so a bad size can't make the interpreter allocate gigabytes.
+const MAX_BUFFER_ITEMS: i32 = i16::MAX as i32;
+
+/// NOTE: Gives the heap ID of a buffer argument, or `-1` if the value isn't a reference to one.
+fn find_buffer_id(engine_ref: &mut Engine, buffer_arg_opt: Option<Value>) -> i32 {
+    let Some(Value::HeapRef(buffer_id)) = buffer_arg_opt else {
+        return -1;
+    };
+
+    let buffer_len = engine_ref.fetch_heap_value_by((ArgMode::HeapId, buffer_id))
+        .map_or(-1, |heap_value_ref| heap_value_ref.try_array_len());
+
+    if buffer_len == -1 { -1 } else { buffer_id }
+}
+
+/// NOTE: Makes a buffer of `size` ints which all start as `0` e.g `new_buffer(3)` gives `[0, 0, 0]`. The size must be from 0 to `MAX_BUFFER_ITEMS`.
+pub fn native_new_buffer(engine_ref: &mut Engine) -> ExecStatus {
+    let Some(Value::Int(buffer_size)) = engine_ref.pop_off() else {
+        engine_ref.report_diagnostic("Expected an int size for new_buffer!");
+        return ExecStatus::BadArgs;
+    };
+
+    if !(0..=MAX_BUFFER_ITEMS).contains(&buffer_size) {
+        engine_ref.report_diagnostic(format!("Expected a size from 0 to {MAX_BUFFER_ITEMS} for new_buffer, but found '{buffer_size}'").as_str());
+        return ExecStatus::BadArgs;
+    }
+
+    if !engine_ref.push_new_array(vec![Value::Int(0); buffer_size as usize]) {
+        return ExecStatus::RefError;
+    }
+
+    ExecStatus::Ok
+}
+
+pub fn native_buffer_get(engine_ref: &mut Engine) -> ExecStatus {
+    let item_pos_opt = engine_ref.pop_off();
+    let buffer_arg_opt = engine_ref.pop_off();
+    let buffer_id = find_buffer_id(engine_ref, buffer_arg_opt);
+
+    let (Some(Value::Int(item_pos)), true) = (item_pos_opt, buffer_id != -1) else {
+        engine_ref.report_diagnostic("Expected a buffer and an int position for buffer_get!");
+        return ExecStatus::BadArgs;
+    };
+
+    let item_opt = engine_ref.fetch_heap_value_by((ArgMode::HeapId, buffer_id))
+        .and_then(|heap_value_ref| heap_value_ref.try_array_get(item_pos));
+
+    let Some(item) = item_opt else {
+        engine_ref.report_diagnostic(format!("Position '{item_pos}' is out of range for buffer_get!").as_str());
+        return ExecStatus::BadArgs;
+    };
+
+    engine_ref.push_in(item);
+
+    ExecStatus::Ok
+}
+
+/// NOTE: Like `intrin_varchar_set`, an out-of-range position gives `false` instead of failing, letting the program recover.
+pub fn native_buffer_set(engine_ref: &mut Engine) -> ExecStatus {
+    let item_opt = engine_ref.pop_off();
+    let item_pos_opt = engine_ref.pop_off();
+    let buffer_arg_opt = engine_ref.pop_off();
+    let buffer_id = find_buffer_id(engine_ref, buffer_arg_opt);
+
+    let (Some(Value::Int(item_pos)), Some(item @ Value::Int(_)), true) = (item_pos_opt, item_opt, buffer_id != -1) else {
+        engine_ref.report_diagnostic("Expected a buffer, an int position, and an int for buffer_set!");
+        return ExecStatus::BadArgs;
+    };
+
+    let result_flag = engine_ref.fetch_heap_value_by((ArgMode::HeapId, buffer_id))
+        .is_some_and(|heap_value_ref| heap_value_ref.try_array_set(item_pos, item));
+
+    engine_ref.push_in(Value::Bool(result_flag));
+
+    if !result_flag {
+        return ExecStatus::Recoverable;
+    }
+
+    ExecStatus::Ok
+}
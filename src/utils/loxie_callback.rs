@@ -0,0 +1,33 @@
+use crate::vm::{callable::ExecStatus, engine::Engine, value::Value};
+
+/// NOTE: Applies a Loxie function taking & giving an int `n` times over, feeding each result into the next call e.g `apply_times(twice, 3, 2)` gives `12`. The count must be non-negative.
+pub fn native_apply_times(engine_ref: &mut Engine) -> ExecStatus {
+    let count_arg_opt = engine_ref.pop_off();
+    let start_arg_opt = engine_ref.pop_off();
+    let callback_arg_opt = engine_ref.pop_off();
+
+    let (Some(Value::Int(callback_id)), Some(Value::Int(start_arg)), Some(Value::Int(count_arg))) = (callback_arg_opt, start_arg_opt, count_arg_opt) else {
+        engine_ref.report_diagnostic("Expected a callback and 2 ints for apply_times!");
+        return ExecStatus::BadArgs;
+    };
+
+    if count_arg < 0 {
+        engine_ref.report_diagnostic(format!("Expected a non-negative count for apply_times, but found '{count_arg}'").as_str());
+        return ExecStatus::BadArgs;
+    }
+
+    let mut current_value = Value::Int(start_arg);
+
+    for _ in 0..count_arg {
+        current_value = engine_ref.call_function(callback_id, &[current_value]);
+
+        if !matches!(current_value, Value::Int(_)) {
+            engine_ref.report_diagnostic("Expected the callback of apply_times to give an int!");
+            return ExecStatus::BadArgs;
+        }
+    }
+
+    engine_ref.push_in(current_value);
+
+    ExecStatus::Ok
+}
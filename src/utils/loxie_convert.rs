@@ -0,0 +1,38 @@
+use crate::vm::{callable::ExecStatus, engine::Engine, value::Value};
+
+pub fn native_to_float(engine_ref: &mut Engine) -> ExecStatus {
+    let int_arg_opt = engine_ref.pop_off();
+
+    if let Some(Value::Int(int_arg)) = int_arg_opt {
+        engine_ref.push_in(Value::Float(int_arg as f32));
+        return ExecStatus::Ok;
+    }
+
+    engine_ref.report_diagnostic("Expected an int for to_float!");
+    ExecStatus::BadArgs
+}
+
+/// NOTE: The float is truncated towards zero, and out of range values saturate to the int limits.
+pub fn native_to_int(engine_ref: &mut Engine) -> ExecStatus {
+    let float_arg_opt = engine_ref.pop_off();
+
+    if let Some(Value::Float(float_arg)) = float_arg_opt {
+        engine_ref.push_in(Value::Int(float_arg as i32));
+        return ExecStatus::Ok;
+    }
+
+    engine_ref.report_diagnostic("Expected a float for to_int!");
+    ExecStatus::BadArgs
+}
+
+pub fn native_char_code(engine_ref: &mut Engine) -> ExecStatus {
+    let char_arg_opt = engine_ref.pop_off();
+
+    if let Some(Value::Char(char_arg)) = char_arg_opt {
+        engine_ref.push_in(Value::Int(char_arg as i32));
+        return ExecStatus::Ok;
+    }
+
+    engine_ref.report_diagnostic("Expected a char for char_code!");
+    ExecStatus::BadArgs
+}
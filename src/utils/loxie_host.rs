@@ -0,0 +1,29 @@
+use crate::vm::{bytecode, callable::ExecStatus, engine::Engine, value::Value};
+
+/// NOTE: Gives a copy of the named host string, so a script changing its result never alters what the host seeded for later reads.
+pub fn native_host_string(engine_ref: &mut Engine) -> ExecStatus {
+    let name_ref_opt = engine_ref.pop_off();
+
+    let Some(Value::HeapRef(name_obj_id)) = name_ref_opt else {
+        engine_ref.report_diagnostic("Expected a varchar name for host_string!");
+        return ExecStatus::BadArgs;
+    };
+
+    let Some(host_name) = engine_ref.fetch_heap_value_by((bytecode::ArgMode::HeapId, name_obj_id))
+        .and_then(|name_ref| name_ref.try_varchar_view())
+        .map(String::from) else {
+        engine_ref.report_diagnostic("Expected a varchar name for host_string!");
+        return ExecStatus::BadArgs;
+    };
+
+    let Some(host_text) = engine_ref.find_host_string(host_name.as_str()) else {
+        engine_ref.report_diagnostic(format!("No host string named '{host_name}' was given!").as_str());
+        return ExecStatus::BadArgs;
+    };
+
+    if !engine_ref.push_new_varchar(host_text.as_str()) {
+        return ExecStatus::RefError;
+    }
+
+    ExecStatus::Ok
+}
@@ -0,0 +1,54 @@
+use crate::vm::{callable::ExecStatus, engine::Engine, value::Value};
+
+/// NOTE: The `==` operator stays exact, so this is for computed floats e.g `0.1 + 0.2` which only approximate `0.3`. The tolerance must be a non-negative float.
+pub fn native_float_eq(engine_ref: &mut Engine) -> ExecStatus {
+    let eps_arg_opt = engine_ref.pop_off();
+    let rhs_arg_opt = engine_ref.pop_off();
+    let lhs_arg_opt = engine_ref.pop_off();
+
+    let (Some(Value::Float(lhs_arg)), Some(Value::Float(rhs_arg)), Some(Value::Float(eps_arg))) = (lhs_arg_opt, rhs_arg_opt, eps_arg_opt) else {
+        engine_ref.report_diagnostic("Expected 3 floats for float_eq!");
+        return ExecStatus::BadArgs;
+    };
+
+    if eps_arg.is_nan() || eps_arg < 0.0 {
+        engine_ref.report_diagnostic(format!("Expected a non-negative tolerance for float_eq, but found '{eps_arg}'").as_str());
+        return ExecStatus::BadArgs;
+    }
+
+    engine_ref.push_in(Value::Bool((lhs_arg - rhs_arg).abs() <= eps_arg));
+
+    ExecStatus::Ok
+}
+
+/// NOTE: Bounds an int or float to `[lo, hi]`, so all 3 arguments must share that numeric type & the range must not be reversed. A NaN bound counts as reversed, as it has no order.
+pub fn native_clamp(engine_ref: &mut Engine) -> ExecStatus {
+    let hi_arg_opt = engine_ref.pop_off();
+    let lo_arg_opt = engine_ref.pop_off();
+    let value_arg_opt = engine_ref.pop_off();
+
+    let clamped_value = match (value_arg_opt, lo_arg_opt, hi_arg_opt) {
+        (Some(Value::Int(value_arg)), Some(Value::Int(lo_arg)), Some(Value::Int(hi_arg))) if lo_arg <= hi_arg => {
+            Value::Int(value_arg.clamp(lo_arg, hi_arg))
+        },
+        (Some(Value::Float(value_arg)), Some(Value::Float(lo_arg)), Some(Value::Float(hi_arg))) if lo_arg <= hi_arg => {
+            Value::Float(value_arg.clamp(lo_arg, hi_arg))
+        },
+        (Some(Value::Int(_)), Some(Value::Int(lo_arg)), Some(Value::Int(hi_arg))) => {
+            engine_ref.report_diagnostic(format!("Expected lo <= hi for clamp, but found '{lo_arg}' & '{hi_arg}'").as_str());
+            return ExecStatus::BadArgs;
+        },
+        (Some(Value::Float(_)), Some(Value::Float(lo_arg)), Some(Value::Float(hi_arg))) => {
+            engine_ref.report_diagnostic(format!("Expected lo <= hi for clamp, but found '{lo_arg}' & '{hi_arg}'").as_str());
+            return ExecStatus::BadArgs;
+        },
+        _ => {
+            engine_ref.report_diagnostic("Expected 3 ints or 3 floats for clamp!");
+            return ExecStatus::BadArgs;
+        },
+    };
+
+    engine_ref.push_in(clamped_value);
+
+    ExecStatus::Ok
+}
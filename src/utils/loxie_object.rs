@@ -0,0 +1,79 @@
+use crate::vm::{bytecode::ArgMode, callable::ExecStatus, engine::Engine, heap::{HeapValue, ObjectTag}, value::Value};
+
+/// NOTE: Instances don't record their class at runtime yet, so every instance is named `"object"`.
+pub fn native_type_name(engine_ref: &mut Engine) -> ExecStatus {
+    let arg_value_opt = engine_ref.pop_off();
+
+    if arg_value_opt.is_none() {
+        engine_ref.report_diagnostic("Expected a value for type_name!");
+        return ExecStatus::BadArgs;
+    }
+
+    let type_name_str = match arg_value_opt.unwrap() {
+        Value::Empty() => "empty",
+        Value::Bool(_) => "bool",
+        Value::Char(_) => "char",
+        Value::Int(_) => "int",
+        Value::Float(_) => "float",
+        Value::Long(_) => "long",
+        Value::HeapRef(obj_id) => {
+            let obj_tag = engine_ref.fetch_heap_value_by((ArgMode::HeapId, obj_id))
+                .map(|obj_ref| obj_ref.get_object_tag())
+                .unwrap_or(ObjectTag::None);
+
+            match obj_tag {
+                ObjectTag::Varchar => "varchar",
+                ObjectTag::Array => "buffer",
+                ObjectTag::Instance => "object",
+                ObjectTag::None => "empty",
+            }
+        },
+    };
+
+    if !engine_ref.push_new_varchar(type_name_str) {
+        return ExecStatus::RefError;
+    }
+
+    ExecStatus::Ok
+}
+
+/// NOTE: The heap ID is only a stable identity while the instance is live, as collected cells get reused & heap compaction moves instances to new IDs.
+pub fn native_object_id(engine_ref: &mut Engine) -> ExecStatus {
+    let obj_ref_opt = engine_ref.pop_off();
+
+    let obj_heap_id = if let Some(Value::HeapRef(obj_id)) = obj_ref_opt { obj_id } else { -1 };
+
+    let obj_tag = engine_ref.fetch_heap_value_by((ArgMode::HeapId, obj_heap_id))
+        .map(|obj_ref| obj_ref.get_object_tag())
+        .unwrap_or(ObjectTag::None);
+
+    if obj_heap_id == -1 || obj_tag != ObjectTag::Instance {
+        engine_ref.report_diagnostic("Expected a class instance for object_id!");
+        return ExecStatus::BadArgs;
+    }
+
+    engine_ref.push_in(Value::Int(obj_heap_id));
+
+    ExecStatus::Ok
+}
+
+/// NOTE: The copy is shallow, so its fields reference the same objects as the original's e.g a shared varchar, while scalar fields change independently.
+pub fn native_clone(engine_ref: &mut Engine) -> ExecStatus {
+    let obj_ref_opt = engine_ref.pop_off();
+
+    let obj_heap_id = if let Some(Value::HeapRef(obj_id)) = obj_ref_opt { obj_id } else { -1 };
+
+    let copied_fields_opt = engine_ref.fetch_heap_value_by((ArgMode::HeapId, obj_heap_id))
+        .and_then(|obj_ref| if let HeapValue::Instance(fields) = obj_ref { Some(fields.clone()) } else { None });
+
+    let Some(copied_fields) = copied_fields_opt else {
+        engine_ref.report_diagnostic("Expected a class instance for clone!");
+        return ExecStatus::BadArgs;
+    };
+
+    if !engine_ref.push_new_instance(copied_fields) {
+        return ExecStatus::RefError;
+    }
+
+    ExecStatus::Ok
+}
@@ -1,7 +1,36 @@
-use crate::vm::{bytecode::ArgMode, callable::ExecStatus, engine::Engine, value::Value};
+use crate::vm::{bytecode::ArgMode, callable::ExecStatus, engine::Engine, heap::HeapValue, value::Value};
+
+/// NOTE: Nested heap references are resolved only one level deep, so buffer & instance fields show up as e.g `object-<heap-id>` to avoid endless output for cyclic objects.
+fn format_instance_fields(engine_ref: &mut Engine, fields: &[Value]) -> String {
+    let mut field_texts = Vec::<String>::with_capacity(fields.len());
+
+    for field_value in fields {
+        let field_text = match field_value {
+            Value::HeapRef(field_obj_id) => {
+                match engine_ref.fetch_heap_value_by((ArgMode::HeapId, *field_obj_id)) {
+                    Some(HeapValue::Varchar(field_vc)) => format!("\"{field_vc}\""),
+                    Some(HeapValue::Array(_)) => format!("buffer-{field_obj_id}"),
+                    Some(HeapValue::Instance(_)) => format!("object-{field_obj_id}"),
+                    _ => String::from("empty"),
+                }
+            },
+            _ => field_value.to_string(),
+        };
+
+        field_texts.push(field_text);
+    }
+
+    format!("Instance {{ {} }}", field_texts.join(", "))
+}
 
 pub fn native_read_int(engine_ref: &mut Engine) -> ExecStatus {
-    println!("Enter an integer: ");
+    if engine_ref.is_interactive() {
+        if writeln!(engine_ref.get_output_mut(), "Enter an integer: ").is_err() {
+            return ExecStatus::NotOk;
+        }
+
+        let _ = engine_ref.get_output_mut().flush();
+    }
 
     let mut raw_input = String::new();
 
@@ -12,7 +41,7 @@ pub fn native_read_int(engine_ref: &mut Engine) -> ExecStatus {
     let temp_int = raw_input.trim().parse::<i32>();
 
     if temp_int.is_err() {
-        eprintln!("Invalid input for int: '{raw_input}'");
+        engine_ref.report_diagnostic(format!("Invalid input for int: '{raw_input}'").as_str());
         return ExecStatus::BadArgs;
     }
 
@@ -24,22 +53,29 @@ pub fn native_read_int(engine_ref: &mut Engine) -> ExecStatus {
 pub fn native_print_val(engine_ref: &mut Engine) -> ExecStatus {
     let temp_value_opt = engine_ref.pop_off();
 
-    if temp_value_opt.is_none() {
-        engine_ref.push_in(Value::Bool(false));
-        ExecStatus::NotOk
-    } else {
-        let temp_value = temp_value_opt.unwrap();
+    if let Some(temp_value) = temp_value_opt {
+        let temp_text = if let Value::HeapRef(heap_value_id) = temp_value {
+            let heap_value = engine_ref.fetch_heap_value_by((ArgMode::HeapId, heap_value_id)).unwrap().clone();
 
-        if let Value::HeapRef(heap_value_id) = temp_value {
-            let temp_value = engine_ref.fetch_heap_value_by((ArgMode::HeapId, heap_value_id)).unwrap();
+            if let HeapValue::Instance(fields) = heap_value {
+                format_instance_fields(engine_ref, &fields)
+            } else {
+                heap_value.to_string()
+            }
+        } else {
+            temp_value.to_string()
+        };
 
-            println!("{temp_value}");
-        } else {   
-            println!("{temp_value}");
+        if writeln!(engine_ref.get_output_mut(), "{temp_text}").is_err() {
+            engine_ref.push_in(Value::Bool(false));
+            return ExecStatus::NotOk;
         }
 
         engine_ref.push_in(Value::Bool(true));
 
         ExecStatus::Ok
+    } else {
+        engine_ref.push_in(Value::Bool(false));
+        ExecStatus::NotOk
     }
 }
@@ -4,7 +4,7 @@ pub fn native_intrin_varchar_len(engine_ref: &mut Engine) -> ExecStatus {
     let vc_ref_opt = engine_ref.pop_off();
 
     if vc_ref_opt.is_none() {
-        eprintln!("Unexpected none reference to varchar!");
+        engine_ref.report_diagnostic("Unexpected none reference to varchar!");
         return ExecStatus::RefError;
     }
 
@@ -28,44 +28,81 @@ pub fn native_intrin_varchar_get(engine_ref: &mut Engine) -> ExecStatus {
     let vc_ref_opt = engine_ref.pop_off();
 
     if vc_ref_opt.is_none() {
-        eprintln!("Unexpected none reference to varchar!");
+        engine_ref.report_diagnostic("Unexpected none reference to varchar!");
         return ExecStatus::RefError;
     }
 
     let mut fallback_dud: HeapValue = HeapValue::Empty();
 
     let vc_heap_id = vc_ref_opt.unwrap();
-    let vc_item = engine_ref.fetch_heap_value_by(
+    let vc_pos: i32 = vc_index.into::<>();
+    let vc_item_opt = engine_ref.fetch_heap_value_by(
         (
             bytecode::ArgMode::HeapId,
             if let Value::HeapRef(obj_id) = vc_heap_id { obj_id } else { -1 }
         )
-    ).unwrap_or(&mut fallback_dud).try_varchar_get(vc_index.into::<>());
+    ).unwrap_or(&mut fallback_dud).try_varchar_get(vc_pos);
+
+    let Some(vc_item) = vc_item_opt else {
+        engine_ref.report_diagnostic(format!("Position '{vc_pos}' is out of range for intrin_varchar_get!").as_str());
+        return ExecStatus::BadArgs;
+    };
 
     engine_ref.push_in(Value::Char(vc_item));
 
     ExecStatus::Ok
 }
 
-pub fn native_intrin_varchar_set(engine_ref: &mut Engine) -> ExecStatus {
+/// NOTE: Pops the varchar, position & char of a write to try it, giving the position with whether the char was written or `None` for a missing varchar.
+fn help_varchar_write(engine_ref: &mut Engine) -> Option<(i32, bool)> {
     let next_ascii_c = engine_ref.pop_off().unwrap_or(Value::Char(0));
     let vc_index = engine_ref.pop_off().unwrap_or(Value::Int(-1));
     let vc_ref_opt = engine_ref.pop_off();
 
     if vc_ref_opt.is_none() {
-        eprintln!("Unexpected none reference to varchar!");
-        return ExecStatus::RefError;
+        engine_ref.report_diagnostic("Unexpected none reference to varchar!");
+        return None;
     }
 
     let mut fallback_dud: HeapValue = HeapValue::Empty();
 
     let vc_heap_id = vc_ref_opt.unwrap();
+    let vc_pos: i32 = vc_index.into::<>();
     let result_flag = engine_ref.fetch_heap_value_by(
         (
             bytecode::ArgMode::HeapId,
             if let Value::HeapRef(obj_id) = vc_heap_id { obj_id } else { -1 }
         )
-    ).unwrap_or(&mut fallback_dud).try_varchar_set(vc_index.into::<>(), next_ascii_c.into::<>());
+    ).unwrap_or(&mut fallback_dud).try_varchar_set(vc_pos, next_ascii_c.into::<>());
+
+    Some((vc_pos, result_flag))
+}
+
+/// NOTE: An out-of-range position gives `false` instead of failing, letting the program recover.
+pub fn native_intrin_varchar_set(engine_ref: &mut Engine) -> ExecStatus {
+    let Some((_, result_flag)) = help_varchar_write(engine_ref) else {
+        return ExecStatus::RefError;
+    };
+
+    engine_ref.push_in(Value::Bool(result_flag));
+
+    if !result_flag {
+        return ExecStatus::Recoverable;
+    }
+
+    ExecStatus::Ok
+}
+
+/// NOTE: Backs assignments like `s[i] = c`, where an out-of-range position is a runtime error just like reading `s[i]` is.
+pub fn native_intrin_varchar_store(engine_ref: &mut Engine) -> ExecStatus {
+    let Some((vc_pos, result_flag)) = help_varchar_write(engine_ref) else {
+        return ExecStatus::RefError;
+    };
+
+    if !result_flag {
+        engine_ref.report_diagnostic(format!("Position '{vc_pos}' is out of range for the varchar assignment!").as_str());
+        return ExecStatus::BadArgs;
+    }
 
     engine_ref.push_in(Value::Bool(result_flag));
 
@@ -77,7 +114,7 @@ pub fn native_intrin_varchar_push(engine_ref: &mut Engine) -> ExecStatus {
     let vc_ref_opt = engine_ref.pop_off();
 
     if vc_ref_opt.is_none() {
-        eprintln!("Unexpected none reference to varchar!");
+        engine_ref.report_diagnostic("Unexpected none reference to varchar!");
         return ExecStatus::RefError;
     }
 
@@ -100,7 +137,7 @@ pub fn native_intrin_varchar_pop(engine_ref: &mut Engine) -> ExecStatus {
     let vc_ref_opt = engine_ref.pop_off();
 
     if vc_ref_opt.is_none() {
-        eprintln!("Unexpected none reference to varchar!");
+        engine_ref.report_diagnostic("Unexpected none reference to varchar!");
         return ExecStatus::RefError;
     }
 
@@ -118,3 +155,34 @@ pub fn native_intrin_varchar_pop(engine_ref: &mut Engine) -> ExecStatus {
 
     ExecStatus::Ok
 }
+
+/// NOTE: Gives `-1`, `0`, or `1` as the first varchar orders before, the same as, or after the second one by bytes, e.g `"ab"` before `"b"` and `"ab"` before `"abc"`.
+pub fn native_varchar_cmp(engine_ref: &mut Engine) -> ExecStatus {
+    let rhs_ref_opt = engine_ref.pop_off();
+    let lhs_ref_opt = engine_ref.pop_off();
+
+    let mut fetch_text = |ref_opt: Option<Value>| -> Option<String> {
+        let Some(Value::HeapRef(obj_id)) = ref_opt else {
+            return None;
+        };
+
+        engine_ref.fetch_heap_value_by((bytecode::ArgMode::HeapId, obj_id))
+            .and_then(|obj_ref| obj_ref.try_varchar_view())
+            .map(String::from)
+    };
+
+    let (Some(lhs_text), Some(rhs_text)) = (fetch_text(lhs_ref_opt), fetch_text(rhs_ref_opt)) else {
+        engine_ref.report_diagnostic("Expected two varchars for varchar_cmp!");
+        return ExecStatus::BadArgs;
+    };
+
+    let order_flag = match lhs_text.cmp(&rhs_text) {
+        std::cmp::Ordering::Less => -1,
+        std::cmp::Ordering::Equal => 0,
+        std::cmp::Ordering::Greater => 1,
+    };
+
+    engine_ref.push_in(Value::Int(order_flag));
+
+    ExecStatus::Ok
+}
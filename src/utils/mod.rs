@@ -1,3 +1,10 @@
 pub mod bundle;
+pub mod logging;
+pub mod loxie_host;
+pub mod loxie_buffer;
+pub mod loxie_callback;
+pub mod loxie_convert;
+pub mod loxie_math;
+pub mod loxie_object;
 pub mod loxie_stdio;
 pub mod loxie_varchar;
@@ -1,6 +1,9 @@
 // use crate::vm::callable::*;
 // use crate::vm::engine::Engine;
-use crate::vm::{heap::HeapValue, value::Value};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result};
+
+use crate::vm::{heap::{HeapValue, MAX_HEAP_SLOTS}, value::Value};
 
 #[repr(i32)]
 #[derive(Clone, Copy, PartialEq)]
@@ -37,10 +40,14 @@ pub enum Instruction {
     LoadConst(Argument),
     Push(Argument),
     Pop,
+    PushTrue,
+    PushFalse,
     MakeHeapValue(Argument),
     MakeHeapObject(Argument),
     Replace(Argument, Argument),
+    GetField(Argument, Argument),
     Neg(Argument),
+    Not,
     Inc(Argument),
     Dec(Argument),
     Add,
@@ -51,6 +58,8 @@ pub enum Instruction {
     CompareNe,
     CompareLt,
     CompareGt,
+    CompareLe,
+    CompareGe,
     JumpIf(Argument, Argument),
     JumpElse(Argument, Argument),
     Jump(Argument),
@@ -62,6 +71,43 @@ pub enum Instruction {
 }
 
 impl Instruction {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Nop => "NOP",
+            Self::LoadConst(_) => "LOAD_CONST",
+            Self::Push(_) => "PUSH",
+            Self::Pop => "POP",
+            Self::PushTrue => "PUSH_TRUE",
+            Self::PushFalse => "PUSH_FALSE",
+            Self::MakeHeapValue(_) => "MAKE_HEAP_VAL",
+            Self::MakeHeapObject(_) => "MAKE_HEAP_OBJ",
+            Self::Replace(_, _) => "REPLACE",
+            Self::GetField(_, _) => "GET_FIELD",
+            Self::Neg(_) => "NEG",
+            Self::Not => "NOT",
+            Self::Inc(_) => "INC",
+            Self::Dec(_) => "DEC",
+            Self::Add => "ADD",
+            Self::Sub => "SUB",
+            Self::Mul => "MUL",
+            Self::Div => "DIV",
+            Self::CompareEq => "CMP_EQ",
+            Self::CompareNe => "CMP_NE",
+            Self::CompareLt => "CMP_LT",
+            Self::CompareGt => "CMP_GT",
+            Self::CompareLe => "CMP_LE",
+            Self::CompareGe => "CMP_GE",
+            Self::JumpIf(_, _) => "JMP_IF",
+            Self::JumpElse(_, _) => "JMP_ELSE",
+            Self::Jump(_) => "JMP",
+            Self::Return(_) => "RETURN",
+            Self::Leave => "LEAVE",
+            Self::Call(_, _) => "CALL",
+            Self::InstanceCall(_, _, _) => "INST_CALL",
+            Self::NativeCall(_) => "NATIVE_CALL",
+        }
+    }
+
     pub fn is_valid_jump(&self) -> bool {
         match self {
             Self::JumpIf(_, target) => target.1 != -1,
@@ -96,18 +142,26 @@ impl Chunk {
     pub fn get_code(&self) -> &Vec<Instruction> {
         &self.code
     }
+
+    pub fn get_constant_count(&self) -> usize {
+        self.constants.len()
+    }
 }
 
 pub struct Procedure {
     chunk: Chunk,
     id: i32,
+
+    /// NOTE: the most stack slots of a call frame in use at once, counting arguments, which the engine checks for room before each call.
+    frame_slots: i32,
 }
 
 impl Procedure {
-    pub fn new(chunk_arg: Chunk, id_arg: i32) -> Self {
+    pub fn new(chunk_arg: Chunk, id_arg: i32, frame_slots_arg: i32) -> Self {
         Self {
             chunk: chunk_arg,
             id: id_arg,
+            frame_slots: frame_slots_arg,
         }
     }
 
@@ -122,6 +176,10 @@ impl Procedure {
     pub fn get_id(&self) -> i32 {
         self.id
     }
+
+    pub fn get_frame_slots(&self) -> i32 {
+        self.frame_slots
+    }
 }
 
 #[derive(Default)]
@@ -129,6 +187,12 @@ pub struct Program {
     procedures: Vec<Procedure>,
     heap_preloadables: Vec<HeapValue>,
     entry_id: i32,
+
+    /// NOTE: maps plain function names to their procedure ID & arity, see `Engine::run_procedure`.
+    function_exports: HashMap<String, (i32, i32)>,
+
+    /// NOTE: maps the names of host-provided strings to the heap IDs of their preloaded varchars, see `host_string`.
+    host_string_ids: HashMap<String, i32>,
 }
 
 impl Program {
@@ -137,9 +201,43 @@ impl Program {
             procedures: procedures_arg,
             heap_preloadables: heap_preloadables_arg,
             entry_id: entry_id_arg,
+            function_exports: HashMap::new(),
+            host_string_ids: HashMap::new(),
         }
     }
 
+    pub fn set_function_exports(&mut self, function_exports_arg: HashMap<String, (i32, i32)>) {
+        self.function_exports = function_exports_arg;
+    }
+
+    /// NOTE: Gives the procedure ID & arity of a plain function by its name e.g `"add"`, or `None` if there's no such function.
+    pub fn find_function(&self, name: &str) -> Option<(i32, i32)> {
+        self.function_exports.get(name).copied()
+    }
+
+    /// NOTE: Gives the arity of a plain function by its procedure ID, or `None` for other procedures e.g methods & constructors.
+    pub fn find_function_arity(&self, proc_id: i32) -> Option<i32> {
+        self.function_exports.values().find(|(export_id, _)| *export_id == proc_id).map(|(_, arity)| *arity)
+    }
+
+    /// NOTE: Seeds a named host string e.g a config value, which is preloaded into the heap after the program's own literals. Gives `false` for a repeated name or once no heap IDs are left.
+    pub fn add_host_string(&mut self, name: &str, text: &str) -> bool {
+        let host_heap_id = self.heap_preloadables.len() as i32;
+
+        if self.host_string_ids.contains_key(name) || host_heap_id >= MAX_HEAP_SLOTS {
+            return false;
+        }
+
+        self.heap_preloadables.push(HeapValue::Varchar(String::from(text)));
+        self.host_string_ids.insert(String::from(name), host_heap_id);
+
+        true
+    }
+
+    pub fn find_host_string_id(&self, name: &str) -> Option<i32> {
+        self.host_string_ids.get(name).copied()
+    }
+
     pub fn get_procedures(&self) -> &Vec<Procedure> {
         &self.procedures
     }
@@ -163,4 +261,100 @@ impl Program {
     pub fn get_heap_preloadables_mut(&mut self) -> &mut Vec<HeapValue> {
         &mut self.heap_preloadables
     }
+
+    /// NOTE: Visits every instruction of the program as `(proc_id, instruction_pos, instruction)` by procedure order, so analysis tools needn't nest loops over each procedure's chunk.
+    pub fn instructions(&self) -> impl Iterator<Item = (i32, usize, &Instruction)> {
+        self.procedures.iter().flat_map(|procedure| {
+            procedure.get_chunk().get_code().iter().enumerate().map(|(instruction_pos, instruction)| (procedure.get_id(), instruction_pos, instruction))
+        })
+    }
+
+    pub fn summary(&self) -> ProgramSummary {
+        let mut temp_instruction_count = 0;
+        let mut temp_constant_counts = Vec::<usize>::with_capacity(self.procedures.len());
+
+        for procedure in &self.procedures {
+            let temp_chunk = procedure.get_chunk();
+
+            temp_instruction_count += temp_chunk.get_code().len();
+            temp_constant_counts.push(temp_chunk.get_constant_count());
+        }
+
+        ProgramSummary {
+            procedure_count: self.procedures.len(),
+            instruction_count: temp_instruction_count,
+            constant_counts: temp_constant_counts,
+            heap_preloadable_count: self.heap_preloadables.len(),
+            entry_id: self.get_entry_procedure_id(),
+        }
+    }
+}
+
+/**
+ * ### ABOUT
+ * Brief overview of a compiled `Program` for diagnostics & tooling. Constant counts are listed by procedure (chunk) order.
+ */
+pub struct ProgramSummary {
+    pub procedure_count: usize,
+    pub instruction_count: usize,
+    pub constant_counts: Vec<usize>,
+    pub heap_preloadable_count: usize,
+    pub entry_id: Option<i32>,
+}
+
+impl Display for ProgramSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "procedures: {}", self.procedure_count)?;
+        writeln!(f, "instructions: {}", self.instruction_count)?;
+
+        for (chunk_pos, constant_n) in self.constant_counts.iter().enumerate() {
+            writeln!(f, "constants of chunk #{chunk_pos}: {constant_n}")?;
+        }
+
+        writeln!(f, "heap preloadables: {}", self.heap_preloadable_count)?;
+
+        match self.entry_id {
+            Some(id) => write!(f, "entry proc-id: {id}"),
+            None => write!(f, "entry proc-id: none"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instructions_visit_every_chunk() {
+        let source = "fun twice(n: int): int {\n    return n * 2;\n}\n\nfun main(): int {\n    if twice(2) != 4 {\n        return 1;\n    }\n\n    return 0;\n}\n";
+        let program = crate::compile("./test.loxie", source).expect("test source should compile");
+        let summary = program.summary();
+
+        assert_eq!(summary.procedure_count, 2);
+        assert_eq!(program.instructions().count(), summary.instruction_count);
+        assert!(program.instructions().all(|(proc_id, _, _)| proc_id == 0 || proc_id == 1));
+    }
+
+    #[test]
+    fn summaries_count_each_chunk() {
+        let main_chunk = Chunk::new(vec![Value::Int(1), Value::Int(2)], vec![Instruction::LoadConst((ArgMode::ConstantId, 0)), Instruction::Return((ArgMode::ConstantId, 1))]);
+        let helper_chunk = Chunk::new(Vec::new(), vec![Instruction::Leave]);
+        let procedures = vec![Procedure::new(main_chunk, 0, 1), Procedure::new(helper_chunk, 1, 0)];
+        let summary = Program::new(procedures, vec![HeapValue::Varchar(String::from("hi"))], 0).summary();
+
+        assert_eq!(summary.procedure_count, 2);
+        assert_eq!(summary.instruction_count, 3);
+        assert_eq!(summary.constant_counts, vec![2, 0]);
+        assert_eq!(summary.heap_preloadable_count, 1);
+        assert_eq!(summary.entry_id, Some(0));
+        assert_eq!(summary.to_string(), "procedures: 2\ninstructions: 3\nconstants of chunk #0: 2\nconstants of chunk #1: 0\nheap preloadables: 1\nentry proc-id: 0");
+    }
+
+    #[test]
+    fn summaries_show_a_missing_entry() {
+        let summary = Program::new(Vec::new(), Vec::new(), -1).summary();
+
+        assert_eq!(summary.entry_id, None);
+        assert_eq!(summary.to_string(), "procedures: 0\ninstructions: 0\nheap preloadables: 0\nentry proc-id: none");
+    }
 }
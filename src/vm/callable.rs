@@ -8,7 +8,13 @@ pub enum ExecStatus {
     BadMath,
     IllegalInstruction,
     BadArgs,
+    StackUnderflow,
+    CallDepthExceeded,
+    HeapExhausted,
     NotOk,
+
+    /// NOTE: Only for natives- The native pushed a failure flag as its result, so the program may keep running and check it.
+    Recoverable,
 }
 
 /*
@@ -1,11 +1,12 @@
 use std::collections::VecDeque;
+use std::io::{self, Write};
 use std::ptr::null;
 
 use crate::utils::bundle::Bundle;
 use crate::vm::bytecode::{self, ArgMode, Procedure, Program};
 use crate::vm::callable::ExecStatus;
-use crate::vm::value::Value;
-use crate::vm::heap::{HeapValue, ObjectHeap, ObjectTag};
+use crate::vm::value::{ArithError, Value};
+use crate::vm::heap::{HeapValue, MAX_HEAP_SLOTS, ObjectHeap, ObjectTag, SWEEP_BATCH_LIMIT, TOTAL_STRING_OVERHEAD};
 
 struct CallFrame {
     /// NOTE: Tracks caller procedure ID.
@@ -19,15 +20,129 @@ struct CallFrame {
     pub opt_instance: i32,
 }
 
-/// TODO: integrate ObjectHeap... add GC sweeping methods.
+pub const DEFAULT_HEAP_SIZE: usize = TOTAL_STRING_OVERHEAD * 128;
+pub const DEFAULT_STACK_SIZE: i32 = 128;
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 256;
+
+/**
+ ### ABOUT
+ * Configures & creates an `Engine` by named options. Any unset option keeps its `DEFAULT_*` value, while tracing and checked arithmetic start off and input prompts start on.
+ ### EXAMPLES
+ * `EngineBuilder::new().stack_size(256).checked_arithmetic(true).build(program)`
+ */
+pub struct EngineBuilder {
+    heap_size: usize,
+    stack_size: i32,
+    max_call_depth: usize,
+    trace: bool,
+    checked_arithmetic: bool,
+    interactive: bool,
+    compact_heap: bool,
+    max_heap_cells: i32,
+}
+
+impl Default for EngineBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EngineBuilder {
+    pub fn new() -> Self {
+        Self {
+            heap_size: DEFAULT_HEAP_SIZE,
+            stack_size: DEFAULT_STACK_SIZE,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            trace: false,
+            checked_arithmetic: false,
+            interactive: true,
+            compact_heap: false,
+            max_heap_cells: MAX_HEAP_SLOTS,
+        }
+    }
+
+    /// NOTE: The heap size is in estimated bytes, see `TOTAL_STRING_OVERHEAD`.
+    pub fn heap_size(mut self, heap_size_arg: usize) -> Self {
+        self.heap_size = heap_size_arg;
+        self
+    }
+
+    pub fn stack_size(mut self, stack_size_arg: i32) -> Self {
+        self.stack_size = stack_size_arg.max(1);
+        self
+    }
+
+    /// NOTE: Calls beyond this many active frames abort the program with `ExecStatus::CallDepthExceeded`.
+    pub fn max_call_depth(mut self, max_call_depth_arg: usize) -> Self {
+        self.max_call_depth = max_call_depth_arg.max(1);
+        self
+    }
+
+    /// NOTE: Tracing reports every executed instruction with the register state to the diagnostics sink.
+    pub fn trace(mut self, flag: bool) -> Self {
+        self.trace = flag;
+        self
+    }
+
+    /// NOTE: Checked arithmetic aborts with `ExecStatus::BadMath` on int overflows instead of wrapping.
+    pub fn checked_arithmetic(mut self, flag: bool) -> Self {
+        self.checked_arithmetic = flag;
+        self
+    }
+
+    /// NOTE: A non-interactive engine skips input prompts like `read_int`'s, so piped runs only write the program's own output.
+    pub fn interactive(mut self, flag: bool) -> Self {
+        self.interactive = flag;
+        self
+    }
+
+    /// NOTE: Heap compaction moves live objects to the lowest heap IDs once a fragmented heap is found, which frees the memory of unused cells in long runs.
+    pub fn compact_heap(mut self, flag: bool) -> Self {
+        self.compact_heap = flag;
+        self
+    }
+
+    /// NOTE: Caps how many heap cells may be live at once, below what the heap size allows. Allocating past the cap aborts the program with `ExecStatus::HeapExhausted`.
+    pub fn max_heap_cells(mut self, max_heap_cells_arg: i32) -> Self {
+        self.max_heap_cells = max_heap_cells_arg.max(1);
+        self
+    }
+
+    /// NOTE: The engine takes ownership of the program, as its heap preloadables are moved into the engine's heap.
+    pub fn build(self, program: Program) -> Engine {
+        let mut temp_engine = Engine::new(program, self.heap_size, self.max_heap_cells, self.stack_size);
+
+        temp_engine.max_call_depth = self.max_call_depth;
+        temp_engine.trace = self.trace;
+        temp_engine.checked_arithmetic = self.checked_arithmetic;
+        temp_engine.interactive = self.interactive;
+        temp_engine.compact_heap = self.compact_heap;
+
+        temp_engine
+    }
+}
+
 pub struct Engine {
+    /// INFO: Holds the running program, which the engine owns once built.
+    program: Program,
+
     heap: ObjectHeap,
+
+    /// INFO: Holds the heap size in estimated bytes, which a reset heap gets again.
+    heap_size: usize,
+
+    /// INFO: Holds the cap of live heap cells, which a reset heap gets again.
+    max_heap_cells: i32,
+
     frames: VecDeque<CallFrame>,
     stack: Vec<Value>,
 
     /// INFO: Holds a viewing pointer directly to all Procedures.
     procs_view: *const Procedure,
 
+    /// INFO: Holds a viewing pointer to the running natives, so `call_function` can dispatch callbacks that call natives too.
+    natives_view: *const Bundle,
+
     /// INFO: Holds the current Procedure pointer.
     rpp: *const Procedure,
 
@@ -42,58 +157,193 @@ pub struct Engine {
 
     stack_limit: i32,
 
+    /// INFO: Limits how many call frames may be active at once.
+    max_call_depth: usize,
+
+    /// INFO: Enables reporting of each executed instruction.
+    trace: bool,
+
+    /// INFO: Enables overflow checks on int arithmetic.
+    checked_arithmetic: bool,
+
+    /// INFO: Enables prompts before natives read input.
+    interactive: bool,
+
+    /// INFO: Enables moving live heap objects to the lowest heap IDs when the heap gets fragmented.
+    compact_heap: bool,
+
     /// INFO: Indicates execution status, including when to abort the program early.
     status: ExecStatus,
-}
 
-impl Engine {
-    pub fn new(program: &mut Program, heap_size: usize, stack_size: i32) -> Self {
-        let mut initial_frames = VecDeque::<CallFrame>::new();
+    /// INFO: Holds the integer result of `main` once `run` finishes, or `1` for failed or non-integer results.
+    exit_code: i32,
 
-        initial_frames.push_back(CallFrame {
-            caller_id: program.get_entry_procedure_id().unwrap_or(-1),
-            caller_pos: 0,
-            old_rbp: 0,
-            opt_instance: -1,
-        });
+    /// INFO: Receives all program output e.g from `print_val`.
+    output: Box<dyn Write>,
 
-        let mut initial_heap = ObjectHeap::new(heap_size);
+    /// INFO: Receives all runtime diagnostics e.g error messages.
+    diagnostics: Box<dyn Write>,
+}
 
-        for temp_heap_val in program.get_heap_preloadables_mut() {
-            let temp_cell_id = initial_heap.try_create_cell(temp_heap_val.get_object_tag());
-            *initial_heap.get_cell_mut(temp_cell_id).unwrap().get_value_mut() = std::mem::take(temp_heap_val);
-        }
+impl Engine {
+    fn new(mut program: Program, heap_size: usize, max_heap_cells: i32, stack_size: i32) -> Self {
+        let initial_frames = VecDeque::<CallFrame>::from([Self::make_entry_frame(&program)]);
+        let (initial_heap, initial_status) = Self::preload_heap(&mut program, heap_size, max_heap_cells);
 
         let initial_stack_size = stack_size as usize;
         let mut initial_stack_mem = Vec::<Value>::with_capacity(initial_stack_size);
         initial_stack_mem.resize(initial_stack_size, Value::Empty());
 
         Self {
+            program,
             heap: initial_heap,
+            heap_size,
+            max_heap_cells,
             frames: initial_frames,
             stack: initial_stack_mem,
             procs_view: null(),
+            natives_view: null(),
             rpp: null(),
             rip: 0,
             rbp: 0,
             rsp: -1,
             stack_limit: stack_size,
-            status: ExecStatus::Ok,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            trace: false,
+            checked_arithmetic: false,
+            interactive: true,
+            compact_heap: false,
+            status: initial_status,
+            exit_code: 1,
+            output: Box::new(io::stdout()),
+            diagnostics: Box::new(io::stderr()),
+        }
+    }
+
+    fn make_entry_frame(program: &Program) -> CallFrame {
+        CallFrame {
+            caller_id: program.get_entry_procedure_id().unwrap_or(-1),
+            caller_pos: 0,
+            old_rbp: 0,
+            opt_instance: -1,
+        }
+    }
+
+    /// NOTE: Makes a fresh heap holding the program's preloadables, which are moved out of the program. A heap too small for all of them gives `ExecStatus::HeapExhausted`, which `run` reports before executing anything.
+    fn preload_heap(program: &mut Program, heap_size: usize, max_heap_cells: i32) -> (ObjectHeap, ExecStatus) {
+        let mut temp_heap = ObjectHeap::new(heap_size);
+        temp_heap.set_cell_limit(max_heap_cells);
+
+        for temp_heap_val in program.get_heap_preloadables_mut() {
+            let temp_cell_id = temp_heap.try_create_cell(temp_heap_val.get_object_tag());
+
+            let Some(temp_cell_ref) = temp_heap.get_cell_mut(temp_cell_id) else {
+                return (temp_heap, ExecStatus::HeapExhausted);
+            };
+
+            *temp_cell_ref.get_value_mut() = std::mem::take(temp_heap_val);
+
+            // NOTE: preloaded literals are owned by the program itself, so they are pinned against sweeps.
+            temp_heap.retain_cell(temp_cell_id);
         }
+
+        (temp_heap, ExecStatus::Ok)
+    }
+
+    /// NOTE: Swaps in another program to run with the same settings & sinks, e.g for REPLs or test harnesses. The stack, frames, & registers start over like a freshly built engine's, and the heap is rebuilt from the new program's preloadables, so nothing of the previous run leaks into the next one.
+    pub fn reset(&mut self, mut program: Program) {
+        let (next_heap, next_status) = Self::preload_heap(&mut program, self.heap_size, self.max_heap_cells);
+
+        self.frames.clear();
+        self.frames.push_back(Self::make_entry_frame(&program));
+        self.stack.fill(Value::Empty());
+        self.heap = next_heap;
+        self.program = program;
+        self.procs_view = null();
+        self.natives_view = null();
+        self.rpp = null();
+        self.rip = 0;
+        self.rbp = 0;
+        self.rsp = -1;
+        self.status = next_status;
+        self.exit_code = 1;
+    }
+
+    /// NOTE: Replaces the default stdout / stderr sinks, e.g for embedders capturing program output.
+    pub fn set_output_sinks(&mut self, output_arg: Box<dyn Write>, diagnostics_arg: Box<dyn Write>) {
+        self.output = output_arg;
+        self.diagnostics = diagnostics_arg;
+    }
+
+    pub fn get_exit_code(&self) -> i32 {
+        self.exit_code
+    }
+
+    pub fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    pub fn get_output_mut(&mut self) -> &mut dyn Write {
+        self.output.as_mut()
     }
 
+    pub fn report_diagnostic(&mut self, msg: &str) {
+        let _ = writeln!(self.diagnostics, "{msg}");
+    }
+
+    /// NOTE: Only a bounded batch of doomed cells is collected per ripe check, which amortizes reclamation across instructions.
     fn try_sweep(&mut self) {
+        if self.compact_heap && self.heap.is_fragmented() {
+            self.compact_heap_objects();
+            return;
+        }
+
         if !self.heap.is_ripe_for_sweep() {
             return;
         }
 
-        for val in &mut self.stack {
-            if let Value::HeapRef(object_id) = val {
-                self.heap.try_collect_cell(*object_id);
+        self.heap.sweep_doomed(SWEEP_BATCH_LIMIT);
+    }
+
+    /// NOTE: Collects every doomed cell before compacting the heap, then moves each `HeapRef` held on the stack or by a frame to its object's new heap ID.
+    fn compact_heap_objects(&mut self) {
+        self.heap.sweep_doomed(usize::MAX);
+
+        let new_heap_ids = self.heap.compact();
+
+        for slot_value in self.stack.iter_mut().take((self.rsp + 1) as usize) {
+            if let Value::HeapRef(object_id) = slot_value {
+                *object_id = new_heap_ids[*object_id as usize];
+            }
+        }
+
+        for frame in self.frames.iter_mut() {
+            if frame.opt_instance != -1 {
+                frame.opt_instance = new_heap_ids[frame.opt_instance as usize];
             }
         }
     }
 
+    fn retain_value(&mut self, value: &Value) {
+        if let Value::HeapRef(object_id) = value {
+            self.heap.retain_cell(*object_id);
+        }
+    }
+
+    fn release_value(&mut self, value: &Value) {
+        if let Value::HeapRef(object_id) = value {
+            self.heap.release_cell(*object_id);
+        }
+    }
+
+    /// NOTE: Drops the references held by stack slots `from_slot..=rsp` before a frame discards them.
+    fn release_stack_above(&mut self, from_slot: i32) {
+        for slot_it in from_slot.max(0)..=self.rsp {
+            let slot_value = *self.stack.get(slot_it as usize).unwrap();
+            self.release_value(&slot_value);
+        }
+    }
+
     fn last_sweep(&mut self) {
         self.heap.force_collect_all();
     }
@@ -154,41 +404,105 @@ impl Engine {
         None
     }
 
+    /// NOTE: Fails the program with `ExecStatus::HeapExhausted` once an allocation finds no free cell even after collecting all doomed cells.
+    fn report_heap_exhausted(&mut self, object_kind: &str) {
+        self.report_diagnostic(format!("RunError: heap ran out of cells for a new {object_kind}, as all {} of {} cells are live.", self.heap.get_live_count(), self.heap.get_cell_limit()).as_str());
+        self.status = ExecStatus::HeapExhausted;
+    }
+
     fn make_heap_value(&mut self, tag: ObjectTag) -> bool {
         match tag {
             ObjectTag::Varchar => {
                 let temp_obj_id = self.heap.try_create_cell(tag);
 
-                if temp_obj_id != -1 {
-                    self.push_in(Value::HeapRef(temp_obj_id));
+                if temp_obj_id == -1 {
+                    self.report_heap_exhausted("varchar");
+                    return false;
                 }
 
-                temp_obj_id != -1
+                self.push_in(Value::HeapRef(temp_obj_id));
+
+                true
+            },
+            _ => {
+                self.status = ExecStatus::RefError;
+                false
             },
-            _ => false,
         }
     }
 
+    /// NOTE: Gives the preloaded varchar of a host string by its name, see `Program::add_host_string`.
+    pub fn find_host_string(&mut self, name: &str) -> Option<String> {
+        let host_heap_id = self.program.find_host_string_id(name)?;
+
+        self.fetch_heap_value_by((ArgMode::HeapId, host_heap_id))
+            .and_then(|host_value| host_value.try_varchar_view())
+            .map(String::from)
+    }
+
+    /// NOTE: Lets natives return fresh varchars by allocating & filling a heap cell, then pushing its reference.
+    pub fn push_new_varchar(&mut self, text: &str) -> bool {
+        let temp_obj_id = self.heap.try_create_cell(ObjectTag::Varchar);
+
+        if !self.heap.preload_cell_at(temp_obj_id, HeapValue::Varchar(String::from(text))) {
+            self.report_heap_exhausted("varchar");
+            return false;
+        }
+
+        self.push_in(Value::HeapRef(temp_obj_id));
+
+        true
+    }
+
+    /// NOTE: Lets natives return fresh buffers by allocating a heap cell holding the given items, then pushing its reference. The items must not be heap references, as buffers don't own what they hold.
+    pub fn push_new_array(&mut self, items: Vec<Value>) -> bool {
+        let temp_obj_id = self.heap.try_create_cell(ObjectTag::Array);
+
+        if !self.heap.preload_cell_at(temp_obj_id, HeapValue::Array(items)) {
+            self.report_heap_exhausted("buffer");
+            return false;
+        }
+
+        self.push_in(Value::HeapRef(temp_obj_id));
+
+        true
+    }
+
+    /// NOTE: Lets natives return fresh instances e.g copies by allocating a heap cell holding the given fields, then pushing its reference. Each field's object gains an owner first, so a sweep run by the allocation can't collect it.
+    pub fn push_new_instance(&mut self, fields: Vec<Value>) -> bool {
+        for field_value in &fields {
+            self.retain_value(field_value);
+        }
+
+        let temp_obj_id = self.heap.try_create_cell(ObjectTag::Instance);
+
+        if temp_obj_id == -1 {
+            for field_value in &fields {
+                self.release_value(field_value);
+            }
+
+            self.report_heap_exhausted("class instance");
+            return false;
+        }
+
+        self.heap.preload_cell_at(temp_obj_id, HeapValue::Instance(fields));
+        self.push_in(Value::HeapRef(temp_obj_id));
+
+        true
+    }
+
     pub fn push_in(&mut self, temp: Value) {
         self.rsp += 1;
 
-        if self.rsp > self.stack_limit {
-            eprintln!(
-                "RunError: rsp too large: {}",
-                self.rsp
-            );
+        if self.rsp >= self.stack_limit {
+            self.report_diagnostic(format!("RunError: rsp too large: {}", self.rsp).as_str());
             self.status = ExecStatus::AccessError;
             return;
         }
 
-        if let Value::HeapRef(object_id) = &temp {
-            let object_ref_opt = self.heap.get_cell_mut(*object_id);
-
-            if let Some(object_ref) = object_ref_opt {
-                object_ref.inc_rc();
-            } else {
-                self.status = ExecStatus::RefError;
-            }
+        if let Value::HeapRef(object_id) = &temp
+            && !self.heap.retain_cell(*object_id) {
+            self.status = ExecStatus::RefError;
         }
 
         unsafe {
@@ -198,24 +512,83 @@ impl Engine {
 
     pub fn pop_off(&mut self) -> Option<Value> {
         if self.rsp < 0 {
-            self.status = ExecStatus::AccessError;
+            self.status = ExecStatus::StackUnderflow;
             return None;
         }
 
-        let temp_value = self.stack.get_mut(self.rsp as usize).unwrap();
+        let temp_value = *self.stack.get(self.rsp as usize).unwrap();
         self.rsp -= 1;
 
-        if let Value::HeapRef(object_id) = temp_value {
-            let object_ref_opt = self.heap.get_cell_mut(*object_id);
+        if let Value::HeapRef(object_id) = &temp_value
+            && !self.heap.release_cell(*object_id) {
+            self.status = ExecStatus::RefError;
+        }
 
-            if let Some(object_ref) = object_ref_opt {
-                object_ref.dec_rc();
-            } else {
-                self.status = ExecStatus::RefError;
+        Some(temp_value)
+    }
+
+    /// NOTE: Lets a native run a Loxie function by its procedure ID e.g from a `callback` argument, giving back its result. The callee's frame returns into the native's `NATIVE_CALL`, so dispatching stops right there. A failed call gives `Value::Empty()` with the engine status set, which the running native keeps.
+    /// Natives should pop their heap arguments after any callbacks, as a sweep during the callback may collect unreferenced objects or move them to new heap IDs.
+    pub fn call_function(&mut self, proc_id: i32, args: &[Value]) -> Value {
+        let proc_count = self.program.get_procedures().len() as i32;
+
+        if self.natives_view.is_null() || proc_id < 0 || proc_id >= proc_count {
+            self.report_diagnostic(format!("RunError: invalid callback ID {proc_id} found, as only proc-0 to proc-{} exist.", proc_count - 1).as_str());
+            self.status = ExecStatus::BadArgs;
+            return Value::Empty();
+        }
+
+        let Some(callback_arity) = self.program.find_function_arity(proc_id) else {
+            self.report_diagnostic(format!("RunError: callback proc-{proc_id} is not a plain function.").as_str());
+            self.status = ExecStatus::BadArgs;
+            return Value::Empty();
+        };
+
+        if args.len() as i32 != callback_arity {
+            self.report_diagnostic(format!("RunError: callback proc-{proc_id} takes {callback_arity} argument(s), but {} were given.", args.len()).as_str());
+            self.status = ExecStatus::BadArgs;
+            return Value::Empty();
+        }
+
+        if self.frames.len() >= self.max_call_depth {
+            self.status = ExecStatus::CallDepthExceeded;
+            return Value::Empty();
+        }
+
+        for arg in args {
+            self.push_in(*arg);
+        }
+
+        if self.status != ExecStatus::Ok {
+            return Value::Empty();
+        }
+
+        let outer_depth = self.frames.len();
+
+        unsafe {
+            let eff_caller_rpid = self.rpp.offset_from(self.procs_view) as i32;
+
+            self.frames.push_back(CallFrame {
+                caller_id: eff_caller_rpid,
+                caller_pos: self.rip,
+                old_rbp: self.rbp,
+                opt_instance: -1,
+            });
+
+            self.rpp = self.procs_view.offset(proc_id as isize);
+            self.rip = 0;
+            self.rbp = self.rsp + 1 - args.len() as i32;
+
+            if self.check_frame_room() {
+                self.dispatch_until(&*self.natives_view, outer_depth);
             }
         }
 
-        Some(*temp_value)
+        if self.status != ExecStatus::Ok {
+            return Value::Empty();
+        }
+
+        self.pop_off().unwrap_or(Value::Empty())
     }
 
     fn do_load_const(&mut self, const_id: bytecode::Argument) {
@@ -229,16 +602,19 @@ impl Engine {
     fn do_push(&mut self, source: bytecode::Argument) {
         let pushing_item_opt = self.fetch_value_by(source);
 
-        if self.rsp > self.stack_limit {
+        if self.rsp + 1 >= self.stack_limit {
             self.status = ExecStatus::AccessError;
             return;
         }
 
         if let Some(val) = pushing_item_opt {
-            *self.stack.get_mut((self.rsp + 1) as usize).unwrap() = *val;
+            let pushing_item = *val;
+            self.retain_value(&pushing_item);
+            *self.stack.get_mut((self.rsp + 1) as usize).unwrap() = pushing_item;
             self.rsp += 1;
             self.rip += 1;
         } else if source.0 == ArgMode::HeapId {
+            self.heap.retain_cell(source.1);
             *self.stack.get_mut((self.rsp + 1) as usize).unwrap() = Value::HeapRef(source.1);
             self.rsp += 1;
             self.rip += 1;
@@ -248,15 +624,24 @@ impl Engine {
     }
 
     fn do_pop(&mut self) {
-        if self.rsp <= 0 {
-            self.status = ExecStatus::ValueError;
+        if self.rsp < 0 {
+            self.status = ExecStatus::StackUnderflow;
             return;
         }
 
+        let popped_value = *self.stack.get(self.rsp as usize).unwrap();
+        self.release_value(&popped_value);
         self.rsp -= 1;
         self.rip += 1;
     }
 
+    /// NOTE: Bool literals are pushed without the constant pool, so they take no constant slots.
+    fn do_push_bool(&mut self, flag: bool) {
+        self.push_in(Value::Bool(flag));
+
+        self.rip += 1;
+    }
+
     fn do_make_heap_value(&mut self, arg: bytecode::Argument) {
         let arg_tag = match arg.1 {
             0 => ObjectTag::Varchar,
@@ -264,7 +649,6 @@ impl Engine {
         };
         
         if !self.make_heap_value(arg_tag) {
-            self.status = ExecStatus::RefError;
             return;
         }
 
@@ -280,12 +664,12 @@ impl Engine {
         let obj_id = self.heap.try_create_cell(ObjectTag::Instance);
 
         if !self.heap.preload_cell_at(obj_id, HeapValue::Instance(temp_fields)) {
-            self.status = ExecStatus::RefError;
-            eprintln!("RunError: invalid reference created for a class instance: heap-id-{obj_id}");
+            self.report_heap_exhausted("class instance");
             return;
         }
 
         self.push_in(Value::HeapRef(obj_id));
+        self.heap.retain_cell(obj_id);
 
         unsafe {
             self.frames.back_mut().unwrap_unchecked().opt_instance = obj_id;
@@ -308,27 +692,59 @@ impl Engine {
         let has_object_field = target.0 == ArgMode::InstanceFieldId && instance_heap_id != -1;
 
         if has_object_field {
-            unsafe {
-                let incoming_value_for_field = incoming_value_opt.unwrap_unchecked();
+            let incoming_value_for_field = unsafe { *incoming_value_opt.unwrap_unchecked() };
+            self.retain_value(&incoming_value_for_field);
 
-                *self.heap.get_cell_mut(instance_heap_id).unwrap().get_value_mut().try_ref_instance_field_mut(target_slot).unwrap() = *incoming_value_for_field;
-            }
+            let old_field_value = std::mem::replace(
+                self.heap.get_cell_mut(instance_heap_id).unwrap().get_value_mut().try_ref_instance_field_mut(target_slot).unwrap(),
+                incoming_value_for_field
+            );
+            self.release_value(&old_field_value);
         } else if target_mode == ArgMode::StackOffset {
             let abs_stack_slot = self.rbp + target_slot;
 
-            unsafe {
-                let incoming_value = incoming_value_opt.unwrap_unchecked();
-                *self.stack.get_unchecked_mut(abs_stack_slot as usize) = *incoming_value;
-            }
+            let incoming_value = unsafe { *incoming_value_opt.unwrap_unchecked() };
+            self.retain_value(&incoming_value);
+
+            let old_value = unsafe {
+                std::mem::replace(self.stack.get_unchecked_mut(abs_stack_slot as usize), incoming_value)
+            };
+            self.release_value(&old_value);
         } else {
             self.status = ExecStatus::BadArgs;
-            eprintln!("RunError: invalid argument for REPLACE instruction.");
+            self.report_diagnostic("RunError: invalid argument for REPLACE instruction.");
             return;
         }
 
         self.rip += 1;
     }
 
+    /// NOTE: Pushes a field's value of the instance referenced by a stack slot e.g `other.value`, whereas `InstanceFieldId` arguments only reach the current frame's instance.
+    fn do_get_field(&mut self, instance_slot: bytecode::Argument, field_id: bytecode::Argument) {
+        if instance_slot.0 != ArgMode::StackOffset || field_id.0 != ArgMode::InstanceFieldId {
+            self.status = ExecStatus::BadArgs;
+            self.report_diagnostic("RunError: invalid argument for GET_FIELD instruction.");
+            return;
+        }
+
+        let instance_heap_id = match self.fetch_stack_temp(instance_slot.1) {
+            Value::HeapRef(heap_id) => *heap_id,
+            _ => -1,
+        };
+
+        let field_value_opt = self.heap.get_cell(instance_heap_id)
+            .and_then(|cell_ref| cell_ref.get_value().try_ref_instance_field(field_id.1))
+            .copied();
+
+        if let Some(field_value) = field_value_opt {
+            self.push_in(field_value);
+            self.rip += 1;
+        } else {
+            self.status = ExecStatus::RefError;
+            self.report_diagnostic(format!("RunError: GET_FIELD found no field #{} of an instance at heap-id-{}.", field_id.1, instance_heap_id).as_str());
+        }
+    }
+
     fn do_neg(&mut self, target: bytecode::Argument) {
         if target.0 != ArgMode::StackOffset {
             self.status = ExecStatus::AccessError;
@@ -337,8 +753,35 @@ impl Engine {
 
         let target_slot = self.rbp + target.1;
 
-        unsafe {
-            self.stack.get_unchecked_mut(target_slot as usize).negate();
+        let step_result = unsafe {
+            let target_ref = self.stack.get_unchecked_mut(target_slot as usize);
+
+            if self.checked_arithmetic {
+                target_ref.checked_negate()
+            } else {
+                target_ref.negate();
+                Ok(())
+            }
+        };
+
+        if !self.help_check_step_result(step_result) {
+            return;
+        }
+
+        self.rip += 1;
+    }
+
+    fn do_not(&mut self) {
+        if self.rsp < 0 {
+            self.status = ExecStatus::StackUnderflow;
+            return;
+        }
+
+        if let Some(Value::Bool(flag)) = self.stack.get_mut(self.rsp as usize) {
+            *flag = !*flag;
+        } else {
+            self.status = ExecStatus::ValueError;
+            return;
         }
 
         self.rip += 1;
@@ -352,10 +795,19 @@ impl Engine {
 
         let target_slot = self.rbp + target.1;
 
-        unsafe {
-            self.stack
-                .get_unchecked_mut(target_slot as usize)
-                .increment();
+        let step_result = unsafe {
+            let target_ref = self.stack.get_unchecked_mut(target_slot as usize);
+
+            if self.checked_arithmetic {
+                target_ref.checked_increment()
+            } else {
+                target_ref.increment();
+                Ok(())
+            }
+        };
+
+        if !self.help_check_step_result(step_result) {
+            return;
         }
 
         self.rip += 1;
@@ -369,103 +821,196 @@ impl Engine {
 
         let target_slot = self.rbp + target.1;
 
-        unsafe {
-            self.stack
-                .get_unchecked_mut(target_slot as usize)
-                .decrement();
+        let step_result = unsafe {
+            let target_ref = self.stack.get_unchecked_mut(target_slot as usize);
+
+            if self.checked_arithmetic {
+                target_ref.checked_decrement()
+            } else {
+                target_ref.decrement();
+                Ok(())
+            }
+        };
+
+        if !self.help_check_step_result(step_result) {
+            return;
         }
 
         self.rip += 1;
     }
 
+    /// NOTE: Aborts with `BadMath` once a checked in-place step like `++` overflows its int.
+    fn help_check_step_result(&mut self, result: Result<(), ArithError>) -> bool {
+        if result.is_err() {
+            self.report_diagnostic("RunError: int overflow in checked arithmetic.");
+            self.status = ExecStatus::BadMath;
+            return false;
+        }
+
+        true
+    }
+
+    /// NOTE: Pushes a checked arithmetic result, or aborts with `BadMath` for illegal math and `ValueError` for mismatched operands.
+    fn help_push_arith_result(&mut self, result: Result<Value, ArithError>) -> bool {
+        match result {
+            Ok(result_value) => {
+                self.push_in(result_value);
+                true
+            },
+            Err(ArithError::ZeroDivision) => {
+                self.status = ExecStatus::BadMath;
+                false
+            },
+            Err(ArithError::Overflow) => {
+                self.report_diagnostic("RunError: int overflow in checked arithmetic.");
+                self.status = ExecStatus::BadMath;
+                false
+            },
+            Err(ArithError::TypeMismatch) => {
+                self.status = ExecStatus::ValueError;
+                false
+            },
+        }
+    }
+
+    /// NOTE: Checks that the current frame holds `count` operands before an op pops any, so a failing op leaves the stack unchanged.
+    fn has_frame_operands(&mut self, count: i32) -> bool {
+        if self.rsp - self.rbp + 1 < count {
+            self.status = ExecStatus::StackUnderflow;
+            return false;
+        }
+
+        true
+    }
+
     fn do_add(&mut self) {
+        if !self.has_frame_operands(2) {
+            return;
+        }
+
         let rhs_temp = self.pop_off();
         let lhs_temp = self.pop_off();
 
         if lhs_temp.is_none() || rhs_temp.is_none() {
-            self.status = ExecStatus::AccessError;
+            self.status = ExecStatus::StackUnderflow;
             return;
         }
 
         unsafe {
-            let lhs_value = lhs_temp
-                .unwrap_unchecked()
-                .add(rhs_temp.as_ref().unwrap_unchecked());
-            self.push_in(lhs_value);
+            let lhs_operand = lhs_temp.unwrap_unchecked();
+            let rhs_operand = rhs_temp.as_ref().unwrap_unchecked();
+
+            if self.checked_arithmetic {
+                if !self.help_push_arith_result(lhs_operand.checked_add(rhs_operand)) {
+                    return;
+                }
+            } else {
+                self.push_in(lhs_operand.add(rhs_operand));
+            }
         }
 
         self.rip += 1;
     }
 
     fn do_sub(&mut self) {
+        if !self.has_frame_operands(2) {
+            return;
+        }
+
         let rhs_temp = self.pop_off();
         let lhs_temp = self.pop_off();
 
         if lhs_temp.is_none() || rhs_temp.is_none() {
-            self.status = ExecStatus::AccessError;
+            self.status = ExecStatus::StackUnderflow;
             return;
         }
 
         unsafe {
-            let lhs_value = lhs_temp
-                .unwrap_unchecked()
-                .sub(rhs_temp.as_ref().unwrap_unchecked());
-            self.push_in(lhs_value);
+            let lhs_operand = lhs_temp.unwrap_unchecked();
+            let rhs_operand = rhs_temp.as_ref().unwrap_unchecked();
+
+            if self.checked_arithmetic {
+                if !self.help_push_arith_result(lhs_operand.checked_sub(rhs_operand)) {
+                    return;
+                }
+            } else {
+                self.push_in(lhs_operand.sub(rhs_operand));
+            }
         }
 
         self.rip += 1;
     }
 
     fn do_mul(&mut self) {
+        if !self.has_frame_operands(2) {
+            return;
+        }
+
         let rhs_temp = self.pop_off();
         let lhs_temp = self.pop_off();
 
         if lhs_temp.is_none() || rhs_temp.is_none() {
-            self.status = ExecStatus::AccessError;
+            self.status = ExecStatus::StackUnderflow;
             return;
         }
 
         unsafe {
-            let lhs_value = lhs_temp
-                .unwrap_unchecked()
-                .mul(rhs_temp.as_ref().unwrap_unchecked());
-            self.push_in(lhs_value);
+            let lhs_operand = lhs_temp.unwrap_unchecked();
+            let rhs_operand = rhs_temp.as_ref().unwrap_unchecked();
+
+            if self.checked_arithmetic {
+                if !self.help_push_arith_result(lhs_operand.checked_mul(rhs_operand)) {
+                    return;
+                }
+            } else {
+                self.push_in(lhs_operand.mul(rhs_operand));
+            }
         }
 
         self.rip += 1;
     }
 
     fn do_div(&mut self) {
+        if !self.has_frame_operands(2) {
+            return;
+        }
+
         let rhs_temp = self.pop_off();
         let lhs_temp = self.pop_off();
 
         if lhs_temp.is_none() || rhs_temp.is_none() {
-            self.status = ExecStatus::AccessError;
+            self.status = ExecStatus::StackUnderflow;
             return;
         }
 
         unsafe {
-            let lhs_value = lhs_temp
-                .unwrap_unchecked()
-                .div(rhs_temp.as_ref().unwrap_unchecked());
+            let lhs_operand = lhs_temp.unwrap_unchecked();
+            let rhs_operand = rhs_temp.as_ref().unwrap_unchecked();
 
-            if let Value::Empty() = lhs_value {
-                self.status = ExecStatus::BadMath;
+            let lhs_value_res = if self.checked_arithmetic {
+                lhs_operand.checked_div(rhs_operand)
+            } else {
+                lhs_operand.div(rhs_operand)
+            };
+
+            if !self.help_push_arith_result(lhs_value_res) {
                 return;
             }
-
-            self.push_in(lhs_value);
         }
 
         self.rip += 1;
     }
 
     fn do_cmp_eq(&mut self) {
+        if !self.has_frame_operands(2) {
+            return;
+        }
+
         let rhs_temp = self.pop_off();
         let lhs_temp = self.pop_off();
 
         if lhs_temp.is_none() || rhs_temp.is_none() {
-            self.status = ExecStatus::AccessError;
+            self.status = ExecStatus::StackUnderflow;
             return;
         }
 
@@ -481,11 +1026,15 @@ impl Engine {
     }
 
     fn do_cmp_ne(&mut self) {
+        if !self.has_frame_operands(2) {
+            return;
+        }
+
         let rhs_temp = self.pop_off();
         let lhs_temp = self.pop_off();
 
         if lhs_temp.is_none() || rhs_temp.is_none() {
-            self.status = ExecStatus::AccessError;
+            self.status = ExecStatus::StackUnderflow;
             return;
         }
 
@@ -501,11 +1050,15 @@ impl Engine {
     }
 
     fn do_cmp_lt(&mut self) {
+        if !self.has_frame_operands(2) {
+            return;
+        }
+
         let rhs_temp = self.pop_off();
         let lhs_temp = self.pop_off();
 
         if lhs_temp.is_none() || rhs_temp.is_none() {
-            self.status = ExecStatus::AccessError;
+            self.status = ExecStatus::StackUnderflow;
             return;
         }
 
@@ -521,11 +1074,15 @@ impl Engine {
     }
 
     fn do_cmp_gt(&mut self) {
+        if !self.has_frame_operands(2) {
+            return;
+        }
+
         let rhs_temp = self.pop_off();
         let lhs_temp = self.pop_off();
 
         if lhs_temp.is_none() || rhs_temp.is_none() {
-            self.status = ExecStatus::AccessError;
+            self.status = ExecStatus::StackUnderflow;
             return;
         }
 
@@ -540,7 +1097,66 @@ impl Engine {
         self.rip += 1;
     }
 
+    fn do_cmp_le(&mut self) {
+        if !self.has_frame_operands(2) {
+            return;
+        }
+
+        let rhs_temp = self.pop_off();
+        let lhs_temp = self.pop_off();
+
+        if lhs_temp.is_none() || rhs_temp.is_none() {
+            self.status = ExecStatus::StackUnderflow;
+            return;
+        }
+
+        unsafe {
+            let cmp_value = lhs_temp
+                .unwrap_unchecked()
+                .is_lesser_equal(rhs_temp.as_ref().unwrap_unchecked());
+
+            self.push_in(Value::Bool(cmp_value));
+        }
+
+        self.rip += 1;
+    }
+
+    fn do_cmp_ge(&mut self) {
+        if !self.has_frame_operands(2) {
+            return;
+        }
+
+        let rhs_temp = self.pop_off();
+        let lhs_temp = self.pop_off();
+
+        if lhs_temp.is_none() || rhs_temp.is_none() {
+            self.status = ExecStatus::StackUnderflow;
+            return;
+        }
+
+        unsafe {
+            let cmp_value = lhs_temp
+                .unwrap_unchecked()
+                .is_greater_equal(rhs_temp.as_ref().unwrap_unchecked());
+
+            self.push_in(Value::Bool(cmp_value));
+        }
+
+        self.rip += 1;
+    }
+
+    /// NOTE: Only the top stack slot is a checked temporary, so tested constants, fields, or variables below it are never popped.
+    fn is_top_temp(&self, test: bytecode::Argument) -> bool {
+        test.0 == ArgMode::StackOffset && self.rbp + test.1 == self.rsp
+    }
+
     fn do_jump_if(&mut self, test: bytecode::Argument, jump_to: bytecode::Argument) {
+        // NOTE: a checked temporary is popped after jumping, so an empty stack must abort before any `rsp` change. Other tests pop nothing.
+        if test.0 == ArgMode::StackOffset && self.rsp < 0 {
+            self.status = ExecStatus::StackUnderflow;
+            return;
+        }
+
         let test_value = self.fetch_value_by(test);
 
         if test_value.is_none() {
@@ -563,10 +1179,17 @@ impl Engine {
             }
         }
 
-        self.rsp -= 1;
+        if self.is_top_temp(test) {
+            self.rsp -= 1;
+        }
     }
 
     fn do_jump_else(&mut self, test: bytecode::Argument, jump_to: bytecode::Argument) {
+        if test.0 == ArgMode::StackOffset && self.rsp < 0 {
+            self.status = ExecStatus::StackUnderflow;
+            return;
+        }
+
         let test_value = self.fetch_value_by(test);
 
         if test_value.is_none() {
@@ -589,7 +1212,10 @@ impl Engine {
             }
         }
 
-        self.rsp -= 1; // Pop temporary boolean value that was checked...
+        // Pop temporary boolean value that was checked...
+        if self.is_top_temp(test) {
+            self.rsp -= 1;
+        }
     }
 
     fn do_jump(&mut self, jump_to: bytecode::Argument) {
@@ -609,8 +1235,10 @@ impl Engine {
             return;
         }
 
-        let result_temp = result_value_opt.unwrap();
-        *self.stack.get_mut(self.rbp as usize).unwrap() = *result_temp;
+        let result_temp = *result_value_opt.unwrap();
+        self.retain_value(&result_temp);
+        self.release_stack_above(self.rbp);
+        *self.stack.get_mut(self.rbp as usize).unwrap() = result_temp;
 
         let returning_frame = self.frames.back().unwrap();
 
@@ -624,7 +1252,7 @@ impl Engine {
         let leaving_instance_ref = self.frames.back().unwrap().opt_instance;
 
         if leaving_instance_ref != -1 {
-            self.heap.get_cell_mut(leaving_instance_ref).unwrap().dec_rc();
+            self.heap.release_cell(leaving_instance_ref);
         }
 
         self.rbp = returning_frame.old_rbp;
@@ -640,13 +1268,32 @@ impl Engine {
         }
 
         self.rip = self.frames.back().unwrap().caller_pos;
+        self.release_stack_above(self.rbp);
+
+        // NOTE: the frame's own reference to the new instance is only dropped after the pushed result takes its place.
         self.rsp = self.rbp - 1;
         self.push_in(Value::HeapRef(heap_ref_id));
+        self.heap.release_cell(heap_ref_id);
 
         self.rbp = self.frames.back().unwrap().old_rbp;
         self.frames.pop_back();
     }
 
+    /// NOTE: Checks that the whole frame of the procedure being entered fits in the stack from RBP, so a call without room fails before its body runs.
+    fn check_frame_room(&mut self) -> bool {
+        let frame_slots = unsafe { (&*self.rpp).get_frame_slots() };
+
+        if self.rbp + frame_slots > self.stack_limit {
+            let proc_id = unsafe { self.rpp.offset_from(self.procs_view) };
+            self.report_diagnostic(format!("RunError: proc-{proc_id} needs {frame_slots} stack slots from RBP = {}, but the stack only has {} slots.", self.rbp, self.stack_limit).as_str());
+            self.status = ExecStatus::AccessError;
+
+            return false;
+        }
+
+        true
+    }
+
     fn do_call(&mut self, procedure_id: bytecode::Argument, arg_count: bytecode::Argument) {
         let (proc_arg_mode, proc_id) = procedure_id;
 
@@ -655,6 +1302,11 @@ impl Engine {
             return;
         }
 
+        if self.frames.len() >= self.max_call_depth {
+            self.status = ExecStatus::CallDepthExceeded;
+            return;
+        }
+
         let (_, pending_arg_count) = arg_count;
 
         let ret_instruction_pos = self.rip + 1;
@@ -672,8 +1324,11 @@ impl Engine {
             self.rpp = self.procs_view.offset(proc_id as isize);
         }
 
+        // NOTE: arguments were already retained when pushed, so they stay alive as the callee's locals until `do_return()` releases them.
         self.rip = 0;
         self.rbp = self.rsp + 1 - pending_arg_count;
+
+        self.check_frame_room();
     }
 
     fn do_instance_call(&mut self, instance_arg: bytecode::Argument, fun_id_arg: bytecode::Argument, args_n: bytecode::Argument) {
@@ -692,12 +1347,17 @@ impl Engine {
             _ => -1,
         };
 
+        if self.frames.len() >= self.max_call_depth {
+            self.status = ExecStatus::CallDepthExceeded;
+            return;
+        }
+
         if instance_heap_id == -1 {
             self.status = ExecStatus::RefError;
-            eprintln!("RunError: invalid instance ID found for method call.");
+            self.report_diagnostic("RunError: invalid instance ID found for method call.");
             return;
         } else {
-            self.heap.get_cell_mut(instance_heap_id).unwrap().inc_rc();
+            self.heap.retain_cell(instance_heap_id);
         }
 
         let (proc_arg_mode, proc_id) = fun_id_arg;
@@ -725,34 +1385,52 @@ impl Engine {
                 opt_instance: instance_heap_id,
             });
         }
+
+        self.check_frame_room();
     }
 
     fn do_native_call(&mut self, natives: &Bundle, native_arg: bytecode::Argument) {
-        unsafe {
-            self.status = natives.get_native(native_arg.1)(self);
+        let native_status = unsafe { natives.get_native(native_arg.1)(self) };
+
+        // NOTE: a failure within a callback run by `call_function` stays, even if the native itself reports no error.
+        if self.status == ExecStatus::Ok {
+            self.status = if native_status == ExecStatus::Recoverable { ExecStatus::Ok } else { native_status };
         }
 
         self.rip += 1;
     }
 
-    fn is_done(&mut self) -> bool {
-        self.frames.is_empty() || self.status != ExecStatus::Ok
+    fn is_done(&mut self, stop_depth: usize) -> bool {
+        self.frames.len() <= stop_depth || self.status != ExecStatus::Ok
     }
 
-    pub fn run(&mut self, program: &Program, natives: &Bundle) -> ExecStatus {
-        if program.get_entry_procedure_id().is_none() {
-            println!("RunNote: No main procedure found.");
-            return ExecStatus::Ok;
-        }
-
+    /// # SAFETY
+    /// The procedure & native pointers must be set up by `run` first. Dispatching stops once the call frames shrink to `stop_depth` or execution fails.
+    unsafe fn dispatch_until(&mut self, natives: &Bundle, stop_depth: usize) {
         unsafe {
-            let main_id = program.get_entry_procedure_id().unwrap();
-            self.procs_view = program.get_procedures().as_ptr();
-            self.rpp = self.procs_view.offset(main_id as isize);
+            while !self.is_done(stop_depth) {
+                let proc_code = (&*self.rpp).get_chunk().get_code();
+
+                // NOTE: codegen ends every procedure in a `RETURN` or `LEAVE`, so this overrun check only runs in debug builds to catch emitter bugs.
+                if cfg!(debug_assertions) && self.rip as usize >= proc_code.len() {
+                    self.report_diagnostic(format!("RunError: RIP = {} ran past the {} instructions of proc-{}.", self.rip, proc_code.len(), self.rpp.offset_from(self.procs_view)).as_str());
+                    self.status = ExecStatus::IllegalInstruction;
+                    break;
+                }
 
-            while !self.is_done() {
-                let next_instr = (&*self.rpp).get_chunk().get_code().get_unchecked(self.rip as usize);
-                // println!("RIP = {}, RBP = {}, RSP = {}", self.rip, self.rbp, self.rsp); // debug
+                // NOTE: codegen sizes each frame up front, so using more slots than that is also an emitter bug to catch in debug builds.
+                if cfg!(debug_assertions) && self.rsp - self.rbp + 1 > (&*self.rpp).get_frame_slots() {
+                    self.report_diagnostic(format!("RunError: RSP = {} went past the {} frame slots of proc-{} from RBP = {}.", self.rsp, (&*self.rpp).get_frame_slots(), self.rpp.offset_from(self.procs_view), self.rbp).as_str());
+                    self.status = ExecStatus::IllegalInstruction;
+                    break;
+                }
+
+                let next_instr = proc_code.get_unchecked(self.rip as usize);
+
+                if self.trace {
+                    let trace_proc_id = self.rpp.offset_from(self.procs_view);
+                    self.report_diagnostic(format!("trace: proc-{trace_proc_id} RIP = {}, RBP = {}, RSP = {}: {}", self.rip, self.rbp, self.rsp, next_instr.get_name()).as_str());
+                }
 
                 match next_instr {
                     bytecode::Instruction::Nop => {
@@ -767,6 +1445,12 @@ impl Engine {
                     bytecode::Instruction::Pop => {
                         self.do_pop();
                     },
+                    bytecode::Instruction::PushTrue => {
+                        self.do_push_bool(true);
+                    },
+                    bytecode::Instruction::PushFalse => {
+                        self.do_push_bool(false);
+                    },
                     bytecode::Instruction::MakeHeapValue(tag_arg) => {
                         self.do_make_heap_value(*tag_arg);
                     },
@@ -776,9 +1460,15 @@ impl Engine {
                     bytecode::Instruction::Replace(target, source) => {
                         self.do_replace(*target, *source);
                     },
+                    bytecode::Instruction::GetField(instance_slot, field_id) => {
+                        self.do_get_field(*instance_slot, *field_id);
+                    },
                     bytecode::Instruction::Neg(target) => {
                         self.do_neg(*target);
                     },
+                    bytecode::Instruction::Not => {
+                        self.do_not();
+                    },
                     bytecode::Instruction::Inc(target) => {
                         self.do_inc(*target);
                     },
@@ -809,6 +1499,12 @@ impl Engine {
                     bytecode::Instruction::CompareGt => {
                         self.do_cmp_gt();
                     },
+                    bytecode::Instruction::CompareLe => {
+                        self.do_cmp_le();
+                    },
+                    bytecode::Instruction::CompareGe => {
+                        self.do_cmp_ge();
+                    },
                     bytecode::Instruction::JumpIf(test, jump_target) => {
                         self.do_jump_if(*test, *jump_target);
                     },
@@ -845,12 +1541,99 @@ impl Engine {
                 // println!();
             }
         }
+    }
+
+    pub fn get_status(&self) -> ExecStatus {
+        self.status
+    }
+
+    /// NOTE: Runs any procedure as the entry point with the given args until it returns, giving its result e.g for testing a single function from Rust. The args must match the procedure's arity, see `Program::find_function`. A failed run gives `Value::Empty()` with the status set, see `get_status`. Unlike `run`, the heap is kept afterwards so a `HeapRef` result stays valid until the next run.
+    pub fn run_procedure(&mut self, natives: &Bundle, proc_id: i32, args: &[Value]) -> Value {
+        let proc_count = self.program.get_procedures().len() as i32;
+
+        if proc_id < 0 || proc_id >= proc_count {
+            self.report_diagnostic(format!("RunError: invalid entry ID {proc_id} found, as only proc-0 to proc-{} exist.", proc_count - 1).as_str());
+            self.status = ExecStatus::BadArgs;
+            return Value::Empty();
+        }
+
+        if self.status == ExecStatus::HeapExhausted {
+            self.report_diagnostic(format!("RunError: heap is out of memory for the program's {} preloaded literals.", self.program.get_heap_preloadables().len()).as_str());
+            return Value::Empty();
+        }
+
+        // NOTE: the previous run's result is still on the stack, so its reference is dropped before starting over.
+        self.release_stack_above(0);
+        self.rsp = -1;
+        self.rbp = 0;
+        self.rip = 0;
+        self.status = ExecStatus::Ok;
+        self.frames.clear();
+        self.frames.push_back(CallFrame {
+            caller_id: proc_id,
+            caller_pos: 0,
+            old_rbp: 0,
+            opt_instance: -1,
+        });
+
+        for arg in args {
+            self.push_in(*arg);
+        }
+
+        unsafe {
+            self.procs_view = self.program.get_procedures().as_ptr();
+            self.natives_view = natives;
+            self.rpp = self.procs_view.offset(proc_id as isize);
+
+            if self.status == ExecStatus::Ok && self.check_frame_room() {
+                self.dispatch_until(natives, 0);
+            }
+        }
+
+        let _ = self.output.flush();
+        let _ = self.diagnostics.flush();
+
+        if self.status != ExecStatus::Ok {
+            return Value::Empty();
+        }
+
+        *self.stack.first().unwrap()
+    }
+
+    pub fn run(&mut self, natives: &Bundle) -> ExecStatus {
+        if self.program.get_entry_procedure_id().is_none() {
+            self.report_diagnostic("RunNote: No main procedure found.");
+            return ExecStatus::Ok;
+        }
+
+        if self.status == ExecStatus::HeapExhausted {
+            self.report_diagnostic(format!("RunError: heap is out of memory for the program's {} preloaded literals.", self.program.get_heap_preloadables().len()).as_str());
+            return self.status;
+        }
+
+        unsafe {
+            let main_id = self.program.get_entry_procedure_id().unwrap();
+            self.procs_view = self.program.get_procedures().as_ptr();
+            self.natives_view = natives;
+            self.rpp = self.procs_view.offset(main_id as isize);
+
+            if self.check_frame_room() {
+                self.dispatch_until(natives, 0);
+            }
+        }
 
         self.last_sweep();
 
+        let _ = self.output.flush();
+        let _ = self.diagnostics.flush();
+
         let main_result_code = self.stack.first().unwrap();
         let zero_ok = Value::Int(0);
 
+        if self.status == ExecStatus::Ok {
+            self.exit_code = if let Value::Int(main_result_n) = main_result_code { *main_result_n } else { 1 };
+        }
+
         if self.status == ExecStatus::Ok && !main_result_code.is_equal(&zero_ok) {
             self.status = ExecStatus::NotOk;
         }
@@ -858,3 +1641,167 @@ impl Engine {
         self.status
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::compiler::driver::CompilerMain;
+    use crate::utils::logging::LogLevel;
+
+    /// NOTE: Collects an engine's output in memory, so tests can check what a program printed after running it.
+    #[derive(Clone, Default)]
+    struct SharedSink(Rc<RefCell<Vec<u8>>>);
+
+    impl SharedSink {
+        fn text(&self) -> String {
+            String::from_utf8_lossy(&self.0.borrow()).into_owned()
+        }
+    }
+
+    impl Write for SharedSink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// NOTE: Compiles & runs a source against the given natives, giving the run's status with its collected output & diagnostics.
+    fn run_source(source: &str, natives: &Bundle) -> (ExecStatus, String, String) {
+        let mut test_compiler = CompilerMain::new("./test.loxie", source, natives.peek_registry());
+        test_compiler.set_log_level(LogLevel::Quiet);
+
+        let program = test_compiler.compile_from_start(crate::setup_lexical_items()).expect("test source should compile");
+        let output = SharedSink::default();
+        let diagnostics = SharedSink::default();
+
+        let mut engine = EngineBuilder::new().build(program);
+        engine.set_output_sinks(Box::new(output.clone()), Box::new(diagnostics.clone()));
+
+        let status = engine.run(natives);
+
+        (status, output.text(), diagnostics.text())
+    }
+
+    /// NOTE: Runs a hand-written entry procedure, so tests can feed the engine code the emitters would never produce. Gives the run's status with the stack top it stopped at.
+    fn run_code(code: Vec<bytecode::Instruction>) -> (ExecStatus, i32) {
+        run_chunk(bytecode::Chunk::new(Vec::new(), code))
+    }
+
+    fn run_chunk(chunk: bytecode::Chunk) -> (ExecStatus, i32) {
+        let entry_proc = Procedure::new(chunk, 0, 1);
+        let mut engine = EngineBuilder::new().build(Program::new(vec![entry_proc], Vec::new(), 0));
+        engine.set_output_sinks(Box::new(SharedSink::default()), Box::new(SharedSink::default()));
+
+        let status = engine.run(&Bundle::new());
+
+        (status, engine.rsp)
+    }
+
+    fn native_call_with_two(engine_ref: &mut Engine) -> ExecStatus {
+        let Some(Value::Int(callback_id)) = engine_ref.pop_off() else {
+            return ExecStatus::BadArgs;
+        };
+
+        let result = engine_ref.call_function(callback_id, &[Value::Int(1), Value::Int(2)]);
+
+        if matches!(result, Value::Empty()) {
+            return engine_ref.get_status();
+        }
+
+        engine_ref.push_in(result);
+
+        ExecStatus::Ok
+    }
+
+    #[test]
+    fn callbacks_given_extra_args_are_bad_args() {
+        let mut natives = Bundle::new();
+        natives.register_native("call_with_two", Box::new(native_call_with_two), 1);
+
+        let source = "foreign call_with_two(f: callback): int;\n\nfun twice(n: int): int {\n    return n * 2;\n}\n\nfun main(): int {\n    return call_with_two(twice);\n}\n";
+        let (status, _, diagnostics) = run_source(source, &natives);
+
+        assert!(status == ExecStatus::BadArgs);
+        assert!(diagnostics.contains("takes 1 argument(s), but 2 were given."), "{diagnostics}");
+    }
+
+    #[test]
+    fn printed_values_reach_the_output_sink() {
+        let mut natives = Bundle::new();
+        natives.register_native("print_val", Box::new(crate::utils::loxie_stdio::native_print_val), 1);
+
+        let source = "foreign print_val(arg: int): bool;\n\nfun main(): int {\n    print_val(42);\n    print_val(6 + 1);\n\n    return 0;\n}\n";
+        let (status, output, diagnostics) = run_source(source, &natives);
+
+        assert!(status == ExecStatus::Ok);
+        assert_eq!(output, "42\n7\n");
+        assert!(diagnostics.is_empty(), "{diagnostics}");
+    }
+
+    #[test]
+    fn ops_on_missing_operands_underflow() {
+        use bytecode::Instruction;
+
+        assert!(run_code(vec![Instruction::Pop, Instruction::Leave]) == (ExecStatus::StackUnderflow, -1));
+        assert!(run_code(vec![Instruction::Not, Instruction::Leave]) == (ExecStatus::StackUnderflow, -1));
+        assert!(run_code(vec![Instruction::Add, Instruction::Leave]) == (ExecStatus::StackUnderflow, -1));
+
+        // NOTE: an op missing only its lhs must not pop the rhs it found.
+        assert!(run_code(vec![Instruction::PushTrue, Instruction::Add, Instruction::Leave]) == (ExecStatus::StackUnderflow, 0));
+        assert!(run_code(vec![Instruction::PushTrue, Instruction::CompareLt, Instruction::Leave]) == (ExecStatus::StackUnderflow, 0));
+    }
+
+    fn native_mark_ran(engine_ref: &mut Engine) -> ExecStatus {
+        if write!(engine_ref.get_output_mut(), "ran").is_err() {
+            return ExecStatus::NotOk;
+        }
+
+        engine_ref.push_in(Value::Int(0));
+
+        ExecStatus::Ok
+    }
+
+    #[test]
+    fn oversized_preloads_stop_the_run_before_any_code() {
+        let mut natives = Bundle::new();
+        natives.register_native("mark_ran", Box::new(native_mark_ran), 0);
+
+        let code = vec![bytecode::Instruction::NativeCall((ArgMode::NativeId, 0)), bytecode::Instruction::Leave];
+        let entry_proc = Procedure::new(bytecode::Chunk::new(Vec::new(), code), 0, 1);
+        let preloads = (0..3).map(|n| HeapValue::Varchar(format!("t{n}"))).collect();
+
+        let mut engine = EngineBuilder::new().max_heap_cells(2).build(Program::new(vec![entry_proc], preloads, 0));
+        let output = SharedSink::default();
+        let diagnostics = SharedSink::default();
+        engine.set_output_sinks(Box::new(output.clone()), Box::new(diagnostics.clone()));
+
+        assert!(engine.run(&natives) == ExecStatus::HeapExhausted);
+        assert!(output.text().is_empty(), "{}", output.text());
+        assert!(diagnostics.text().contains("heap is out of memory for the program's 3 preloaded literals."), "{}", diagnostics.text());
+
+        assert!(matches!(engine.run_procedure(&natives, 0, &[]), Value::Empty()));
+        assert!(output.text().is_empty(), "{}", output.text());
+    }
+
+    #[test]
+    fn only_jumps_on_temporaries_underflow() {
+        use bytecode::Instruction;
+
+        let constants = vec![Value::Bool(true), Value::Bool(false), Value::Int(0)];
+
+        for jump in [Instruction::JumpIf((ArgMode::ConstantId, 0), (ArgMode::CodeOffset, 2)), Instruction::JumpElse((ArgMode::ConstantId, 1), (ArgMode::CodeOffset, 2))] {
+            let code = vec![jump, Instruction::Pop, Instruction::Return((ArgMode::ConstantId, 2))];
+
+            assert!(run_chunk(bytecode::Chunk::new(constants.clone(), code)) == (ExecStatus::Ok, 0));
+        }
+
+        assert!(run_code(vec![Instruction::JumpIf((ArgMode::StackOffset, 0), (ArgMode::CodeOffset, 1)), Instruction::Leave]) == (ExecStatus::StackUnderflow, -1));
+    }
+}
@@ -8,12 +8,21 @@ pub const TOTAL_STRING_OVERHEAD: usize = BASE_STRING_OVERHEAD + PRESET_STRING_CO
 const MAX_HEAP_OVERHEAD: usize = i16::MAX as usize * TOTAL_STRING_OVERHEAD;
 const DUD_OVERHEAD: usize = 1;
 
+/// NOTE: This is how many cells the largest heap has, so heap IDs of preloaded literals must stay below it.
+pub const MAX_HEAP_SLOTS: i32 = 1 + (MAX_HEAP_OVERHEAD / TOTAL_STRING_OVERHEAD) as i32;
+
+/// NOTE: This caps how many doomed cells are collected per sweep, so no single instruction pays for reclaiming the whole heap.
+pub const SWEEP_BATCH_LIMIT: usize = 8;
+
+/// NOTE: This is how many free cells a heap needs before compacting it is worthwhile, see `ObjectHeap::is_fragmented`.
+const COMPACTION_FREE_MIN: usize = 16;
+
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq)]
 pub enum ObjectTag {
     None,
     Varchar,
-    // Array,
+    Array,
     Instance,
 }
 
@@ -21,7 +30,7 @@ pub enum ObjectTag {
 pub enum HeapValue {
     Empty(),
     Varchar(String),
-    // Array(Vec<Value>),
+    Array(Vec<Value>),
     Instance(Vec<Value>),
 }
 
@@ -31,11 +40,23 @@ impl Default for HeapValue {
     }
 }
 
+/// NOTE: Resolves a varchar or array position, where a negative one counts back from the end e.g `-1` for the last item. Positions out of range either way give `None`.
+fn resolve_position(pos: i32, len: usize) -> Option<usize> {
+    let resolved_pos = if pos < 0 { pos as i64 + len as i64 } else { pos as i64 };
+
+    if resolved_pos < 0 || resolved_pos >= len as i64 {
+        return None;
+    }
+
+    Some(resolved_pos as usize)
+}
+
 impl HeapValue {
     pub fn get_object_tag(&self) -> ObjectTag {
         match self {
             Self::Empty() => ObjectTag::None,
             Self::Varchar(_) => ObjectTag::Varchar,
+            Self::Array(_) => ObjectTag::Array,
             Self::Instance(_) => ObjectTag::Instance,
         }
     }
@@ -63,51 +84,77 @@ impl HeapValue {
         -1
     }
 
-    pub fn try_varchar_get(&self, pos: i32) -> u8 {
-        if let Self::Varchar(s) = self {
-            if s.is_ascii() && pos >= 0 && pos < s.len() as i32 {
-                return s.as_bytes()[pos as usize];
-            }
+    pub fn try_varchar_get(&self, pos: i32) -> Option<u8> {
+        if let Self::Varchar(s) = self
+            && s.is_ascii()
+            && let Some(char_pos) = resolve_position(pos, s.len()) {
+            return Some(s.as_bytes()[char_pos]);
         }
 
-        0
+        None
     }
 
     pub fn try_varchar_set(&mut self, pos: i32, c: char) -> bool {
-        if let Self::Varchar(s) = self {
-            if s.is_ascii() {
-                unsafe {
-                   s.as_bytes_mut()[pos as usize] = c as u8;
-                }
-               return true;
+        if let Self::Varchar(s) = self
+            && s.is_ascii()
+            && let Some(char_pos) = resolve_position(pos, s.len()) {
+            unsafe {
+                s.as_bytes_mut()[char_pos] = c as u8;
             }
+            return true;
         }
 
         false
     }
 
     pub fn try_varchar_push(&mut self, c: char) -> bool {
-        if let Self::Varchar(s) = self {
-            if s.is_ascii() {
-                s.push(c);
-                return true
-            }
+        if let Self::Varchar(s) = self
+            && s.is_ascii() {
+            s.push(c);
+            return true
         }
 
         false
     }
 
     pub fn try_varchar_pop(&mut self) -> u8 {
-        if let Self::Varchar(s) = self {
-            if s.is_ascii() {
-                let ascii_c = s.pop().unwrap_or('\0');
-                return ascii_c as u8;
-            }
+        if let Self::Varchar(s) = self
+            && s.is_ascii() {
+            let ascii_c = s.pop().unwrap_or('\0');
+            return ascii_c as u8;
         }
 
         0
     }
 
+    pub fn try_array_len(&self) -> i32 {
+        if let Self::Array(items) = self {
+            return items.len() as i32;
+        }
+
+        -1
+    }
+
+    pub fn try_array_get(&self, pos: i32) -> Option<Value> {
+        if let Self::Array(items) = self
+            && let Some(item_pos) = resolve_position(pos, items.len()) {
+            return items.get(item_pos).copied();
+        }
+
+        None
+    }
+
+    pub fn try_array_set(&mut self, pos: i32, item: Value) -> bool {
+        if let Self::Array(items) = self
+            && let Some(item_pos) = resolve_position(pos, items.len())
+            && let Some(item_ref) = items.get_mut(item_pos) {
+            *item_ref = item;
+            return true;
+        }
+
+        false
+    }
+
     pub fn try_ref_instance_field(&self, field_pos: i32) -> Option<&Value> {
         if let Self::Instance(fields) = self {
             return fields.get(field_pos as usize);
@@ -130,6 +177,11 @@ impl Display for HeapValue {
         match self {
             Self::Empty() => write!(f, "HeapValue(empty)"),
             Self::Varchar(s_val) => write!(f, "{s_val}"),
+            Self::Array(items) => {
+                let item_texts: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+
+                write!(f, "[{}]", item_texts.join(", "))
+            },
             Self::Instance(vals) => {
                 let mut building_str = String::default();
 
@@ -148,6 +200,7 @@ impl Display for HeapValue {
 pub struct HeapCell {
     value: HeapValue,
     ref_count: i16,
+    sweep_queued: bool,
 }
 
 impl HeapCell {
@@ -155,6 +208,16 @@ impl HeapCell {
         Self {
             value: value_arg,
             ref_count: 1,
+            sweep_queued: false,
+        }
+    }
+
+    /// NOTE: Fresh objects start without owners, so the first stack slot / frame / field holding it brings the count to 1.
+    pub fn new_unowned(value_arg: HeapValue) -> Self {
+        Self {
+            value: value_arg,
+            ref_count: 0,
+            sweep_queued: false,
         }
     }
 
@@ -181,6 +244,9 @@ impl HeapCell {
 
 pub struct ObjectHeap {
     free_list: VecDeque<i32>,
+
+    /// NOTE: Holds IDs of cells whose ref-count dropped to 0 since the last sweep. Sweeps only visit these cells instead of scanning every stack slot.
+    doomed_list: VecDeque<i32>,
     entries: Vec<HeapCell>,
     overhead_limit: usize,
     overhead: usize,
@@ -193,12 +259,11 @@ impl ObjectHeap {
         let checked_max_overhead: usize = if max_overhead <= MAX_HEAP_OVERHEAD { max_overhead } else { MAX_HEAP_OVERHEAD };
         let calculated_slot_n = 1 + checked_max_overhead / TOTAL_STRING_OVERHEAD;
 
-        let mut temp_entries = Vec::<HeapCell>::with_capacity(calculated_slot_n);
-        temp_entries.resize(calculated_slot_n, HeapCell::new(HeapValue::Empty()));
-
+        // NOTE: cells are only added as they're first used, so a compacted heap can give back the memory of its freed tail.
         Self {
             free_list: VecDeque::<i32>::new(),
-            entries: temp_entries,
+            doomed_list: VecDeque::<i32>::new(),
+            entries: Vec::<HeapCell>::new(),
             overhead_limit: max_overhead,
             overhead: 0,
             slot_limit: calculated_slot_n as i32,
@@ -206,12 +271,31 @@ impl ObjectHeap {
         }
     }
 
+    /// NOTE: Lowers how many cells may be live at once from what the heap size allows, e.g `8` to make tests run out of cells quickly. Preloaded literals still need their cells.
+    pub fn set_cell_limit(&mut self, max_cells: i32) {
+        self.slot_limit = self.slot_limit.min(max_cells.max(1));
+    }
+
+    pub fn get_cell_limit(&self) -> i32 {
+        self.slot_limit
+    }
+
+    /// NOTE: Counts every cell not on the free list, including doomed cells that are not swept yet.
+    pub fn get_live_count(&self) -> i32 {
+        self.next_id - self.free_list.len() as i32
+    }
+
     pub fn is_ripe_for_sweep(&self) -> bool {
         self.overhead > self.overhead_limit
     }
 
     pub fn preload_cell_at(&mut self, target_id: i32, value: HeapValue) -> bool {
+        if target_id < 0 {
+            return false;
+        }
+
         if let Some(target_ref) = self.entries.get_mut(target_id as usize) {
+            self.overhead = self.overhead.saturating_sub(target_ref.get_value().get_overhead());
             self.overhead += value.get_overhead();
             *target_ref.get_value_mut() = value;
             return true;
@@ -229,77 +313,221 @@ impl ObjectHeap {
     }
 
     pub fn try_create_cell(&mut self, tag: ObjectTag) -> i32 {
-        let next_free_slot_opt = self.free_list.pop_front();
-        let mut has_reclaimed_slot = false;
+        // NOTE: a full heap gets one last chance by collecting every doomed cell before failing the allocation.
+        if self.free_list.is_empty() && self.next_id >= self.slot_limit {
+            self.sweep_doomed(usize::MAX);
+        }
 
-        let created_slot_id = if let Some(next_free_id) = next_free_slot_opt {
-            has_reclaimed_slot = true;
+        let created_slot_id = if let Some(next_free_id) = self.free_list.pop_front() {
             next_free_id
-        } else if self.next_id <= self.slot_limit {
-            has_reclaimed_slot = false;
+        } else if self.next_id < self.slot_limit {
             let temp_next_id = self.next_id;
             self.next_id += 1;
             temp_next_id
         } else { -1 };
 
         if created_slot_id != -1 {
-            #[allow(clippy::single_match)]
-            match tag {
-                ObjectTag::Varchar => {
-                    let temp = HeapValue::Varchar(String::new());
-                    let temp_size = temp.get_overhead();
-
-                    if !has_reclaimed_slot {
-                        *self.entries.get_mut(created_slot_id as usize).unwrap().get_value_mut() = temp;
-                    } else {
-                        *self.entries.get_mut(created_slot_id as usize).unwrap() = HeapCell::new(temp);
-                    }
-                    self.overhead += temp_size;
-                },
-                ObjectTag::Instance => {
-                    let temp_instance_dud = HeapValue::Empty();
-
-                    if !has_reclaimed_slot {
-                        *self.entries.get_mut(created_slot_id as usize).unwrap().get_value_mut() = temp_instance_dud.clone();
-                    } else {
-                        *self.entries.get_mut(created_slot_id as usize).unwrap() = HeapCell::new(temp_instance_dud.clone());
-                    }
+            let temp = match tag {
+                ObjectTag::Varchar => HeapValue::Varchar(String::new()),
+                _ => HeapValue::Empty(),
+            };
 
-                    self.overhead += temp_instance_dud.get_overhead();
-                },
-                _ => {},
+            self.overhead += temp.get_overhead();
+
+            if let Some(reused_cell_ref) = self.entries.get_mut(created_slot_id as usize) {
+                *reused_cell_ref = HeapCell::new_unowned(temp);
+            } else {
+                self.entries.push(HeapCell::new_unowned(temp));
             }
         }
 
         created_slot_id
     }
 
-    pub fn try_collect_cell(&mut self, id: i32) {
-        if self.free_list.binary_search(&id).is_ok() || id >= self.next_id {
-            return;
+    pub fn retain_cell(&mut self, id: i32) -> bool {
+        if id < 0 {
+            return false;
+        }
+
+        if let Some(cell_ref) = self.entries.get_mut(id as usize) {
+            cell_ref.inc_rc();
+            return true;
+        }
+
+        false
+    }
+
+    /// NOTE: Drops one reference to a cell, queueing it for a later sweep once no owners remain.
+    pub fn release_cell(&mut self, id: i32) -> bool {
+        if id < 0 {
+            return false;
+        }
+
+        if let Some(cell_ref) = self.entries.get_mut(id as usize) {
+            cell_ref.dec_rc();
+
+            if !cell_ref.is_live() && !cell_ref.sweep_queued {
+                cell_ref.sweep_queued = true;
+                self.doomed_list.push_back(id);
+            }
+
+            return true;
+        }
+
+        false
+    }
+
+    pub fn get_doomed_count(&self) -> usize {
+        self.doomed_list.len()
+    }
+
+    /// NOTE: Collects at most `batch_limit` doomed cells, returning how many were actually reclaimed. Revived cells are skipped.
+    pub fn sweep_doomed(&mut self, batch_limit: usize) -> usize {
+        let mut reclaimed_count: usize = 0;
+        let mut visited_count: usize = 0;
+
+        while visited_count < batch_limit {
+            let Some(doomed_id) = self.doomed_list.pop_front() else {
+                break;
+            };
+
+            visited_count += 1;
+            self.entries.get_mut(doomed_id as usize).unwrap().sweep_queued = false;
+
+            if self.try_collect_cell(doomed_id) {
+                reclaimed_count += 1;
+            }
+        }
+
+        reclaimed_count
+    }
+
+    pub fn try_collect_cell(&mut self, id: i32) -> bool {
+        if id < 0 || id >= self.next_id {
+            return false;
         }
 
         if self.entries.get_mut(id as usize).unwrap().is_live() {
-            return;
+            return false;
         }
 
         let dud_cell = HeapCell::new(HeapValue::Empty());
-        let overhead_dec_n = self.entries.get_mut(id as usize).unwrap().get_value_mut().get_overhead();
-        self.overhead -= overhead_dec_n;
+        let dead_value = std::mem::replace(self.entries.get_mut(id as usize).unwrap(), dud_cell).value;
+        self.overhead = self.overhead.saturating_sub(dead_value.get_overhead());
+
+        // NOTE: objects held only by the dead instance's fields lose an owner too.
+        if let HeapValue::Instance(dead_fields) = dead_value {
+            for field_value in dead_fields {
+                if let Value::HeapRef(field_ref_id) = field_value {
+                    self.release_cell(field_ref_id);
+                }
+            }
+        }
+
+        self.free_list.push_back(id);
 
-        *self.entries.get_mut(id as usize).unwrap() = dud_cell;
+        true
+    }
 
-        if id == self.next_id - 1 {
-            self.next_id -= 1;
+    /// NOTE: Checks if at least half of the used cells are free, which makes compaction worthwhile e.g after a full collection reclaimed many short-lived objects.
+    pub fn is_fragmented(&self) -> bool {
+        self.free_list.len() >= COMPACTION_FREE_MIN && self.free_list.len() * 2 >= self.next_id as usize
+    }
+
+    /**
+     * ### ABOUT
+     * Moves every live cell down into the lowest free IDs while keeping the cells in order, then drops the freed tail of cells. Since preloaded literals are never freed and take the lowest IDs, they stay at the IDs the bytecode refers to.
+     * ### CAVEATS
+     * Doomed cells must be swept beforehand. The result maps each old ID to its new ID (or `-1` for free cells), which the owner of the heap must use to update all `HeapRef`s outside of the heap e.g on the stack. References within instances are updated here.
+     */
+    pub fn compact(&mut self) -> Vec<i32> {
+        let mut is_free_id = vec![false; self.next_id as usize];
+
+        for free_id in &self.free_list {
+            is_free_id[*free_id as usize] = true;
+        }
+
+        let mut new_ids = vec![-1; self.next_id as usize];
+        let mut next_low_id: i32 = 0;
+
+        for old_id in 0..self.next_id {
+            if is_free_id[old_id as usize] {
+                continue;
+            }
+
+            new_ids[old_id as usize] = next_low_id;
+            self.entries.swap(old_id as usize, next_low_id as usize);
+            next_low_id += 1;
         }
 
-        self.free_list.push_front(id);
+        for cell in self.entries.iter_mut().take(next_low_id as usize) {
+            if let HeapValue::Instance(fields) = cell.get_value_mut() {
+                for field_value in fields.iter_mut() {
+                    if let Value::HeapRef(field_ref_id) = field_value {
+                        *field_ref_id = new_ids[*field_ref_id as usize];
+                    }
+                }
+            }
+        }
+
+        self.entries.truncate(next_low_id as usize);
+        self.entries.shrink_to_fit();
+        self.free_list.clear();
+        self.next_id = next_low_id;
+
+        new_ids
     }
 
     pub fn force_collect_all(&mut self) {
         self.entries.clear();
         self.free_list.clear();
+        self.doomed_list.clear();
         self.overhead = 0;
         self.next_id = 0;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// NOTE: Makes a heap with `count` owned cells already released, so all of them wait on the doomed list.
+    fn heap_with_doomed(count: usize) -> (ObjectHeap, Vec<i32>) {
+        let mut heap = ObjectHeap::new(4 * count * TOTAL_STRING_OVERHEAD);
+        let mut cell_ids = Vec::new();
+
+        for _ in 0..count {
+            let cell_id = heap.try_create_cell(ObjectTag::Varchar);
+            assert!(heap.retain_cell(cell_id));
+            assert!(heap.release_cell(cell_id));
+            cell_ids.push(cell_id);
+        }
+
+        (heap, cell_ids)
+    }
+
+    #[test]
+    fn sweeps_reclaim_at_most_one_batch() {
+        let doomed_total = 3 * SWEEP_BATCH_LIMIT + 2;
+        let (mut heap, _) = heap_with_doomed(doomed_total);
+        let mut sweep_sizes = Vec::new();
+
+        while heap.get_doomed_count() > 0 {
+            sweep_sizes.push(heap.sweep_doomed(SWEEP_BATCH_LIMIT));
+        }
+
+        assert_eq!(sweep_sizes, vec![SWEEP_BATCH_LIMIT, SWEEP_BATCH_LIMIT, SWEEP_BATCH_LIMIT, 2]);
+        assert_eq!(heap.get_live_count(), 0);
+    }
+
+    #[test]
+    fn revived_cells_survive_their_sweep() {
+        let (mut heap, cell_ids) = heap_with_doomed(SWEEP_BATCH_LIMIT);
+        assert!(heap.retain_cell(cell_ids[0]));
+
+        assert_eq!(heap.sweep_doomed(SWEEP_BATCH_LIMIT), SWEEP_BATCH_LIMIT - 1);
+        assert_eq!(heap.get_doomed_count(), 0);
+        assert_eq!(heap.get_live_count(), 1);
+        assert!(heap.get_cell(cell_ids[0]).unwrap().is_live());
+    }
+}
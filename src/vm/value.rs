@@ -1,5 +1,18 @@
 use std::fmt::{Display, Formatter, Result};
 
+/// Describes why an arithmetic operation on `Value`s failed.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ArithError {
+    /// the operand types were mismatched or non-numeric e.g `1 / true`
+    TypeMismatch,
+
+    /// the divisor was zero e.g `1 / 0`
+    ZeroDivision,
+
+    /// the int result went out of range, only reported by the `checked_*` operations
+    Overflow,
+}
+
 #[derive(Clone, Copy)]
 pub enum Value {
     Empty(),
@@ -7,18 +20,32 @@ pub enum Value {
     Char(u8),
     Int(i32),
     Float(f32),
+    Long(i64),
 
     /// References a handle to an interned `varchar` / other heap typed value.
     HeapRef(i32),
 }
 
+/// NOTE: Control characters & other non-printable bytes are shown as escapes e.g `'\n'` or `'\x1b'`, so they stay visible in output.
+fn write_char_value(f: &mut Formatter<'_>, c: u8) -> Result {
+    match c {
+        b'\n' => write!(f, "'\\n'"),
+        b'\t' => write!(f, "'\\t'"),
+        b'\r' => write!(f, "'\\r'"),
+        b'\0' => write!(f, "'\\0'"),
+        _ if c.is_ascii_graphic() || c == b' ' => write!(f, "'{}'", c as char),
+        _ => write!(f, "'\\x{c:02x}'"),
+    }
+}
+
 impl Display for Value {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
             Self::Bool(flag) => write!(f, "{}", *flag),
-            Self::Char(c) => write!(f, "'{}'", *c as char),
+            Self::Char(c) => write_char_value(f, *c),
             Self::Int(value) => write!(f, "{}", *value),
             Self::Float(value) => write!(f, "{}", *value),
+            Self::Long(value) => write!(f, "{}", *value),
             Self::HeapRef(id) => write!(f, "object-{}", *id),
             _ => write!(f, "(empty)"),
         }
@@ -61,6 +88,15 @@ impl From<Value> for i32 {
     }
 }
 
+impl From<Value> for i64 {
+    fn from(val: Value) -> Self {
+        match val {
+            Value::Long(value) => value,
+            _ => -1,
+        }
+    }
+}
+
 impl From<Value> for f32 {
     fn from(val: Value) -> Self {
         match val {
@@ -79,6 +115,7 @@ impl Value {
             Self::Int(_) => 3,
             Self::Float(_) => 4,
             Self::HeapRef(_) => 5,
+            Self::Long(_) => 6,
         }
     }
 
@@ -109,6 +146,7 @@ impl Value {
             Self::Char(value) => *value != 0,
             Self::Int(value) => *value != 0,
             Self::Float(value) => *value != 0.0f32,
+            Self::Long(value) => *value != 0,
             Self::HeapRef(id) => *id != -1,
         }
     }
@@ -116,11 +154,14 @@ impl Value {
     pub fn negate(&mut self) {
         match self {
             Self::Int(value) => {
-                *value = -*value;
+                *value = value.wrapping_neg();
             }
             Self::Float(value) => {
                 *value = -*value;
             }
+            Self::Long(value) => {
+                *value = value.wrapping_neg();
+            }
             _ => {}
         }
     }
@@ -128,11 +169,14 @@ impl Value {
     pub fn increment(&mut self) {
         match self {
             Self::Int(value) => {
-                *value += 1;
+                *value = value.wrapping_add(1);
             }
             Self::Float(value) => {
                 *value += 1.0f32;
             }
+            Self::Long(value) => {
+                *value = value.wrapping_add(1);
+            }
             _ => {}
         }
     }
@@ -140,15 +184,49 @@ impl Value {
     pub fn decrement(&mut self) {
         match self {
             Self::Int(value) => {
-                *value -= 1;
+                *value = value.wrapping_sub(1);
             }
             Self::Float(value) => {
                 *value -= 1.0f32;
             }
+            Self::Long(value) => {
+                *value = value.wrapping_sub(1);
+            }
             _ => {}
         }
     }
 
+    /// NOTE: The `checked_*` steps report int & long overflows instead of wrapping them like `negate`, `increment`, and `decrement`, leaving the value as it was.
+    pub fn checked_negate(&mut self) -> std::result::Result<(), ArithError> {
+        match self {
+            Self::Int(value) => *value = value.checked_neg().ok_or(ArithError::Overflow)?,
+            Self::Long(value) => *value = value.checked_neg().ok_or(ArithError::Overflow)?,
+            _ => self.negate(),
+        }
+
+        Ok(())
+    }
+
+    pub fn checked_increment(&mut self) -> std::result::Result<(), ArithError> {
+        match self {
+            Self::Int(value) => *value = value.checked_add(1).ok_or(ArithError::Overflow)?,
+            Self::Long(value) => *value = value.checked_add(1).ok_or(ArithError::Overflow)?,
+            _ => self.increment(),
+        }
+
+        Ok(())
+    }
+
+    pub fn checked_decrement(&mut self) -> std::result::Result<(), ArithError> {
+        match self {
+            Self::Int(value) => *value = value.checked_sub(1).ok_or(ArithError::Overflow)?,
+            Self::Long(value) => *value = value.checked_sub(1).ok_or(ArithError::Overflow)?,
+            _ => self.decrement(),
+        }
+
+        Ok(())
+    }
+
     pub fn is_equal(&self, rhs: &Self) -> bool {
         if !self.check_type_code(rhs) {
             return false;
@@ -160,6 +238,7 @@ impl Value {
             Self::Char(value) => *value == (*rhs).into(),
             Self::Int(value) => *value == (*rhs).into(),
             Self::Float(value) => *value == (*rhs).into(),
+            Self::Long(value) => *value == (*rhs).into(),
             _ => {
                 self.is_same_ref(rhs)
             },
@@ -179,6 +258,7 @@ impl Value {
             Self::Int(value) => *value < (*rhs).into(),
             Self::Char(value) => *value < (*rhs).into(),
             Self::Float(value) => *value < (*rhs).into(),
+            Self::Long(value) => *value < (*rhs).into(),
             _ => false,
         }
     }
@@ -192,6 +272,36 @@ impl Value {
             Self::Int(value) => *value > (*rhs).into(),
             Self::Char(value) => *value > (*rhs).into(),
             Self::Float(value) => *value > (*rhs).into(),
+            Self::Long(value) => *value > (*rhs).into(),
+            _ => false,
+        }
+    }
+
+    /// NOTE: This isn't `!is_greater()`, as a NaN float is neither lesser, equal, nor greater than anything.
+    pub fn is_lesser_equal(&self, rhs: &Self) -> bool {
+        if !self.check_type_code(rhs) {
+            return false;
+        }
+
+        match self {
+            Self::Int(value) => *value <= (*rhs).into(),
+            Self::Char(value) => *value <= (*rhs).into(),
+            Self::Float(value) => *value <= (*rhs).into(),
+            Self::Long(value) => *value <= (*rhs).into(),
+            _ => false,
+        }
+    }
+
+    pub fn is_greater_equal(&self, rhs: &Self) -> bool {
+        if !self.check_type_code(rhs) {
+            return false;
+        }
+
+        match self {
+            Self::Int(value) => *value >= (*rhs).into(),
+            Self::Char(value) => *value >= (*rhs).into(),
+            Self::Float(value) => *value >= (*rhs).into(),
+            Self::Long(value) => *value >= (*rhs).into(),
             _ => false,
         }
     }
@@ -206,7 +316,7 @@ impl Value {
                 let lhs_int = *value;
                 let rhs_int: i32 = (*rhs).into();
 
-                Value::Int(lhs_int + rhs_int)
+                Value::Int(lhs_int.wrapping_add(rhs_int))
             }
             Self::Float(value) => {
                 let lhs_float = *value;
@@ -214,6 +324,12 @@ impl Value {
 
                 Value::Float(lhs_float + rhs_float)
             }
+            Self::Long(value) => {
+                let lhs_long = *value;
+                let rhs_long: i64 = (*rhs).into();
+
+                Value::Long(lhs_long.wrapping_add(rhs_long))
+            }
             _ => Value::Empty(),
         }
     }
@@ -228,7 +344,7 @@ impl Value {
                 let lhs_int = *value;
                 let rhs_int: i32 = (*rhs).into();
 
-                Value::Int(lhs_int - rhs_int)
+                Value::Int(lhs_int.wrapping_sub(rhs_int))
             }
             Self::Float(value) => {
                 let lhs_float = *value;
@@ -236,6 +352,12 @@ impl Value {
 
                 Value::Float(lhs_float - rhs_float)
             }
+            Self::Long(value) => {
+                let lhs_long = *value;
+                let rhs_long: i64 = (*rhs).into();
+
+                Value::Long(lhs_long.wrapping_sub(rhs_long))
+            }
             _ => Value::Empty(),
         }
     }
@@ -250,7 +372,7 @@ impl Value {
                 let lhs_int = *value;
                 let rhs_int: i32 = (*rhs).into();
 
-                Value::Int(lhs_int * rhs_int)
+                Value::Int(lhs_int.wrapping_mul(rhs_int))
             }
             Self::Float(value) => {
                 let lhs_float = *value;
@@ -258,18 +380,80 @@ impl Value {
 
                 Value::Float(lhs_float * rhs_float)
             }
+            Self::Long(value) => {
+                let lhs_long = *value;
+                let rhs_long: i64 = (*rhs).into();
+
+                Value::Long(lhs_long.wrapping_mul(rhs_long))
+            }
             _ => Value::Empty(),
         }
     }
 
-    /// NOTE: Division is checked for illegal operations such as returning `Value::Empty`` on `1 / 0`.
-    pub fn div(&self, rhs: &Self) -> Value {
-        if !self.check_type_code(rhs) {
-            return Value::Empty();
+    /// NOTE: The `checked_*` operations report int overflows instead of wrapping them like `add`, `sub`, `mul`, and `div`.
+    pub fn checked_add(&self, rhs: &Self) -> std::result::Result<Value, ArithError> {
+        if let (Self::Int(lhs_int), Self::Int(rhs_int)) = (self, rhs) {
+            return lhs_int.checked_add(*rhs_int).map(Value::Int).ok_or(ArithError::Overflow);
         }
 
-        if !rhs.test() {
-            return Value::Empty();
+        if let (Self::Long(lhs_long), Self::Long(rhs_long)) = (self, rhs) {
+            return lhs_long.checked_add(*rhs_long).map(Value::Long).ok_or(ArithError::Overflow);
+        }
+
+        match self.add(rhs) {
+            Value::Empty() => Err(ArithError::TypeMismatch),
+            result => Ok(result),
+        }
+    }
+
+    pub fn checked_sub(&self, rhs: &Self) -> std::result::Result<Value, ArithError> {
+        if let (Self::Int(lhs_int), Self::Int(rhs_int)) = (self, rhs) {
+            return lhs_int.checked_sub(*rhs_int).map(Value::Int).ok_or(ArithError::Overflow);
+        }
+
+        if let (Self::Long(lhs_long), Self::Long(rhs_long)) = (self, rhs) {
+            return lhs_long.checked_sub(*rhs_long).map(Value::Long).ok_or(ArithError::Overflow);
+        }
+
+        match self.sub(rhs) {
+            Value::Empty() => Err(ArithError::TypeMismatch),
+            result => Ok(result),
+        }
+    }
+
+    pub fn checked_mul(&self, rhs: &Self) -> std::result::Result<Value, ArithError> {
+        if let (Self::Int(lhs_int), Self::Int(rhs_int)) = (self, rhs) {
+            return lhs_int.checked_mul(*rhs_int).map(Value::Int).ok_or(ArithError::Overflow);
+        }
+
+        if let (Self::Long(lhs_long), Self::Long(rhs_long)) = (self, rhs) {
+            return lhs_long.checked_mul(*rhs_long).map(Value::Long).ok_or(ArithError::Overflow);
+        }
+
+        match self.mul(rhs) {
+            Value::Empty() => Err(ArithError::TypeMismatch),
+            result => Ok(result),
+        }
+    }
+
+    pub fn checked_div(&self, rhs: &Self) -> std::result::Result<Value, ArithError> {
+        if let (Self::Int(lhs_int), Self::Int(rhs_int)) = (self, rhs)
+            && *rhs_int != 0 {
+            return lhs_int.checked_div(*rhs_int).map(Value::Int).ok_or(ArithError::Overflow);
+        }
+
+        if let (Self::Long(lhs_long), Self::Long(rhs_long)) = (self, rhs)
+            && *rhs_long != 0 {
+            return lhs_long.checked_div(*rhs_long).map(Value::Long).ok_or(ArithError::Overflow);
+        }
+
+        self.div(rhs)
+    }
+
+    /// NOTE: Division is checked for illegal operations, so type mismatches and zero divisors e.g `1 / 0` give distinct errors.
+    pub fn div(&self, rhs: &Self) -> std::result::Result<Value, ArithError> {
+        if !self.check_type_code(rhs) {
+            return Err(ArithError::TypeMismatch);
         }
 
         match self {
@@ -277,15 +461,64 @@ impl Value {
                 let lhs_int = *value;
                 let rhs_int: i32 = (*rhs).into();
 
-                Value::Int(lhs_int / rhs_int)
+                if rhs_int == 0 {
+                    return Err(ArithError::ZeroDivision);
+                }
+
+                Ok(Value::Int(lhs_int.wrapping_div(rhs_int)))
             }
             Self::Float(value) => {
                 let lhs_float = *value;
                 let rhs_float: f32 = (*rhs).into();
 
-                Value::Float(lhs_float / rhs_float)
+                if rhs_float == 0.0f32 {
+                    return Err(ArithError::ZeroDivision);
+                }
+
+                Ok(Value::Float(lhs_float / rhs_float))
             }
-            _ => Value::Empty(),
+            Self::Long(value) => {
+                let lhs_long = *value;
+                let rhs_long: i64 = (*rhs).into();
+
+                if rhs_long == 0 {
+                    return Err(ArithError::ZeroDivision);
+                }
+
+                Ok(Value::Long(lhs_long.wrapping_div(rhs_long)))
+            }
+            _ => Err(ArithError::TypeMismatch),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchecked_steps_wrap_ints() {
+        let mut most = Value::Int(i32::MAX);
+        let mut least = Value::Long(i64::MIN);
+
+        most.increment();
+        least.negate();
+
+        assert!(most.is_equal(&Value::Int(i32::MIN)));
+        assert!(least.is_equal(&Value::Long(i64::MIN)));
+    }
+
+    #[test]
+    fn checked_steps_report_overflows() {
+        let mut most = Value::Int(i32::MAX);
+        let mut least = Value::Long(i64::MIN);
+
+        assert!(most.checked_increment().is_err());
+        assert!(least.checked_decrement().is_err());
+        assert!(least.checked_negate().is_err());
+        assert!(most.is_equal(&Value::Int(i32::MAX)));
+
+        assert!(most.checked_decrement().is_ok());
+        assert!(most.is_equal(&Value::Int(i32::MAX - 1)));
+    }
+}
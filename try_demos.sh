@@ -2,7 +2,7 @@ OK_STATUS=0;
 FAIL_STATUS=1;
 
 handle_usage_exit() {
-    echo "Usage: ./try_demos.sh [help | demo]\n\tdemo [simple | negatives]: Run non-stdin demo programs. 0 -> positive checks, 1 -> negative checks";
+    echo "Usage: ./try_demos.sh [help | demo | check | filter | dot | warn | errors | compact | entry | crash | timings | stream | logs | host | summary | reuse | fuzz]\n\tdemo [simple | negatives]: Run non-stdin demo programs. 0 -> positive checks, 1 -> negative checks\n\tcheck [simple | library]: Only check demo programs with --check, which must pass without running them. Library modules have no main so they are checked with --lib\n\tfilter: Pipe each demo's .in file through it with --filter, which must print exactly its .out file\n\tdot: Dump each demo's CFGs with --dump-cfg-dot, which must contain every line of its .expect file\n\twarn: Check each warning demo with --check, whose diagnostics must match its .expect file\n\terrors: Check each error demo with --check, which must fail with all diagnostics of its .expect file\n\tcompact: Run each heap compaction demo with --compact-heap, which must exit with 0\n\tentry: Run each demo's function named in its .args file with --entry, whose output must contain every line of its .expect file\n\tcrash: Run each crashing demo, which must fail at runtime with all diagnostics of its .expect file\n\ttimings: Run each timing demo with --timings, whose phase timings must contain every line of its .expect file\n\tstream: Run each multi-module demo with and without --stream, whose outputs must match each other and contain every line of its .expect file\n\tlogs: Run each logging demo with --verbose, whose output must contain every line of its .expect file, while a normal run must print none of them\n\thost: Run each demo with the host strings of its .hosts file, whose output must contain every line of its .expect file\n\tsummary: Compile each demo with --summary and the flags of its optional .flags file without running it, whose program summary must contain every line of its .expect file\n\treuse: Run each demo and then the program named in its .then file on the same engine with --then, whose outputs must contain every line of its .expect file\n\tfuzz: Check truncated demo sources and random text, which must give diagnostics without crashing";
     exit $1;
 }
 
@@ -28,6 +28,346 @@ handle_simple_demos() {
     done
 }
 
+handle_check_demos() {
+    check_flags="--check";
+
+    if [[ "$1" = "library" ]]; then
+        check_flags="--check --lib";
+    fi
+
+    demos=$( find ./demos/$1/*.loxie );
+
+    for next_prog in $demos
+    do
+        check_output=$( cargo run -r -- $check_flags "$next_prog" );
+
+        if [[ $? -ne 0 || "$check_output" == *"Finished in"* ]]; then
+            echo "\033[1;31mFAILED check of demo '$next_prog'\033[0m";
+            exit 1;
+        else
+            echo "\033[1;32mCHECKED demo '$next_prog'\033[0m";
+        fi
+    done
+}
+
+handle_filter_demos() {
+    cargo build -q -r;
+
+    for next_prog in $( find ./demos/filter/*.loxie ); do
+        prog_stem="${next_prog%.loxie}";
+        filter_output=$( ./target/release/loxim --filter "$next_prog" < "$prog_stem.in" );
+
+        if [[ $? -ne 0 || "$filter_output" != "$( cat "$prog_stem.out" )" ]]; then
+            echo "\033[1;31mFAILED filter demo '$next_prog'\033[0m";
+            exit 1;
+        else
+            echo "\033[1;32mFILTERED demo '$next_prog'\033[0m";
+        fi
+    done
+}
+
+# NOTE: Each line of an .expect file must be found in the output, except lines starting with "!" which must not be found.
+check_expected_lines() {
+    while IFS= read -r expected_line; do
+        if [[ "$expected_line" == "!"* ]]; then
+            if [[ "$1" == *"${expected_line:1}"* ]]; then
+                echo "\033[1;31mFAILED demo '$3' with unwanted line: ${expected_line:1}\033[0m";
+                exit 1;
+            fi
+        elif [[ "$1" != *"$expected_line"* ]]; then
+            echo "\033[1;31mFAILED demo '$3' without line: $expected_line\033[0m";
+            exit 1;
+        fi
+    done < "$2";
+}
+
+handle_dot_demos() {
+    cargo build -q -r;
+
+    for next_prog in $( find ./demos/dot/*.loxie ); do
+        dot_output=$( ./target/release/loxim --dump-cfg-dot "$next_prog" );
+
+        if [[ $? -ne 0 ]]; then
+            echo "\033[1;31mFAILED dot demo '$next_prog'\033[0m";
+            exit 1;
+        fi
+
+        check_expected_lines "$dot_output" "${next_prog%.loxie}.expect" "$next_prog";
+        echo "\033[1;32mDUMPED demo '$next_prog'\033[0m";
+    done
+}
+
+handle_warning_demos() {
+    cargo build -q -r;
+
+    for next_prog in $( find ./demos/warnings/*.loxie ); do
+        warning_output=$( ./target/release/loxim --check "$next_prog" 2>&1 );
+
+        if [[ $? -ne 0 ]]; then
+            echo "\033[1;31mFAILED warning demo '$next_prog'\033[0m";
+            exit 1;
+        fi
+
+        check_expected_lines "$warning_output" "${next_prog%.loxie}.expect" "$next_prog";
+        echo "\033[1;32mWARNED demo '$next_prog'\033[0m";
+    done
+}
+
+# NOTE: Each .args file holds the entry function's name & its int args on one line.
+handle_entry_demos() {
+    cargo build -q -r;
+
+    for next_prog in $( find ./demos/entry/*.loxie ); do
+        prog_stem="${next_prog%.loxie}";
+        read -r entry_name entry_args < "$prog_stem.args";
+        entry_output=$( ./target/release/loxim --filter --entry $entry_name "$next_prog" $entry_args );
+
+        if [[ $? -ne 0 ]]; then
+            echo "\033[1;31mFAILED entry demo '$next_prog'\033[0m";
+            exit 1;
+        fi
+
+        check_expected_lines "$entry_output" "$prog_stem.expect" "$next_prog";
+        echo "\033[1;32mENTERED demo '$next_prog'\033[0m";
+    done
+}
+
+handle_crash_demos() {
+    cargo build -q -r;
+
+    for next_prog in $( find ./demos/crash/*.loxie ); do
+        crash_output=$( ./target/release/loxim --filter "$next_prog" 2>&1 );
+
+        if [[ $? -ne 1 ]]; then
+            echo "\033[1;31mFAILED crash demo '$next_prog'\033[0m";
+            exit 1;
+        fi
+
+        check_expected_lines "$crash_output" "${next_prog%.loxie}.expect" "$next_prog";
+        echo "\033[1;32mCRASHED demo '$next_prog'\033[0m";
+    done
+}
+
+# NOTE: Timings vary by run, so .expect files only name the phases e.g `Timing: parse took`.
+handle_timing_demos() {
+    cargo build -q -r;
+
+    for next_prog in $( find ./demos/timings/*.loxie ); do
+        timing_output=$( ./target/release/loxim --filter --timings "$next_prog" 2>&1 );
+
+        if [[ $? -ne 0 ]]; then
+            echo "\033[1;31mFAILED timing demo '$next_prog'\033[0m";
+            exit 1;
+        fi
+
+        check_expected_lines "$timing_output" "${next_prog%.loxie}.expect" "$next_prog";
+        echo "\033[1;32mTIMED demo '$next_prog'\033[0m";
+    done
+}
+
+handle_stream_demos() {
+    cargo build -q -r;
+
+    for next_prog in $( find ./demos/stream/*.loxie ); do
+        whole_output=$( ./target/release/loxim --filter "$next_prog" 2>&1 );
+        whole_status=$?;
+        streamed_output=$( ./target/release/loxim --filter --stream "$next_prog" 2>&1 );
+
+        if [[ $? -ne 0 || $whole_status -ne 0 ]]; then
+            echo "\033[1;31mFAILED stream demo '$next_prog'\033[0m";
+            exit 1;
+        fi
+
+        if [[ "$whole_output" != "$streamed_output" ]]; then
+            echo "\033[1;31mFAILED stream demo '$next_prog', whose streamed output differs\033[0m";
+            exit 1;
+        fi
+
+        check_expected_lines "$streamed_output" "${next_prog%.loxie}.expect" "$next_prog";
+        echo "\033[1;32mSTREAMED demo '$next_prog'\033[0m";
+    done
+}
+
+handle_log_demos() {
+    cargo build -q -r;
+
+    for next_prog in $( find ./demos/logs/*.loxie ); do
+        normal_output=$( ./target/release/loxim "$next_prog" 2>&1 );
+
+        if [[ $? -ne 0 ]]; then
+            echo "\033[1;31mFAILED log demo '$next_prog'\033[0m";
+            exit 1;
+        fi
+
+        while IFS= read -r progress_line; do
+            if [[ "$normal_output" == *"$progress_line"* ]]; then
+                echo "\033[1;31mFAILED log demo '$next_prog' with a progress line in a normal run: $progress_line\033[0m";
+                exit 1;
+            fi
+        done < "${next_prog%.loxie}.expect";
+
+        verbose_output=$( ./target/release/loxim --verbose "$next_prog" 2>&1 );
+        check_expected_lines "$verbose_output" "${next_prog%.loxie}.expect" "$next_prog";
+        echo "\033[1;32mLOGGED demo '$next_prog'\033[0m";
+    done
+}
+
+# NOTE: Each .hosts file holds one host string per line like <name>=<text>.
+handle_host_demos() {
+    cargo build -q -r;
+
+    for next_prog in $( find ./demos/host/*.loxie ); do
+        prog_stem="${next_prog%.loxie}";
+        host_flags=();
+
+        while IFS= read -r host_line; do
+            host_flags+=(--host-string "$host_line");
+        done < "$prog_stem.hosts";
+
+        host_output=$( ./target/release/loxim --filter "${host_flags[@]}" "$next_prog" );
+
+        if [[ $? -ne 0 ]]; then
+            echo "\033[1;31mFAILED host demo '$next_prog'\033[0m";
+            exit 1;
+        fi
+
+        check_expected_lines "$host_output" "$prog_stem.expect" "$next_prog";
+        echo "\033[1;32mHOSTED demo '$next_prog'\033[0m";
+    done
+}
+
+# NOTE: An optional .flags file holds more flags for --summary on one line e.g `--lib`.
+handle_summary_demos() {
+    cargo build -q -r;
+
+    for next_prog in $( find ./demos/summary/*.loxie ); do
+        summary_flags=();
+
+        if [[ -f "${next_prog%.loxie}.flags" ]]; then
+            read -r -a summary_flags < "${next_prog%.loxie}.flags";
+        fi
+
+        summary_output=$( ./target/release/loxim --summary "${summary_flags[@]}" "$next_prog" 2>&1 );
+
+        if [[ $? -ne 0 ]]; then
+            echo "\033[1;31mFAILED summary demo '$next_prog'\033[0m";
+            exit 1;
+        fi
+
+        check_expected_lines "$summary_output" "${next_prog%.loxie}.expect" "$next_prog";
+        echo "\033[1;32mSUMMARIZED demo '$next_prog'\033[0m";
+    done
+}
+
+handle_reuse_demos() {
+    cargo build -q -r;
+
+    for next_prog in $( find ./demos/reuse/*.loxie ); do
+        prog_stem="${next_prog%.loxie}";
+        reuse_output=$( ./target/release/loxim --filter --then "$( cat "$prog_stem.then" )" "$next_prog" );
+
+        if [[ $? -ne 0 ]]; then
+            echo "\033[1;31mFAILED reuse demo '$next_prog'\033[0m";
+            exit 1;
+        fi
+
+        check_expected_lines "$reuse_output" "$prog_stem.expect" "$next_prog";
+        echo "\033[1;32mREUSED engine for demo '$next_prog'\033[0m";
+    done
+}
+
+handle_compact_demos() {
+    cargo build -q -r;
+
+    for next_prog in $( find ./demos/compact/*.loxie ); do
+        ./target/release/loxim --compact-heap "$next_prog" > /dev/null;
+
+        if [[ $? -ne 0 ]]; then
+            echo "\033[1;31mFAILED compact demo '$next_prog'\033[0m";
+            exit 1;
+        fi
+
+        echo "\033[1;32mCOMPACTED demo '$next_prog'\033[0m";
+    done
+}
+
+handle_error_demos() {
+    cargo build -q -r;
+
+    for next_prog in $( find ./demos/errors/*.loxie ); do
+        error_output=$( ./target/release/loxim --check "$next_prog" 2>&1 );
+
+        if [[ $? -ne 1 ]]; then
+            echo "\033[1;31mFAILED error demo '$next_prog'\033[0m";
+            exit 1;
+        fi
+
+        check_expected_lines "$error_output" "${next_prog%.loxie}.expect" "$next_prog";
+        echo "\033[1;32mREJECTED demo '$next_prog'\033[0m";
+    done
+}
+
+check_fuzz_case() {
+    timeout 10 cargo run -q -r -- --check --lib "$FUZZ_CASE_PATH" > /dev/null 2>&1;
+    fuzz_status=$?;
+
+    # NOTE: Status 1 is a reported diagnostic, but anything else such as a Rust panic (101) or a timeout (124) means a crash.
+    if [[ $fuzz_status -ne 0 && $fuzz_status -ne 1 ]]; then
+        echo "\033[1;31mFAILED fuzz case of $1 with status $fuzz_status, whose input was:\033[0m";
+        cat "$FUZZ_CASE_PATH";
+        echo;
+        cp "$FUZZ_CASE_PATH" ./target/fuzz_failure.loxie;
+        exit 1;
+    fi
+}
+
+# NOTE: Writes up to 300 printable chars & newlines picked by $RANDOM, so the same seed always gives the same cases. This must not run in a subshell, or $RANDOM would repeat its picks every round.
+write_random_fuzz_case() {
+    fuzz_text="";
+    fuzz_text_len=$(( RANDOM % 300 + 1 ));
+
+    for (( char_i = 0; char_i < fuzz_text_len; char_i++ )); do
+        fuzz_text+="${FUZZ_CHARS:RANDOM % ${#FUZZ_CHARS}:1}";
+    done
+
+    printf '%s' "$fuzz_text" > "$FUZZ_CASE_PATH";
+}
+
+handle_fuzz_demos() {
+    FUZZ_CASE_PATH="./target/fuzz_case.loxie";
+    FUZZ_SEED="${FUZZ_SEED:-1421}";
+    FUZZ_CHARS=$'\n';
+    cargo build -q -r;
+
+    for (( char_code = 32; char_code < 127; char_code++ )); do
+        FUZZ_CHARS+=$( printf "\\x$( printf '%x' $char_code )" );
+    done
+
+    for next_prog in $( find ./demos/simple/*.loxie ./demos/negatives/*.loxie ./demos/library/*.loxie ); do
+        prog_size=$( wc -c < "$next_prog" );
+
+        for (( cut_pos = 1; cut_pos < prog_size; cut_pos += 5 )); do
+            head -c $cut_pos "$next_prog" > "$FUZZ_CASE_PATH";
+            check_fuzz_case "'$next_prog' cut at $cut_pos";
+        done
+
+        echo "\033[1;32mFUZZED demo '$next_prog'\033[0m";
+    done
+
+    # NOTE: Set FUZZ_SEED to replay or vary the random cases, which are otherwise the same every run.
+    RANDOM=$FUZZ_SEED;
+
+    for (( round = 0; round < 200; round++ )); do
+        write_random_fuzz_case;
+        printf 'fun main(): int { \xc3\xa9 ' | cat - "$FUZZ_CASE_PATH" > ./target/fuzz_case_utf8.loxie;
+        check_fuzz_case "random text #$round of seed $FUZZ_SEED";
+        mv ./target/fuzz_case_utf8.loxie "$FUZZ_CASE_PATH";
+        check_fuzz_case "random text #$round of seed $FUZZ_SEED with non-ASCII prefix";
+    done
+
+    echo "\033[1;32mFUZZED random text of seed $FUZZ_SEED\033[0m";
+}
+
 dispatch_action() {
     argc=$#;
     action="$1";
@@ -40,6 +380,36 @@ dispatch_action() {
         handle_usage_exit 0;
     elif [[ $action = "demo" && $argc -eq 2 ]]; then
         handle_simple_demos "$2";
+    elif [[ $action = "check" && $argc -eq 2 && ( "$2" = "simple" || "$2" = "library" ) ]]; then
+        handle_check_demos "$2";
+    elif [[ $action = "filter" && $argc -eq 1 ]]; then
+        handle_filter_demos;
+    elif [[ $action = "dot" && $argc -eq 1 ]]; then
+        handle_dot_demos;
+    elif [[ $action = "warn" && $argc -eq 1 ]]; then
+        handle_warning_demos;
+    elif [[ $action = "errors" && $argc -eq 1 ]]; then
+        handle_error_demos;
+    elif [[ $action = "compact" && $argc -eq 1 ]]; then
+        handle_compact_demos;
+    elif [[ $action = "entry" && $argc -eq 1 ]]; then
+        handle_entry_demos;
+    elif [[ $action = "crash" && $argc -eq 1 ]]; then
+        handle_crash_demos;
+    elif [[ $action = "timings" && $argc -eq 1 ]]; then
+        handle_timing_demos;
+    elif [[ $action = "stream" && $argc -eq 1 ]]; then
+        handle_stream_demos;
+    elif [[ $action = "logs" && $argc -eq 1 ]]; then
+        handle_log_demos;
+    elif [[ $action = "host" && $argc -eq 1 ]]; then
+        handle_host_demos;
+    elif [[ $action = "summary" && $argc -eq 1 ]]; then
+        handle_summary_demos;
+    elif [[ $action = "reuse" && $argc -eq 1 ]]; then
+        handle_reuse_demos;
+    elif [[ $action = "fuzz" && $argc -eq 1 ]]; then
+        handle_fuzz_demos;
     else
         handle_usage_exit 1;
     fi